@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use remindee_bot::{DateDivisor, DateInterval, DateRange};
+
+fn interval_range(months: u32, days: u32) -> DateRange {
+    DateRange {
+        from: NaiveDate::from_ymd_opt(2000, 1, 31).unwrap(),
+        until: None,
+        date_divisor: DateDivisor::Interval(DateInterval {
+            years: 0,
+            months,
+            weeks: 0,
+            days,
+        }),
+    }
+}
+
+fn nearest_date(c: &mut Criterion) {
+    let date = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+    for (name, range) in [
+        ("every day", interval_range(0, 1)),
+        ("every month", interval_range(1, 0)),
+        ("every month and day", interval_range(1, 1)),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter(|| range.get_nearest_date(black_box(date)))
+        });
+    }
+}
+
+criterion_group!(benches, nearest_date);
+criterion_main!(benches);
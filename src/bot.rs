@@ -1,81 +1,460 @@
 use crate::cli::CLI;
-use crate::controller::{TgCallbackController, TgMessageController};
-use crate::db::Database;
-use crate::entity::{cron_reminder, reminder};
+use crate::controller::{
+    parse_tagged_query, undo_period_start, SelectionQuery,
+    TgCallbackController, TgMessageController,
+};
+use crate::date;
+use crate::db::{self, Database};
+use crate::digest;
+use crate::entity::{cron_reminder, reminder, reminder_delivery};
 use crate::err::Error;
-use crate::format;
-use crate::parsers::now_time;
+use crate::format::{self, TimeFormat};
+use crate::i18n::{self, Locale};
+use crate::parsers::{now_time, suffix_entities, EditMode, ReplyTo};
+use crate::quiet;
 use crate::serializers::Pattern;
-use crate::tg::send_message;
-use crate::tz::{get_timezone_name_of_location, get_user_timezone};
+use crate::settings;
+use crate::tg::{
+    _send_message, command_descriptions, copy_message, delivery_markup,
+    send_attachment, send_message, Attachment, Localize, TgResponse,
+    DEFAULT_SNOOZE_MINUTES, MAX_MESSAGE_LEN, SNOOZE_MINUTES,
+};
+use crate::tz::{
+    get_timezone, get_timezone_name_of_location, get_user_timezone, Reanchor,
+};
 use async_once::AsyncOnce;
 use async_std::task;
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use chrono_tz::Tz;
 use cron_parser::parse as parse_cron;
-use sea_orm::{ActiveValue::NotSet, IntoActiveModel};
+use sea_orm::{
+    ActiveValue::{NotSet, Set},
+    IntoActiveModel,
+};
 use serde_json::{from_str, to_string};
 use std::cmp::max;
-use std::time::Duration;
-use teloxide::{prelude::*, types::MessageId, utils::command::BotCommands};
+use std::time::{Duration, Instant};
+use teloxide::{
+    prelude::*,
+    types::{MessageEntity, MessageId},
+    utils::command::BotCommands,
+};
 
+/// Descriptions of the commands are in the message catalogs
 #[derive(BotCommands, Clone)]
-#[command(description = "Commands:", rename_rule = "lowercase")]
+#[command(rename_rule = "lowercase")]
 pub enum Command {
-    #[command(description = "list the set reminders")]
-    List,
-    #[command(description = "choose reminders to delete")]
-    Delete,
-    #[command(description = "choose reminders to edit")]
-    Edit,
-    #[command(description = "cancel editing")]
+    List(String),
+    Tags,
+    Search(String),
+    Delete(String),
+    Edit(String),
     Cancel,
-    #[command(description = "choose reminders to pause")]
-    Pause,
-    #[command(description = "set a new reminder")]
+    Pause(String),
+    Quiet(String),
+    Next(String),
+    Digest(String),
+    History,
+    Undo,
     Set(String),
-    #[command(description = "select a timezone")]
+    Settings,
+    Language(String),
     SetTimezone,
-    #[command(description = "show your timezone")]
     Timezone,
-    #[command(description = "show this text")]
     Help,
-    #[command(description = "start")]
     Start,
 }
 
+/// Send the reminder's text with its attachment if it has one,
+/// replying to a copy of the message it was set about, the text
+/// is sent alone if the message was deleted
+#[allow(clippy::too_many_arguments)]
+async fn deliver_reminder(
+    text: &str,
+    chat_id: ChatId,
+    reply_to_chat_id: Option<i64>,
+    reply_to_message_id: Option<i32>,
+    attachment: Option<Attachment>,
+    silent: bool,
+    preferences: &Preferences,
+    bot: &Bot,
+) -> Result<Message, Error> {
+    let reply_to = match (reply_to_chat_id, reply_to_message_id) {
+        (Some(from_chat_id), Some(msg_id)) => {
+            copy_message(
+                bot,
+                chat_id,
+                ChatId(from_chat_id),
+                MessageId(msg_id),
+                silent,
+            )
+            .await?
+        }
+        // Reminders snoozed from a delivered one reply to it
+        (None, Some(msg_id)) => Some(MessageId(msg_id)),
+        (_, None) => None,
+    };
+    let markup = Some(delivery_markup(
+        preferences.locale,
+        preferences.snooze_minutes,
+    ));
+    match attachment {
+        Some(attachment) => {
+            send_attachment(
+                text,
+                &attachment,
+                bot,
+                chat_id,
+                silent,
+                reply_to,
+                markup,
+            )
+            .await
+        }
+        None => {
+            _send_message(text, bot, chat_id, silent, reply_to, markup).await
+        }
+    }
+    .map_err(From::from)
+}
+
 async fn send_reminder(
     reminder: &reminder::Model,
     user_timezone: Tz,
+    preferences: &Preferences,
+    silent: bool,
     bot: &Bot,
-) -> Result<(), Error> {
+) -> Result<Message, Error> {
     let text = format::format_reminder(
         &reminder.clone().into_active_model(),
         user_timezone,
+        preferences.time_format,
+        preferences.locale,
     );
-    send_message(&text, bot, ChatId(reminder.chat_id))
-        .await
-        .map_err(From::from)
+    deliver_reminder(
+        &text,
+        ChatId(reminder.chat_id),
+        reminder.reply_to_chat_id,
+        reminder.reply_to_message_id,
+        Attachment::from_columns(
+            reminder.media_type.as_deref(),
+            reminder.file_id.as_deref(),
+        ),
+        silent,
+        preferences,
+        bot,
+    )
+    .await
 }
 
 async fn send_cron_reminder(
     reminder: &cron_reminder::Model,
     next_reminder: &Option<cron_reminder::Model>,
     user_timezone: Tz,
+    preferences: &Preferences,
+    silent: bool,
     bot: &Bot,
-) -> Result<(), Error> {
-    let text =
-        format::format_cron_reminder(reminder, next_reminder, user_timezone);
-    send_message(&text, bot, ChatId(reminder.chat_id))
+) -> Result<Message, Error> {
+    let text = format::format_cron_reminder(
+        reminder,
+        next_reminder,
+        user_timezone,
+        preferences.time_format,
+        preferences.locale,
+    );
+    deliver_reminder(
+        &text,
+        ChatId(reminder.chat_id),
+        reminder.reply_to_chat_id,
+        reminder.reply_to_message_id,
+        Attachment::from_columns(
+            reminder.media_type.as_deref(),
+            reminder.file_id.as_deref(),
+        ),
+        silent,
+        preferences,
+        bot,
+    )
+    .await
+}
+
+/// How a due reminder is delivered with respect to the user's quiet hours
+enum Delivery {
+    Loud,
+    Silent,
+    DeferUntil(NaiveDateTime),
+}
+
+/// Urgent reminders always ring, others are sent silently or deferred
+/// to the end of the user's quiet hours if they are on
+/// and otherwise follow the user's notification setting,
+/// the quiet hours are in the user's timezone or the chat's one
+/// if the user has none. Quiet hours are personal, so they don't apply
+/// to reminders in groups
+async fn get_delivery(
+    db: &Database,
+    chat_id: ChatId,
+    user_id: UserId,
+    chat_timezone: Tz,
+    urgent: bool,
+) -> Delivery {
+    if urgent {
+        return Delivery::Loud;
+    }
+    let user_id = user_id.0 as i64;
+    if chat_id.is_user() {
+        if let Some(delivery) =
+            get_quiet_hours_delivery(db, user_id, chat_timezone).await
+        {
+            return delivery;
+        }
+    }
+    match db
+        .get_user_setting::<settings::SilentNotifications>(user_id)
         .await
-        .map_err(From::from)
+    {
+        Ok(Some(true)) => Delivery::Silent,
+        Ok(_) => Delivery::Loud,
+        Err(err) => {
+            log::error!("{}", err);
+            Delivery::Loud
+        }
+    }
+}
+
+/// Delivery of a reminder during the user's quiet hours if they are on now
+async fn get_quiet_hours_delivery(
+    db: &Database,
+    user_id: i64,
+    chat_timezone: Tz,
+) -> Option<Delivery> {
+    let quiet_hours = match db.get_user_quiet_hours(user_id).await {
+        Ok(quiet_hours) => quiet_hours?,
+        Err(err) => {
+            log::error!("{}", err);
+            return None;
+        }
+    };
+    let user_timezone = get_user_timezone(db, UserId(user_id as u64))
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(chat_timezone);
+    let end_time =
+        quiet::quiet_hours_end(&quiet_hours, user_timezone, now_time())?;
+    Some(match quiet_hours.silent {
+        true => Delivery::Silent,
+        false => Delivery::DeferUntil(end_time),
+    })
+}
+
+/// User's settings applied to the messages sent without a request
+struct Preferences {
+    locale: Locale,
+    time_format: TimeFormat,
+    snooze_minutes: i64,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            time_format: TimeFormat::default(),
+            snooze_minutes: DEFAULT_SNOOZE_MINUTES,
+        }
+    }
+}
+
+async fn load_preferences(
+    db: &Database,
+    user_id: UserId,
+) -> Result<Preferences, db::Error> {
+    Ok(Preferences {
+        locale: i18n::get_user_locale(db, user_id, None).await?,
+        time_format: TimeFormat::of_user(db, user_id).await?,
+        snooze_minutes: db
+            .get_user_setting::<settings::DefaultSnooze>(user_id.0 as i64)
+            .await?
+            .unwrap_or(DEFAULT_SNOOZE_MINUTES),
+    })
+}
+
+/// User's preferences or the default ones if they fail to load
+async fn get_preferences(db: &Database, user_id: UserId) -> Preferences {
+    load_preferences(db, user_id).await.unwrap_or_else(|err| {
+        log::error!("{}", err);
+        Preferences::default()
+    })
+}
+
+/// Save a sent reminder to the chat's history
+async fn log_reminder_delivery(
+    db: &Database,
+    delivery: reminder_delivery::ActiveModel,
+    sent_msg: &Message,
+) {
+    db.insert_reminder_delivery(reminder_delivery::ActiveModel {
+        id: NotSet,
+        chat_id: Set(sent_msg.chat.id.0),
+        sent_time: Set(sent_msg.date.naive_utc()),
+        message_id: Set(sent_msg.id.0),
+        ..delivery
+    })
+    .await
+    .unwrap_or_else(|err| {
+        log::error!("{}", err);
+    });
+}
+
+/// Delete expired undo entries with the reminders they could restore,
+/// abandoned reminder selections and history entries older than
+/// the configured retention period
+async fn prune_reminders_history(db: &Database) {
+    db.delete_reminder_undos_created_before(undo_period_start())
+        .await
+        .map(|_| ())
+        .unwrap_or_else(|err| {
+            log::error!("{}", err);
+        });
+    db.delete_reminders_deleted_before(undo_period_start())
+        .await
+        .map(|_| ())
+        .unwrap_or_else(|err| {
+            log::error!("{}", err);
+        });
+    db.delete_reminder_selections_created_before(
+        now_time() - chrono::Duration::days(1),
+    )
+    .await
+    .map(|_| ())
+    .unwrap_or_else(|err| {
+        log::error!("{}", err);
+    });
+    if CLI.history_retention_days == 0 {
+        return;
+    }
+    let retention = chrono::Duration::days(CLI.history_retention_days as i64);
+    match db
+        .delete_reminder_deliveries_sent_before(now_time() - retention)
+        .await
+    {
+        Ok(0) => {}
+        Ok(pruned) => log::info!("Pruned {} history entries", pruned),
+        Err(err) => log::error!("{}", err),
+    }
+}
+
+/// Upcoming firings of the chat's active reminders until the end of the day
+/// with their descriptions, sorted by the first firing
+async fn get_digest_entries(
+    db: &Database,
+    chat_id: i64,
+    user_timezone: Tz,
+    now: NaiveDateTime,
+) -> Result<Vec<(Vec<NaiveDateTime>, String)>, db::Error> {
+    let until = digest::end_of_day(user_timezone, now);
+    let mut entries = vec![];
+    for reminder in db.get_pending_chat_reminders(chat_id).await? {
+        if !reminder.paused {
+            let times = digest::reminder_occurrences(
+                &reminder.clone().into_active_model(),
+                user_timezone,
+                now,
+                until,
+            );
+            entries.push((times, reminder.desc));
+        }
+    }
+    for cron_reminder in db.get_pending_chat_cron_reminders(chat_id).await? {
+        if !cron_reminder.paused {
+            let times = digest::reminder_occurrences(
+                &cron_reminder.clone().into_active_model(),
+                user_timezone,
+                now,
+                until,
+            );
+            entries.push((times, cron_reminder.desc));
+        }
+    }
+    entries.retain(|(times, _)| !times.is_empty());
+    entries.sort_by_key(|(times, _)| times[0]);
+    Ok(entries)
+}
+
+/// Send the day's agenda to the chats whose digest time has come
+/// and schedule their next digests
+async fn send_digests(db: &Database, bot: &Bot) {
+    let digests = match db.get_active_digests().await {
+        Ok(digests) => digests,
+        Err(err) => {
+            log::error!("{}", err);
+            return;
+        }
+    };
+    for digest in digests {
+        let user_timezone = match get_timezone(
+            db,
+            ChatId(digest.chat_id),
+            UserId(digest.user_id as u64),
+        )
+        .await
+        {
+            Ok(Some(user_timezone)) => user_timezone,
+            _ => chrono_tz::UTC,
+        };
+        let preferences =
+            get_preferences(db, UserId(digest.user_id as u64)).await;
+        let locale = preferences.locale;
+        let now = now_time();
+        match get_digest_entries(db, digest.chat_id, user_timezone, now).await {
+            Ok(entries) if entries.is_empty() && !digest.include_empty => {}
+            Ok(entries) => {
+                let text = if entries.is_empty() {
+                    TgResponse::EmptyDigest.localize(locale)
+                } else {
+                    format::format_digest(
+                        &TgResponse::DigestHeader.localize(locale),
+                        &entries,
+                        user_timezone,
+                        preferences.time_format,
+                        MAX_MESSAGE_LEN,
+                    )
+                };
+                send_message(&text, bot, ChatId(digest.chat_id))
+                    .await
+                    .map(|_| ())
+                    .unwrap_or_else(|err| {
+                        log::error!("{}", err);
+                    });
+            }
+            Err(err) => log::error!("{}", err),
+        }
+        db.set_chat_digest_next_time(
+            digest.id,
+            date::next_time_of_day(digest.time, user_timezone, now),
+        )
+        .await
+        .unwrap_or_else(|err| {
+            log::error!("{}", err);
+        });
+    }
 }
 
 /// Periodically (every second) check for new reminders.
 /// Send and delete one-time reminders if time has come.
 /// Send cron reminders if time has come and update next reminder time.
+/// Defer or silence reminders during the users' quiet hours.
+/// Record sent reminders to the history and prune it hourly.
 async fn poll_reminders(db: &Database, bot: Bot) {
+    let mut last_history_prune: Option<Instant> = None;
     loop {
+        if last_history_prune
+            .map(|time| time.elapsed() >= HISTORY_PRUNE_PERIOD)
+            .unwrap_or(true)
+        {
+            prune_reminders_history(db).await;
+            last_history_prune = Some(Instant::now());
+        }
         let reminders = db
             .get_active_reminders()
             .await
@@ -83,8 +462,33 @@ async fn poll_reminders(db: &Database, bot: Bot) {
         for reminder in reminders {
             if let Some(user_id) = reminder.user_id.map(|x| UserId(x as u64)) {
                 if let Ok(Some(user_timezone)) =
-                    get_user_timezone(db, user_id).await
+                    get_timezone(db, ChatId(reminder.chat_id), user_id).await
                 {
+                    let silent =
+                        match get_delivery(
+                            db,
+                            ChatId(reminder.chat_id),
+                            user_id,
+                            user_timezone,
+                            reminder.urgent,
+                        )
+                        .await
+                        {
+                            Delivery::Loud => false,
+                            Delivery::Silent => true,
+                            Delivery::DeferUntil(end_time) => {
+                                db.update_reminder_time(
+                                    reminder.id,
+                                    end_time,
+                                    None,
+                                )
+                                .await
+                                .unwrap_or_else(|err| {
+                                    log::error!("{}", err);
+                                });
+                                continue;
+                            }
+                        };
                     let mut next_reminder = None;
                     if let Some(ref serialized) = reminder.pattern {
                         let mut pattern: Pattern =
@@ -98,26 +502,46 @@ async fn poll_reminders(db: &Database, bot: Bot) {
                             });
                         }
                     }
-                    if send_reminder(&reminder, user_timezone, &bot)
-                        .await
-                        .is_ok()
+                    let preferences = get_preferences(db, user_id).await;
+                    if let Ok(sent_msg) = send_reminder(
+                        &reminder,
+                        user_timezone,
+                        &preferences,
+                        silent,
+                        &bot,
+                    )
+                    .await
                     {
-                        db.delete_reminder(reminder.id).await.unwrap_or_else(
-                            |err| {
-                                log::error!("{}", err);
+                        log_reminder_delivery(
+                            db,
+                            reminder_delivery::ActiveModel {
+                                reminder_id: Set(reminder.id),
+                                reminder_type: Set("rem".to_owned()),
+                                desc: Set(reminder.desc.clone()),
+                                time: Set(reminder.time),
+                                user_id: Set(reminder.user_id),
+                                recipients: Set(reminder.recipients.clone()),
+                                ..Default::default()
                             },
-                        );
-                        if let Some(next_reminder) = next_reminder {
-                            let mut next_reminder: reminder::ActiveModel =
-                                next_reminder.into();
-                            next_reminder.id = NotSet;
-                            db.insert_reminder(next_reminder)
+                            &sent_msg,
+                        )
+                        .await;
+                        // Reschedule a recurring reminder in place
+                        // to keep its id
+                        match next_reminder {
+                            Some(next_reminder) => {
+                                db.update_reminder_time(
+                                    reminder.id,
+                                    next_reminder.time,
+                                    next_reminder.pattern,
+                                )
                                 .await
-                                .map(|_| ())
-                                .unwrap_or_else(|err| {
-                                    log::error!("{}", err);
-                                });
+                            }
+                            None => db.delete_reminder(reminder.id).await,
                         }
+                        .unwrap_or_else(|err| {
+                            log::error!("{}", err);
+                        });
                     }
                 }
             }
@@ -131,11 +555,41 @@ async fn poll_reminders(db: &Database, bot: Bot) {
                 cron_reminder.user_id.map(|x| UserId(x as u64))
             {
                 if let Ok(Some(user_timezone)) =
-                    get_user_timezone(db, user_id).await
+                    get_timezone(db, ChatId(cron_reminder.chat_id), user_id)
+                        .await
                 {
+                    let silent =
+                        match get_delivery(
+                            db,
+                            ChatId(cron_reminder.chat_id),
+                            user_id,
+                            user_timezone,
+                            cron_reminder.urgent,
+                        )
+                        .await
+                        {
+                            Delivery::Loud => false,
+                            Delivery::Silent => true,
+                            Delivery::DeferUntil(end_time) => {
+                                db.update_cron_reminder_time(
+                                    cron_reminder.id,
+                                    end_time,
+                                )
+                                .await
+                                .unwrap_or_else(|err| {
+                                    log::error!("{}", err);
+                                });
+                                continue;
+                            }
+                        };
+                    let cron_timezone = cron_reminder
+                        .timezone
+                        .as_deref()
+                        .and_then(|name| name.parse::<Tz>().ok())
+                        .unwrap_or(user_timezone);
                     let new_time = parse_cron(
                         &cron_reminder.cron_expr,
-                        &Utc::now().with_timezone(&user_timezone),
+                        &Utc::now().with_timezone(&cron_timezone),
                     )
                     .map(|user_time| user_time.with_timezone(&Utc));
                     let new_cron_reminder = match new_time {
@@ -148,30 +602,52 @@ async fn poll_reminders(db: &Database, bot: Bot) {
                             None
                         }
                     };
+                    let preferences = get_preferences(db, user_id).await;
                     match send_cron_reminder(
                         &cron_reminder,
                         &new_cron_reminder,
                         user_timezone,
+                        &preferences,
+                        silent,
                         &bot,
                     )
                     .await
                     {
-                        Ok(()) => {
-                            db.delete_cron_reminder(cron_reminder.id)
-                                .await
-                                .unwrap_or_else(|err| {
-                                    log::error!("{}", err);
-                                });
-                            if let Some(new_cron_reminder) = new_cron_reminder {
-                                let mut new_cron_reminder: cron_reminder::ActiveModel = new_cron_reminder.into();
-                                new_cron_reminder.id = NotSet;
-                                db.insert_cron_reminder(new_cron_reminder)
+                        Ok(sent_msg) => {
+                            log_reminder_delivery(
+                                db,
+                                reminder_delivery::ActiveModel {
+                                    reminder_id: Set(cron_reminder.id),
+                                    reminder_type: Set("cron_rem".to_owned()),
+                                    desc: Set(cron_reminder.desc.clone()),
+                                    time: Set(cron_reminder.time),
+                                    user_id: Set(cron_reminder.user_id),
+                                    recipients: Set(cron_reminder
+                                        .recipients
+                                        .clone()),
+                                    ..Default::default()
+                                },
+                                &sent_msg,
+                            )
+                            .await;
+                            match new_cron_reminder {
+                                Some(new_cron_reminder) => {
+                                    db.update_cron_reminder_time(
+                                        cron_reminder.id,
+                                        new_cron_reminder.time,
+                                    )
                                     .await
-                                    .map(|_| ())
-                                    .unwrap_or_else(|err| {
-                                        log::error!("{}", err);
-                                    });
+                                }
+                                None => {
+                                    db.delete_cron_reminder(cron_reminder.id)
+                                        .await
+                                }
                             }
+                            .unwrap_or_else(
+                                |err| {
+                                    log::error!("{}", err);
+                                },
+                            );
                         }
                         Err(err) => {
                             log::error!("{}", err);
@@ -180,17 +656,25 @@ async fn poll_reminders(db: &Database, bot: Bot) {
                 }
             }
         }
+        send_digests(db, &bot).await;
         task::sleep(Duration::from_secs(1)).await;
     }
 }
 
+const HISTORY_PRUNE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
 lazy_static! {
     /// A singleton database with a pool connection
     /// that can be shared between threads
     static ref DATABASE: AsyncOnce<Database> = AsyncOnce::new(async {
-        Database::new(&CLI.database)
-            .await
-            .unwrap_or_else(|err| panic!("Failed to connect to database {:?}: {}", CLI.database, err))
+        match CLI.database_url {
+            Some(ref db_url) => Database::new(db_url)
+                .await
+                .unwrap_or_else(|err| panic!("Failed to connect to database {}: {}", db_url, err)),
+            None => Database::from_file(&CLI.database)
+                .await
+                .unwrap_or_else(|err| panic!("Failed to connect to database {:?}: {}", CLI.database, err)),
+        }
     });
 }
 
@@ -207,9 +691,15 @@ pub async fn run() {
 
     let bot = Bot::new(&CLI.token);
 
-    bot.set_my_commands(Command::bot_commands())
+    bot.set_my_commands(command_descriptions(Locale::default()))
         .await
         .expect("Failed to set bot commands");
+    for locale in Locale::ALL {
+        bot.set_my_commands(command_descriptions(locale))
+            .language_code(locale.code())
+            .await
+            .expect("Failed to set bot commands");
+    }
 
     tokio::spawn(poll_reminders(DATABASE.get().await, bot.clone()));
 
@@ -235,13 +725,17 @@ impl<'a> TgMessageController<'a> {
         chat_id: ChatId,
         user_id: UserId,
         msg_id: MessageId,
+        language_code: Option<&str>,
     ) -> Result<TgMessageController<'a>, Error> {
+        let db = DATABASE.get().await;
         Ok(Self {
-            db: DATABASE.get().await,
+            db,
             bot,
             chat_id,
             user_id,
             msg_id,
+            locale: i18n::get_user_locale(db, user_id, language_code).await?,
+            time_format: TimeFormat::of_user(db, user_id).await?,
         })
     }
 
@@ -249,13 +743,15 @@ impl<'a> TgMessageController<'a> {
         bot: &'a Bot,
         msg: &Message,
     ) -> Result<TgMessageController<'a>, Error> {
+        let user = msg
+            .from()
+            .ok_or_else(|| Error::UserNotFound(Box::new(msg.clone())))?;
         Self::new(
             bot,
             msg.chat.id,
-            msg.from()
-                .ok_or_else(|| Error::UserNotFound(msg.clone()))?
-                .id,
+            user.id,
             msg.id,
+            user.language_code.as_deref(),
         )
         .await
     }
@@ -267,8 +763,15 @@ impl<'a> TgMessageController<'a> {
         let msg = cb_query
             .message
             .as_ref()
-            .ok_or_else(|| Error::NoQueryMessage(cb_query.clone()))?;
-        Self::new(bot, msg.chat.id, cb_query.from.id, msg.id).await
+            .ok_or_else(|| Error::NoQueryMessage(Box::new(cb_query.clone())))?;
+        Self::new(
+            bot,
+            msg.chat.id,
+            cb_query.from.id,
+            msg.id,
+            cb_query.from.language_code.as_deref(),
+        )
+        .await
     }
 }
 
@@ -292,22 +795,64 @@ async fn command_handler(
 ) -> Result<(), Error> {
     let ctl = TgMessageController::from_msg(&bot, &msg).await?;
     match cmd {
-        Command::Help => ctl.reply(Command::descriptions()).await,
+        Command::Help => ctl.reply(TgResponse::Help).await,
         Command::Start => ctl.start().await,
-        Command::List => ctl.list().await,
+        Command::List(ref tag) => ctl.list(tag).await,
+        Command::Tags => ctl.tags().await,
+        Command::Search(ref query) => ctl.search(query).await,
         Command::SetTimezone => ctl.choose_timezone().await,
         Command::Timezone => ctl.get_timezone().await,
-        Command::Delete => ctl.start_delete().await,
-        Command::Edit => ctl.start_edit().await,
+        Command::Settings => ctl.settings().await,
+        Command::Language(ref arg) => ctl.language(arg).await,
+        Command::Delete(ref tag) => ctl.start_delete(tag).await,
+        Command::Edit(ref tag) => ctl.start_edit(tag).await,
         Command::Cancel => ctl.cancel_edit().await,
-        Command::Pause => ctl.start_pause().await,
+        Command::Pause(ref tag) => ctl.start_pause(tag).await,
+        Command::Digest(ref arg) => ctl.digest(arg).await,
+        Command::Next(ref arg) => ctl.next(arg).await,
+        Command::Quiet(ref arg) => ctl.quiet(arg).await,
+        Command::History => ctl.history().await,
+        Command::Undo => ctl.undo_last().await,
         Command::Set(ref reminder_text) => {
-            ctl.set_or_edit_reminder(reminder_text).await
+            ctl.set_or_edit_reminder(
+                reminder_text,
+                &suffix_entities(
+                    msg.text().unwrap_or_default(),
+                    reminder_text,
+                    get_entities(&msg),
+                ),
+                get_reply_to(&msg),
+                get_attachment(&msg).as_ref(),
+            )
+            .await
         }
     }
     .map_err(From::from)
 }
 
+/// Message the message replies to, reminders set in reply to it
+/// are delivered with a copy of it
+fn get_reply_to(msg: &Message) -> Option<ReplyTo<'_>> {
+    msg.reply_to_message().map(|reply| ReplyTo {
+        chat_id: Some(reply.chat.id.0),
+        message_id: reply.id.0,
+        text: reply.text().or_else(|| reply.caption()),
+    })
+}
+
+/// Formatting entities of the text or caption of the message
+fn get_entities(msg: &Message) -> &[MessageEntity] {
+    msg.entities()
+        .or_else(|| msg.caption_entities())
+        .unwrap_or_default()
+}
+
+/// File attached to the message, reminders set by the message are sent
+/// with it, files of the messages replied to come with their copies
+fn get_attachment(msg: &Message) -> Option<Attachment> {
+    Attachment::from_message(msg)
+}
+
 async fn message_handler(msg: Message, bot: Bot) -> Result<(), Error> {
     let ctl = TgMessageController::from_msg(&bot, &msg).await?;
     if !ctl.chat_id.is_user() {
@@ -319,13 +864,54 @@ async fn message_handler(msg: Message, bot: Bot) -> Result<(), Error> {
         ))
         .await
         .map_err(From::from)
-    } else if let Some(text) = msg.text() {
-        ctl.set_or_edit_reminder(text).await.map_err(From::from)
+    } else if msg.forward().is_some() {
+        ctl.set_forwarded_reminder(
+            msg.id,
+            msg.text().or_else(|| msg.caption()),
+            msg.caption(),
+            get_entities(&msg),
+        )
+        .await
+        .map_err(From::from)
+    } else if let Some(text) = msg.text().or_else(|| msg.caption()) {
+        ctl.set_or_edit_reminder(
+            text,
+            get_entities(&msg),
+            get_reply_to(&msg),
+            get_attachment(&msg).as_ref(),
+        )
+        .await
+        .map_err(From::from)
     } else {
         ctl.incorrect_request().await.map_err(From::from)
     }
 }
 
+/// Parse the action, reminder type and id of a search result's button
+fn parse_search_result_query(query: &str) -> Option<(&str, &str, i64)> {
+    let mut parts = query.splitn(3, "::");
+    Some((parts.next()?, parts.next()?, parts.next()?.parse().ok()?))
+}
+
+/// Parse the type and id of a reminder
+fn parse_reminder_query(query: &str) -> Option<(&str, i64)> {
+    let (rem_type, rem_id) = query.split_once("::")?;
+    Some((rem_type, rem_id.parse().ok()?))
+}
+
+/// Parse the part of a reminder to edit and the reminder's type and id
+fn parse_edit_part_query(query: &str) -> Option<(EditMode, &str, i64)> {
+    let (edit_mode, query) = query.split_once("::")?;
+    let (rem_type, rem_id) = parse_reminder_query(query)?;
+    Some((EditMode::from_code(edit_mode)?, rem_type, rem_id))
+}
+
+/// Parse the choice of what the reminders keep and the old timezone
+fn parse_reanchor_query(query: &str) -> Option<(Reanchor, Tz)> {
+    let (reanchor, tz_name) = query.split_once("::")?;
+    Some((Reanchor::from_code(reanchor)?, tz_name.parse().ok()?))
+}
+
 async fn callback_handler(
     cb_query: CallbackQuery,
     bot: Bot,
@@ -341,72 +927,183 @@ async fn callback_handler(
                 .select_timezone_set_page(page_num)
                 .await
                 .map_err(From::from)
+        } else if let Some(page_num) = cb_data
+            .strip_prefix("history::page::")
+            .and_then(|x| x.parse::<u64>().ok())
+        {
+            msg_ctl.history_set_page(page_num).await.map_err(From::from)
+        } else if let Some((page_num, tag_key)) = cb_data
+            .strip_prefix("list::page::")
+            .and_then(parse_tagged_query)
+        {
+            msg_ctl
+                .list_set_page(page_num, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some(undo_id) = cb_data
+            .strip_prefix("undo::")
+            .and_then(|x| x.parse::<i64>().ok())
+        {
+            ctl.undo(undo_id).await.map_err(From::from)
+        } else if let Some(setting) = cb_data.strip_prefix("settings::") {
+            ctl.change_setting(setting).await.map_err(From::from)
         } else if let Some(tz_name) = cb_data.strip_prefix("seltz::tz::") {
             ctl.set_timezone(tz_name).await.map_err(From::from)
-        } else if let Some(page_num) = cb_data
+        } else if let Some((reanchor, old_timezone)) = cb_data
+            .strip_prefix("reanchor::")
+            .and_then(parse_reanchor_query)
+        {
+            ctl.reanchor_reminders(reanchor, old_timezone)
+                .await
+                .map_err(From::from)
+        } else if let Some((kind, rem_type, rem_id)) = cb_data
+            .strip_prefix("searchrem::")
+            .and_then(parse_search_result_query)
+        {
+            match (kind, rem_type) {
+                ("edit", "rem") => ctl.edit_reminder(rem_id).await,
+                ("edit", "cron_rem") => ctl.edit_cron_reminder(rem_id).await,
+                ("del", "rem") => {
+                    ctl.delete_reminder(rem_id, false, None).await
+                }
+                ("del", "cron_rem") => {
+                    ctl.delete_cron_reminder(rem_id, false, None).await
+                }
+                ("pause", "rem") => {
+                    ctl.pause_reminder(rem_id, false, None).await
+                }
+                ("pause", "cron_rem") => {
+                    ctl.pause_cron_reminder(rem_id, false, None).await
+                }
+                _ => {
+                    return Err(Error::UnmatchedQuery(Box::new(
+                        cb_query.clone(),
+                    )))
+                }
+            }
+            .map_err(From::from)
+        } else if let Some((action, query, tag_key)) =
+            SelectionQuery::parse(cb_data)
+        {
+            ctl.handle_selection_query(action, query, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some((page_num, tag_key)) = cb_data
             .strip_prefix("delrem::page::")
-            .and_then(|x| x.parse::<usize>().ok())
+            .and_then(parse_tagged_query)
         {
             msg_ctl
-                .delete_reminder_set_page(page_num)
+                .delete_reminder_set_page(page_num, tag_key)
                 .await
                 .map_err(From::from)
-        } else if let Some(rem_id) = cb_data
+        } else if let Some((rem_id, tag_key)) = cb_data
             .strip_prefix("delrem::rem_alt::")
-            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(parse_tagged_query)
         {
-            ctl.delete_reminder(rem_id).await.map_err(From::from)
-        } else if let Some(cron_rem_id) = cb_data
+            ctl.delete_reminder(rem_id, true, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some((cron_rem_id, tag_key)) = cb_data
             .strip_prefix("delrem::cron_rem_alt::")
-            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(parse_tagged_query)
         {
-            ctl.delete_cron_reminder(cron_rem_id)
+            ctl.delete_cron_reminder(cron_rem_id, true, tag_key)
                 .await
                 .map_err(From::from)
-        } else if let Some(page_num) = cb_data
+        } else if let Some((page_num, tag_key)) = cb_data
             .strip_prefix("editrem::page::")
-            .and_then(|x| x.parse::<usize>().ok())
+            .and_then(parse_tagged_query)
         {
             msg_ctl
-                .edit_reminder_set_page(page_num)
+                .edit_reminder_set_page(page_num, tag_key)
                 .await
                 .map_err(From::from)
-        } else if let Some(rem_id) = cb_data
+        } else if let Some((rem_id, _)) = cb_data
             .strip_prefix("editrem::rem_alt::")
-            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(parse_tagged_query)
         {
             ctl.edit_reminder(rem_id).await.map_err(From::from)
-        } else if let Some(cron_rem_id) = cb_data
+        } else if let Some((cron_rem_id, _)) = cb_data
             .strip_prefix("editrem::cron_rem_alt::")
-            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(parse_tagged_query)
         {
             ctl.edit_cron_reminder(cron_rem_id)
                 .await
                 .map_err(From::from)
-        } else if let Some(page_num) = cb_data
+        } else if let Some((rem_type, rem_id)) = cb_data
+            .strip_prefix("copyrem::")
+            .and_then(parse_reminder_query)
+        {
+            ctl.copy_reminder_text(rem_type, rem_id)
+                .await
+                .map_err(From::from)
+        } else if let Some((edit_mode, rem_type, rem_id)) = cb_data
+            .strip_prefix("editpart::")
+            .and_then(parse_edit_part_query)
+        {
+            ctl.enter_reminder_part(edit_mode, rem_type, rem_id)
+                .await
+                .map_err(From::from)
+        } else if let Some((page_num, tag_key)) = cb_data
             .strip_prefix("pauserem::page::")
-            .and_then(|x| x.parse::<usize>().ok())
+            .and_then(parse_tagged_query)
         {
             msg_ctl
-                .pause_reminder_set_page(page_num)
+                .pause_reminder_set_page(page_num, tag_key)
                 .await
                 .map_err(From::from)
-        } else if let Some(rem_id) = cb_data
+        } else if let Some((rem_id, tag_key)) = cb_data
             .strip_prefix("pauserem::rem_alt::")
-            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(parse_tagged_query)
         {
-            ctl.pause_reminder(rem_id).await.map_err(From::from)
-        } else if let Some(cron_rem_id) = cb_data
+            ctl.pause_reminder(rem_id, true, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some((cron_rem_id, tag_key)) = cb_data
             .strip_prefix("pauserem::cron_rem_alt::")
+            .and_then(parse_tagged_query)
+        {
+            ctl.pause_cron_reminder(cron_rem_id, true, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some((page_num, tag_key)) = cb_data
+            .strip_prefix("nextrem::page::")
+            .and_then(parse_tagged_query)
+        {
+            msg_ctl
+                .next_reminder_set_page(page_num, tag_key)
+                .await
+                .map_err(From::from)
+        } else if let Some((rem_id, _)) = cb_data
+            .strip_prefix("nextrem::rem_alt::")
+            .and_then(parse_tagged_query)
+        {
+            ctl.next_occurrences("rem", rem_id)
+                .await
+                .map_err(From::from)
+        } else if let Some((cron_rem_id, _)) = cb_data
+            .strip_prefix("nextrem::cron_rem_alt::")
+            .and_then(parse_tagged_query)
+        {
+            ctl.next_occurrences("cron_rem", cron_rem_id)
+                .await
+                .map_err(From::from)
+        } else if cb_data == "delivery::done" {
+            ctl.mark_delivery_done(&cb_query.from)
+                .await
+                .map_err(From::from)
+        } else if let Some(minutes) = cb_data
+            .strip_prefix("delivery::snooze::")
             .and_then(|x| x.parse::<i64>().ok())
+            .filter(|minutes| SNOOZE_MINUTES.contains(minutes))
         {
-            ctl.pause_cron_reminder(cron_rem_id)
+            ctl.snooze_delivery(&cb_query.from, minutes)
                 .await
                 .map_err(From::from)
         } else {
-            Err(Error::UnmatchedQuery(cb_query))
+            Err(Error::UnmatchedQuery(Box::new(cb_query)))
         }
     } else {
-        Err(Error::NoQueryData(cb_query))
+        Err(Error::NoQueryData(Box::new(cb_query)))
     }
 }
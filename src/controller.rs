@@ -1,19 +1,42 @@
+use crate::date;
 use crate::db;
-use crate::parsers;
+use crate::format::{self, DateOrder, TimeFormat};
+use crate::grammar;
+use crate::i18n::{self, Locale};
+use crate::parsers::{self, EditMode, Recipient, ReplyTo};
+use crate::serializers::OverlapPolicy;
+use crate::settings;
 use crate::tg;
 use crate::tz;
 
-use crate::entity::{cron_reminder, reminder};
+use crate::entity::{
+    chat_digest, cron_reminder, reminder, reminder_delivery, reminder_undo,
+    user_quiet_hours,
+};
 use crate::generic_reminder::GenericReminder;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use chrono_tz::Tz;
-use sea_orm::IntoActiveModel;
+use sea_orm::{
+    ActiveValue::{NotSet, Set},
+    IntoActiveModel,
+};
 use teloxide::prelude::*;
 use teloxide::types::MessageId;
 use teloxide::types::{
     InlineKeyboardButton, InlineKeyboardButtonKind, InlineKeyboardMarkup,
+    MessageEntity, User,
 };
+use teloxide::utils::markdown;
 use teloxide::RequestError;
-use tg::TgResponse;
+use tg::{Attachment, Localize, TgResponse};
+
+const HISTORY_PAGE_SIZE: u64 = 10;
+const REMINDERS_PAGE_SIZE: usize = 45;
+const SEARCH_RESULTS_LIMIT: usize = 20;
+const LIST_PAGE_SIZE: usize = 20;
+const NEXT_OCCURRENCES_COUNT: usize = 10;
+/// Period of time during which an action can be undone
+pub const UNDO_PERIOD_MINUTES: i64 = 10;
 
 pub struct TgMessageController<'a> {
     pub db: &'a db::Database,
@@ -21,6 +44,8 @@ pub struct TgMessageController<'a> {
     pub chat_id: ChatId,
     pub user_id: UserId,
     pub msg_id: MessageId,
+    pub locale: Locale,
+    pub time_format: TimeFormat,
 }
 
 pub struct TgCallbackController<'a> {
@@ -33,50 +58,793 @@ enum ReminderSetResult {
     NotSet,
 }
 
+/// Replacing reminder keeps the message the replaced one was set about
+fn get_kept_reply_to(
+    reply_to_chat_id: Option<i64>,
+    reply_to_message_id: Option<i32>,
+) -> Option<ReplyTo<'static>> {
+    reply_to_message_id.map(|message_id| ReplyTo {
+        chat_id: reply_to_chat_id,
+        message_id,
+        text: None,
+    })
+}
+
+#[derive(Clone, Copy)]
+enum UndoAction {
+    Delete,
+    Edit,
+    /// Pausing or resuming with the paused state before it
+    Pause(bool),
+}
+
+impl UndoAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Edit => "edit",
+            Self::Pause(_) => "pause",
+        }
+    }
+}
+
+/// Action applied to all the reminders selected in a keyboard
+#[derive(Clone, Copy)]
+pub enum BulkAction {
+    Delete,
+    Pause,
+}
+
+impl BulkAction {
+    fn cb_prefix(&self) -> &'static str {
+        match self {
+            Self::Delete => "delrem",
+            Self::Pause => "pauserem",
+        }
+    }
+}
+
+/// Parse the page number or the reminder's id of a keyboard's callback data
+/// with the key of the keyboard's tag filter (if any)
+pub fn parse_tagged_query<T: std::str::FromStr>(
+    query: &str,
+) -> Option<(T, Option<&str>)> {
+    match query.split_once("::") {
+        Some((value, tag_key)) => Some((value.parse().ok()?, Some(tag_key))),
+        None => Some((query.parse().ok()?, None)),
+    }
+}
+
+/// Callback query of a keyboard in the selection mode
+pub enum SelectionQuery {
+    SetPage(usize),
+    Toggle(usize, String, i64),
+    TogglePage(usize),
+    Apply,
+    Cancel,
+}
+
+impl SelectionQuery {
+    /// Parse the query with the key of the keyboard's tag filter (if any)
+    pub fn parse(cb_data: &str) -> Option<(BulkAction, Self, Option<&str>)> {
+        let (action, query) = [BulkAction::Delete, BulkAction::Pause]
+            .into_iter()
+            .find_map(|action| {
+                cb_data
+                    .strip_prefix(action.cb_prefix())
+                    .and_then(|x| x.strip_prefix("::sel"))
+                    .map(|query| (action, query))
+            })?;
+        let (query, tag_key) = if let Some(rem) = query.strip_prefix("::") {
+            let mut parts = rem.splitn(4, "::");
+            let page_num = parts.next()?.parse::<usize>().ok()?;
+            let rem_type = parts.next()?.to_owned();
+            let rem_id = parts.next()?.parse::<i64>().ok()?;
+            (Self::Toggle(page_num, rem_type, rem_id), parts.next())
+        } else if let Some(query) = query.strip_prefix("_page::") {
+            let (page_num, tag_key) = parse_tagged_query(query)?;
+            (Self::SetPage(page_num), tag_key)
+        } else if let Some(query) = query.strip_prefix("_all::") {
+            let (page_num, tag_key) = parse_tagged_query(query)?;
+            (Self::TogglePage(page_num), tag_key)
+        } else if let Some(query) = query.strip_prefix("_apply") {
+            (Self::Apply, query.strip_prefix("::"))
+        } else if let Some(query) = query.strip_prefix("_cancel") {
+            (Self::Cancel, query.strip_prefix("::"))
+        } else {
+            return None;
+        };
+        Some((action, query, tag_key))
+    }
+}
+
+/// Tag filter given as the command's argument,
+/// `Err` if the argument is not a tag
+fn get_tag_filter(arg: &str) -> Result<Option<String>, TgResponse> {
+    if arg.trim().is_empty() {
+        Ok(None)
+    } else {
+        parsers::parse_tag_filter(arg)
+            .map(Some)
+            .ok_or(TgResponse::IncorrectRequest)
+    }
+}
+
+/// Callback data of a keyboard filtered by the tag (if any)
+/// referring to the tag by its key
+fn with_tag_key(cb_data: String, tag: Option<&str>) -> String {
+    match tag {
+        Some(tag) => cb_data + "::" + &parsers::tag_key(tag),
+        None => cb_data,
+    }
+}
+
+/// Callback data to switch the page of a (maybe filtered by tag) keyboard
+fn get_page_cb_data(cb_prefix: &str, num: usize, tag: Option<&str>) -> String {
+    with_tag_key(format!("{}::page::{}", cb_prefix, num), tag)
+}
+
+/// The earliest creation time of an action that still can be undone
+pub fn undo_period_start() -> NaiveDateTime {
+    parsers::now_time() - Duration::minutes(UNDO_PERIOD_MINUTES)
+}
+
 impl TgMessageController<'_> {
-    pub async fn reply<R: ToString>(
+    /// Timezone of the reminders in the chat
+    pub async fn get_reminders_timezone(
+        &self,
+    ) -> Result<Option<Tz>, crate::err::Error> {
+        tz::get_timezone(self.db, self.chat_id, self.user_id).await
+    }
+
+    /// Tag of the chat's reminders by its key from callback data.
+    /// The key of a tag no reminders have anymore filters out all of them.
+    async fn find_tag(&self, tag_key: Option<&str>) -> Option<String> {
+        let tag_key = tag_key?;
+        let tags =
+            self.db
+                .get_chat_tags(self.chat_id.0)
+                .await
+                .unwrap_or_else(|err| {
+                    log::error!("{}", err);
+                    vec![]
+                });
+        Some(
+            tags.into_iter()
+                .map(|(tag, _)| tag)
+                .find(|tag| parsers::tag_key(tag) == tag_key)
+                .unwrap_or_else(|| tag_key.to_owned()),
+        )
+    }
+
+    pub async fn reply<R: Localize>(
         &self,
         response: R,
     ) -> Result<(), RequestError> {
-        tg::send_silent_message(&response.to_string(), self.bot, self.chat_id)
-            .await
+        tg::send_silent_message(
+            &response.localize(self.locale),
+            self.bot,
+            self.chat_id,
+        )
+        .await
     }
 
     pub async fn start(&self) -> Result<(), RequestError> {
         self.reply(TgResponse::Hello).await
     }
 
-    /// Send a list of all notifications
-    pub async fn list(&self) -> Result<(), RequestError> {
-        // Format reminders
-        let text = match tz::get_user_timezone(self.db, self.user_id).await {
+    /// Send the first page of the list of all notifications
+    /// (or only the ones with the given tag)
+    pub async fn list(&self, tag_arg: &str) -> Result<(), RequestError> {
+        let tag = match get_tag_filter(tag_arg) {
+            Ok(tag) => tag,
+            Err(response) => return self.reply(response).await,
+        };
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
             Ok(Some(user_timezone)) => {
-                match self.db.get_sorted_all_reminders(self.chat_id.0).await {
-                    Ok(sorted_reminders) => std::iter::once(
-                        TgResponse::RemindersListHeader.to_string(),
-                    )
-                    .chain(
-                        sorted_reminders
-                            .into_iter()
-                            .map(|rem| rem.to_string(user_timezone)),
+                match self.get_list_page(0, tag.as_deref(), user_timezone).await
+                {
+                    Ok((text, markup)) => {
+                        tg::send_markup(&text, markup, self.bot, self.chat_id)
+                            .await
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        self.reply(TgResponse::QueryingError).await
+                    }
+                }
+            }
+            _ => self.reply(TgResponse::NoChosenTimezone).await,
+        }
+    }
+
+    /// Switch the list's page
+    pub async fn list_set_page(
+        &self,
+        page_num: usize,
+        tag_key: Option<&str>,
+    ) -> Result<(), RequestError> {
+        let tag = self.find_tag(tag_key).await;
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+            Ok(Some(user_timezone)) => {
+                match self
+                    .get_list_page(page_num, tag.as_deref(), user_timezone)
+                    .await
+                {
+                    Ok((text, markup)) => {
+                        tg::edit_text_and_markup(
+                            &text,
+                            markup,
+                            self.bot,
+                            self.msg_id,
+                            self.chat_id,
+                        )
+                        .await
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        self.reply(TgResponse::QueryingError).await
+                    }
+                }
+            }
+            _ => self.reply(TgResponse::NoChosenTimezone).await,
+        }
+    }
+
+    /// Text and navigation markup of the list's page grouped by dates
+    async fn get_list_page(
+        &self,
+        page_num: usize,
+        tag: Option<&str>,
+        user_timezone: Tz,
+    ) -> Result<(String, InlineKeyboardMarkup), db::Error> {
+        let sorted_reminders = self
+            .db
+            .get_sorted_reminders(self.chat_id.0, tag, false, false)
+            .await?;
+        let header = match tag {
+            Some(tag) => TgResponse::TaggedRemindersListHeader(tag.to_owned()),
+            None => TgResponse::RemindersListHeader,
+        };
+        let mut pages = format::format_reminders_list_pages(
+            &header.localize(self.locale),
+            &sorted_reminders,
+            user_timezone,
+            self.time_format,
+            parsers::now_time(),
+            LIST_PAGE_SIZE,
+            tg::MAX_MESSAGE_LEN,
+            self.locale,
+        );
+        // The list could shrink since the page was shown
+        let page_num = page_num.min(pages.len() - 1);
+        let markup = self.get_markup_for_list_page(page_num, pages.len(), tag);
+        Ok((pages.swap_remove(page_num), markup))
+    }
+
+    fn get_markup_for_list_page(
+        &self,
+        num: usize,
+        num_pages: usize,
+        tag: Option<&str>,
+    ) -> InlineKeyboardMarkup {
+        let mut move_buttons = vec![];
+        if num > 0 {
+            move_buttons.push(InlineKeyboardButton::new(
+                "⬅️",
+                InlineKeyboardButtonKind::CallbackData(get_page_cb_data(
+                    "list",
+                    num - 1,
+                    tag,
+                )),
+            ))
+        }
+        if num + 1 < num_pages {
+            move_buttons.push(InlineKeyboardButton::new(
+                "➡️",
+                InlineKeyboardButtonKind::CallbackData(get_page_cb_data(
+                    "list",
+                    num + 1,
+                    tag,
+                )),
+            ))
+        }
+        InlineKeyboardMarkup::default().append_row(move_buttons)
+    }
+
+    /// Send the reminders matching the query with buttons to alter them
+    pub async fn search(&self, query: &str) -> Result<(), RequestError> {
+        if parsers::parse_search_terms(query).is_empty() {
+            return self.reply(TgResponse::EmptySearchQuery).await;
+        }
+        let user_timezone =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => user_timezone,
+                _ => return self.reply(TgResponse::NoChosenTimezone).await,
+            };
+        // Reminders aren't `Send`, so format them before sending
+        let results =
+            match self.db.search_reminders(self.chat_id.0, query).await {
+                Ok(found) if found.is_empty() => Ok(None),
+                Ok(found) => {
+                    let found = &found[..found.len().min(SEARCH_RESULTS_LIMIT)];
+                    let text = std::iter::once(
+                        TgResponse::SearchResultsHeader.localize(self.locale),
                     )
+                    .chain(found.iter().enumerate().map(|(i, rem)| {
+                        format!(
+                            r"{}\. {}",
+                            i + 1,
+                            rem.to_string(
+                                user_timezone,
+                                self.time_format,
+                                self.locale
+                            )
+                        )
+                    }))
                     .collect::<Vec<String>>()
-                    .join("\n"),
+                    .join("\n");
+                    Ok(Some((text, self.get_markup_for_search_results(found))))
+                }
+                Err(err) => Err(err),
+            };
+        match results {
+            Ok(Some((text, markup))) => {
+                tg::send_markup(&text, markup, self.bot, self.chat_id).await
+            }
+            Ok(None) => self.reply(TgResponse::NothingFound).await,
+            Err(err) => {
+                log::error!("{}", err);
+                self.reply(TgResponse::QueryingError).await
+            }
+        }
+    }
+
+    /// Markup with edit, delete and pause buttons for each found reminder
+    fn get_markup_for_search_results(
+        &self,
+        found: &[Box<dyn GenericReminder>],
+    ) -> InlineKeyboardMarkup {
+        let mut markup = InlineKeyboardMarkup::default();
+        for (i, rem) in found.iter().enumerate() {
+            let rem_id = rem.get_id().unwrap();
+            let button = |text: &str, kind: &str| {
+                InlineKeyboardButton::new(
+                    format!("{} {}", text, i + 1),
+                    InlineKeyboardButtonKind::CallbackData(format!(
+                        "searchrem::{}::{}::{}",
+                        kind,
+                        rem.get_type(),
+                        rem_id
+                    )),
+                )
+            };
+            markup = markup.append_row(vec![
+                button("📝", "edit"),
+                button("🗑", "del"),
+                button("⏸", "pause"),
+            ]);
+        }
+        markup
+    }
+
+    /// Send the tags of the chat's reminders with their counts
+    pub async fn tags(&self) -> Result<(), RequestError> {
+        let text = match self.db.get_chat_tags(self.chat_id.0).await {
+            Ok(tags) if tags.is_empty() => {
+                TgResponse::NoTags.localize(self.locale)
+            }
+            Ok(tags) => std::iter::once(
+                TgResponse::TagsListHeader.localize(self.locale),
+            )
+            .chain(
+                tags.iter()
+                    .map(|(name, count)| format::format_tag(name, *count)),
+            )
+            .collect::<Vec<String>>()
+            .join("\n"),
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::QueryingError.localize(self.locale)
+            }
+        };
+        self.reply(&text).await
+    }
+
+    /// Show, set or disable the chat's daily digest
+    pub async fn digest(&self, arg: &str) -> Result<(), RequestError> {
+        let arg = arg.trim();
+        let response = if arg.is_empty() {
+            match self.db.get_chat_digest(self.chat_id.0).await {
+                Ok(Some(digest)) => TgResponse::DigestSchedule(
+                    self.time_format.time(&digest.time),
+                    digest.include_empty,
+                ),
+                Ok(None) => TgResponse::DigestDisabled,
+                Err(err) => {
+                    log::error!("{}", err);
+                    TgResponse::QueryingError
+                }
+            }
+        } else if arg.eq_ignore_ascii_case("off") {
+            match self.db.delete_chat_digest(self.chat_id.0).await {
+                Ok(_) => TgResponse::DigestDisabled,
+                Err(err) => {
+                    log::error!("{}", err);
+                    TgResponse::FailedSetDigest
+                }
+            }
+        } else if let Some((time, include_empty)) =
+            parsers::parse_digest_args(arg)
+        {
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => {
+                    let digest = chat_digest::ActiveModel {
+                        id: NotSet,
+                        chat_id: Set(self.chat_id.0),
+                        user_id: Set(self.user_id.0 as i64),
+                        time: Set(time),
+                        include_empty: Set(include_empty),
+                        next_time: Set(date::next_time_of_day(
+                            time,
+                            user_timezone,
+                            parsers::now_time(),
+                        )),
+                    };
+                    match self.db.set_chat_digest(digest).await {
+                        Ok(()) => TgResponse::DigestSchedule(
+                            self.time_format.time(&time),
+                            include_empty,
+                        ),
+                        Err(err) => {
+                            log::error!("{}", err);
+                            TgResponse::FailedSetDigest
+                        }
+                    }
+                }
+                _ => TgResponse::NoChosenTimezone,
+            }
+        } else {
+            TgResponse::DigestUsage
+        };
+        self.reply(response).await
+    }
+
+    /// Show, set or disable the user's quiet hours
+    pub async fn quiet(&self, arg: &str) -> Result<(), RequestError> {
+        let arg = arg.trim();
+        let user_id = self.user_id.0 as i64;
+        let format_time = |time: NaiveTime| self.time_format.time(&time);
+        let response = if arg.is_empty() {
+            match self.db.get_user_quiet_hours(user_id).await {
+                Ok(Some(quiet_hours)) => TgResponse::QuietHours(
+                    format_time(quiet_hours.start_time),
+                    format_time(quiet_hours.end_time),
+                    quiet_hours.silent,
+                ),
+                Ok(None) => TgResponse::QuietHoursDisabled,
+                Err(err) => {
+                    log::error!("{}", err);
+                    TgResponse::QueryingError
+                }
+            }
+        } else if arg.eq_ignore_ascii_case("off") {
+            match self.db.delete_user_quiet_hours(user_id).await {
+                Ok(_) => TgResponse::QuietHoursDisabled,
+                Err(err) => {
+                    log::error!("{}", err);
+                    TgResponse::FailedSetQuietHours
+                }
+            }
+        } else if let Some((start_time, end_time, silent)) =
+            parsers::parse_quiet_hours_args(arg)
+        {
+            let quiet_hours = user_quiet_hours::ActiveModel {
+                id: NotSet,
+                user_id: Set(user_id),
+                start_time: Set(start_time),
+                end_time: Set(end_time),
+                silent: Set(silent),
+            };
+            match self.db.set_user_quiet_hours(quiet_hours).await {
+                Ok(()) => TgResponse::QuietHours(
+                    format_time(start_time),
+                    format_time(end_time),
+                    silent,
+                ),
+                Err(err) => {
+                    log::error!("{}", err);
+                    TgResponse::FailedSetQuietHours
+                }
+            }
+        } else {
+            TgResponse::QuietHoursUsage
+        };
+        self.reply(response).await
+    }
+
+    /// Send a markup to select a reminder to show its next occurrences
+    /// or show them right away for the reminder given by its number
+    pub async fn next(&self, arg: &str) -> Result<(), RequestError> {
+        let user_timezone =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => user_timezone,
+                _ => return self.reply(TgResponse::NoChosenTimezone).await,
+            };
+        if arg.trim().is_empty() {
+            let markup = self
+                .get_markup_for_reminders_page_next(0, None, user_timezone)
+                .await;
+            self.start_alter(TgResponse::ChooseNextReminder, markup)
+                .await
+        } else if let Some((rem_type, rem_id)) =
+            parsers::parse_reminder_ref(arg)
+        {
+            self.next_occurrences(rem_type, rem_id).await
+        } else {
+            self.reply(TgResponse::NextUsage).await
+        }
+    }
+
+    /// Reminder or cron reminder of the chat by its type and id
+    async fn get_chat_reminder(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<Option<Box<dyn GenericReminder + Send>>, db::Error> {
+        let chat_id = self.chat_id.0;
+        match rem_type {
+            "rem" => self.db.get_reminder(rem_id).await.map(|rem| {
+                rem.filter(|rem| rem.chat_id == chat_id).map(
+                    |rem| -> Box<dyn GenericReminder + Send> {
+                        Box::new(rem.into_active_model())
+                    },
+                )
+            }),
+            _ => self.db.get_cron_reminder(rem_id).await.map(|rem| {
+                rem.filter(|rem| rem.chat_id == chat_id).map(
+                    |rem| -> Box<dyn GenericReminder + Send> {
+                        Box::new(rem.into_active_model())
+                    },
+                )
+            }),
+        }
+    }
+
+    /// Ask for the reminder replacing the chosen one
+    /// offering to copy the latter as text or to change only its part
+    async fn enter_new_reminder(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        let button = |key, cb_data| {
+            InlineKeyboardButton::new(
+                i18n::tr(self.locale, key),
+                InlineKeyboardButtonKind::CallbackData(cb_data),
+            )
+        };
+        let part_button = |key, edit_mode: EditMode| {
+            button(
+                key,
+                format!(
+                    "editpart::{}::{}::{}",
+                    edit_mode.code(),
+                    rem_type,
+                    rem_id
+                ),
+            )
+        };
+        let markup = InlineKeyboardMarkup::default()
+            .append_row(vec![button(
+                "button-copy-text",
+                format!("copyrem::{}::{}", rem_type, rem_id),
+            )])
+            .append_row(vec![
+                part_button("button-change-text", EditMode::Text),
+                part_button("button-change-pattern", EditMode::Pattern),
+                part_button("button-change-timezone", EditMode::Timezone),
+            ]);
+        tg::send_markup(
+            &TgResponse::EnterNewReminder.localize(self.locale),
+            markup,
+            self.bot,
+            self.chat_id,
+        )
+        .await
+    }
+
+    /// Ask for the part of the reminder replacing the current one
+    pub async fn enter_reminder_part(
+        &self,
+        edit_mode: EditMode,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        let chat_id = self.chat_id.0;
+        let response = match self.get_chat_reminder(rem_type, rem_id).await {
+            Ok(Some(_)) => {
+                let edit_mode_code = Some(edit_mode.code());
+                match self
+                    .db
+                    .reset_reminders_edit(chat_id)
+                    .await
+                    .and(self.db.reset_cron_reminders_edit(chat_id).await)
+                    .and(match rem_type {
+                        "rem" => {
+                            self.db
+                                .mark_reminder_as_edit(rem_id, edit_mode_code)
+                                .await
+                        }
+                        _ => {
+                            self.db
+                                .mark_cron_reminder_as_edit(
+                                    rem_id,
+                                    edit_mode_code,
+                                )
+                                .await
+                        }
+                    }) {
+                    Ok(()) => TgResponse::EnterReminderPart(edit_mode),
                     Err(err) => {
                         log::error!("{}", err);
-                        TgResponse::QueryingError.to_string()
+                        TgResponse::FailedEdit
                     }
                 }
             }
-            _ => TgResponse::NoChosenTimezone.to_string(),
+            Ok(None) => TgResponse::ReminderNotFound,
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::QueryingError
+            }
+        };
+        self.reply(response).await
+    }
+
+    /// Send the text creating the reminder, tapping it copies the text
+    pub async fn copy_reminder_text(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        let user_timezone =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => user_timezone,
+                _ => return self.reply(TgResponse::NoChosenTimezone).await,
+            };
+        let text = match self.get_chat_reminder(rem_type, rem_id).await {
+            Ok(Some(reminder)) => format!(
+                "{}\n{}",
+                TgResponse::CopyReminderText.localize(self.locale),
+                markdown::code_inline(&reminder.to_text(user_timezone))
+            ),
+            Ok(None) => TgResponse::ReminderNotFound.localize(self.locale),
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::QueryingError.localize(self.locale)
+            }
         };
         self.reply(&text).await
     }
 
-    /// Send a markup with all timezones to select
+    /// Send the upcoming firings of the chat's reminder
+    pub async fn next_occurrences(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        let user_timezone =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => user_timezone,
+                _ => return self.reply(TgResponse::NoChosenTimezone).await,
+            };
+        let text = match self.get_chat_reminder(rem_type, rem_id).await {
+            Ok(Some(reminder)) => {
+                let times: Vec<NaiveDateTime> = reminder
+                    .occurrences(user_timezone)
+                    .take(NEXT_OCCURRENCES_COUNT)
+                    .collect();
+                format!(
+                    "{}\n{}",
+                    TgResponse::NextOccurrencesHeader(
+                        parsers::format_reminder_ref(rem_type, rem_id),
+                        reminder.to_unescaped_string(
+                            user_timezone,
+                            self.time_format,
+                            self.locale
+                        ),
+                    )
+                    .localize(self.locale),
+                    format::format_occurrences(
+                        &times,
+                        user_timezone,
+                        self.time_format,
+                        self.locale
+                    )
+                )
+            }
+            Ok(None) => TgResponse::ReminderNotFound.localize(self.locale),
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::QueryingError.localize(self.locale)
+            }
+        };
+        self.reply(&text).await
+    }
+
+    /// Markup with a button showing the next occurrences of the reminder
+    fn get_markup_for_next_occurrences(
+        &self,
+        reminder: &dyn GenericReminder,
+    ) -> InlineKeyboardMarkup {
+        InlineKeyboardMarkup::default().append_row(vec![
+            InlineKeyboardButton::new(
+                i18n::tr(self.locale, "button-next-occurrences"),
+                InlineKeyboardButtonKind::CallbackData(format!(
+                    "nextrem::{}_alt::{}",
+                    reminder.get_type(),
+                    reminder.get_id().unwrap()
+                )),
+            ),
+        ])
+    }
+
+    /// Send the first page of the sent reminders history
+    pub async fn history(&self) -> Result<(), RequestError> {
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+            Ok(Some(user_timezone)) => {
+                match self.get_history_page(0, user_timezone).await {
+                    Ok((text, markup)) => {
+                        tg::send_markup(&text, markup, self.bot, self.chat_id)
+                            .await
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        self.reply(TgResponse::QueryingError).await
+                    }
+                }
+            }
+            _ => self.reply(TgResponse::NoChosenTimezone).await,
+        }
+    }
+
+    /// Whether the user administers the group, only administrators
+    /// change the group's settings
+    async fn is_chat_admin(&self) -> Result<bool, RequestError> {
+        Ok(self
+            .bot
+            .get_chat_member(self.chat_id, self.user_id)
+            .await?
+            .is_privileged())
+    }
+
+    /// Send a markup with all timezones to select, in a group
+    /// the timezone of the group's reminders
     pub async fn choose_timezone(&self) -> Result<(), RequestError> {
+        let response = if self.chat_id.is_user() {
+            TgResponse::SelectTimezone
+        } else if self.is_chat_admin().await? {
+            TgResponse::SelectChatTimezone
+        } else {
+            // Without the group's timezone the member's own one is used,
+            // which they set in the private chat with the bot
+            let chat_timezone = self
+                .db
+                .get_chat_setting::<settings::Timezone>(self.chat_id.0)
+                .await
+                .unwrap_or_else(|err| {
+                    log::error!("{}", err);
+                    None
+                });
+            return self
+                .reply(match chat_timezone {
+                    Some(_) => TgResponse::NotChatAdmin,
+                    None => TgResponse::NoChatTimezone,
+                })
+                .await;
+        };
         tg::send_markup(
-            &TgResponse::SelectTimezone.to_string(),
+            &response.localize(self.locale),
             self.get_markup_for_tz_page_idx(0),
             self.bot,
             self.chat_id,
@@ -84,8 +852,21 @@ impl TgMessageController<'_> {
         .await
     }
 
-    /// Send user's timezone
+    /// Send the group's timezone if it's set or user's one
     pub async fn get_timezone(&self) -> Result<(), RequestError> {
+        if !self.chat_id.is_user() {
+            match self
+                .db
+                .get_chat_setting::<settings::Timezone>(self.chat_id.0)
+                .await
+            {
+                Ok(Some(tz_name)) => {
+                    return self.reply(TgResponse::ChatTimezone(tz_name)).await
+                }
+                Ok(None) => {}
+                Err(err) => log::error!("{}", err),
+            }
+        }
         let response =
             match self.db.get_user_timezone_name(self.user_id.0 as i64).await {
                 Ok(Some(tz_name)) => TgResponse::ChosenTimezone(tz_name),
@@ -98,22 +879,251 @@ impl TgMessageController<'_> {
         self.reply(response).await
     }
 
+    /// Send the menu of the user's settings
+    pub async fn settings(&self) -> Result<(), RequestError> {
+        match self.get_markup_for_settings().await {
+            Ok(markup) => {
+                tg::send_markup(
+                    &TgResponse::SettingsHeader.localize(self.locale),
+                    markup,
+                    self.bot,
+                    self.chat_id,
+                )
+                .await
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.reply(TgResponse::QueryingError).await
+            }
+        }
+    }
+
+    /// Show or set the language of the bot's messages,
+    /// "auto" follows the language of the user's Telegram
+    pub async fn language(&self, arg: &str) -> Result<(), RequestError> {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            return self
+                .reply(format!(
+                    "{}\n{}",
+                    TgResponse::ChosenLanguage.localize(self.locale),
+                    TgResponse::LanguageUsage.localize(self.locale)
+                ))
+                .await;
+        }
+        let user_id = self.user_id.0 as i64;
+        let result = if arg.eq_ignore_ascii_case("auto") {
+            match self
+                .db
+                .delete_user_setting::<settings::Language>(user_id)
+                .await
+            {
+                Ok(()) => {
+                    i18n::get_user_locale(self.db, self.user_id, None).await
+                }
+                Err(err) => Err(err),
+            }
+        } else if let Ok(locale) = arg.parse::<Locale>() {
+            self.db
+                .set_user_setting::<settings::Language>(user_id, &locale)
+                .await
+                .map(|()| locale)
+        } else {
+            return self.reply(TgResponse::LanguageUsage).await;
+        };
+        match result {
+            // Confirm in the new language
+            Ok(locale) => {
+                tg::send_silent_message(
+                    &TgResponse::ChosenLanguage.localize(locale),
+                    self.bot,
+                    self.chat_id,
+                )
+                .await
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.reply(TgResponse::FailedSetSetting).await
+            }
+        }
+    }
+
+    /// How the user wants reminders at repeated clock-change times to fire
+    async fn get_overlap_policy(&self) -> OverlapPolicy {
+        self.db
+            .get_user_setting::<settings::ClockChangeOverlap>(
+                self.user_id.0 as i64,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("{}", err);
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    /// Markup with a button per setting showing its current value
+    async fn get_markup_for_settings(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, db::Error> {
+        let user_id = self.user_id.0 as i64;
+        let timezone = self.db.get_user_timezone_name(user_id).await?;
+        let silent = self
+            .db
+            .get_user_setting::<settings::SilentNotifications>(user_id)
+            .await?
+            .unwrap_or(false);
+        let quiet_hours = self.db.get_user_quiet_hours(user_id).await?;
+        // The format could change since the controller was created
+        let time_format = TimeFormat::of_user(self.db, self.user_id).await?;
+        let snooze_minutes = self
+            .db
+            .get_user_setting::<settings::DefaultSnooze>(user_id)
+            .await?
+            .unwrap_or(tg::DEFAULT_SNOOZE_MINUTES);
+        let overlap = self
+            .db
+            .get_user_setting::<settings::ClockChangeOverlap>(user_id)
+            .await?
+            .unwrap_or_default();
+        let button = |text: String, setting: &str| {
+            vec![InlineKeyboardButton::new(
+                text,
+                InlineKeyboardButtonKind::CallbackData(format!(
+                    "settings::{}",
+                    setting
+                )),
+            )]
+        };
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(button(
+                match timezone {
+                    Some(timezone) => i18n::tr_with(
+                        self.locale,
+                        "button-timezone",
+                        [("timezone", timezone.into())],
+                    ),
+                    None => {
+                        i18n::tr(self.locale, "button-timezone-not-selected")
+                    }
+                },
+                "timezone",
+            ))
+            .append_row(button(
+                i18n::tr(self.locale, "button-language"),
+                "language",
+            ))
+            .append_row(button(
+                i18n::tr(
+                    self.locale,
+                    if silent {
+                        "button-notifications-silent"
+                    } else {
+                        "button-notifications-sound"
+                    },
+                ),
+                "notifications",
+            ))
+            .append_row(button(
+                match quiet_hours {
+                    Some(quiet_hours) => i18n::tr_with(
+                        self.locale,
+                        "button-quiet-hours",
+                        [
+                            (
+                                "start",
+                                time_format
+                                    .time(&quiet_hours.start_time)
+                                    .into(),
+                            ),
+                            (
+                                "end",
+                                time_format.time(&quiet_hours.end_time).into(),
+                            ),
+                        ],
+                    ),
+                    None => i18n::tr(self.locale, "button-quiet-hours-off"),
+                },
+                "quiet",
+            ))
+            .append_row(button(
+                i18n::tr_with(
+                    self.locale,
+                    "button-date-format",
+                    [(
+                        "example",
+                        time_format
+                            .day_month(
+                                &NaiveDate::from_ymd_opt(2000, 12, 31).unwrap(),
+                            )
+                            .into(),
+                    )],
+                ),
+                "date_format",
+            ))
+            .append_row(button(
+                i18n::tr(
+                    self.locale,
+                    if time_format.twelve_hour_clock {
+                        "button-clock-12"
+                    } else {
+                        "button-clock-24"
+                    },
+                ),
+                "clock",
+            ))
+            .append_row(button(
+                i18n::tr_with(
+                    self.locale,
+                    "button-default-snooze",
+                    [("minutes", snooze_minutes.into())],
+                ),
+                "snooze",
+            ))
+            .append_row(button(
+                i18n::tr(
+                    self.locale,
+                    match overlap {
+                        OverlapPolicy::Once => "button-overlap-once",
+                        OverlapPolicy::Twice => "button-overlap-twice",
+                    },
+                ),
+                "overlap",
+            )))
+    }
+
     /// General way to send a markup to select a reminder for some operation
     async fn start_alter(
         &self,
         response: TgResponse,
         markup: InlineKeyboardMarkup,
     ) -> Result<(), RequestError> {
-        tg::send_markup(&response.to_string(), markup, self.bot, self.chat_id)
-            .await
+        tg::send_markup(
+            &response.localize(self.locale),
+            markup,
+            self.bot,
+            self.chat_id,
+        )
+        .await
     }
 
     /// Send a markup to select a reminder for deleting
-    pub async fn start_delete(&self) -> Result<(), RequestError> {
-        match tz::get_user_timezone(self.db, self.user_id).await {
+    pub async fn start_delete(
+        &self,
+        tag_arg: &str,
+    ) -> Result<(), RequestError> {
+        let tag = match get_tag_filter(tag_arg) {
+            Ok(tag) => tag,
+            Err(response) => return self.reply(response).await,
+        };
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
             Ok(Some(user_timezone)) => {
                 let markup = self
-                    .get_markup_for_reminders_page_deletion(0, user_timezone)
+                    .get_markup_for_reminders_page_deletion(
+                        0,
+                        tag.as_deref(),
+                        user_timezone,
+                    )
                     .await;
                 self.start_alter(TgResponse::ChooseDeleteReminder, markup)
                     .await
@@ -123,11 +1133,19 @@ impl TgMessageController<'_> {
     }
 
     /// Send a markup to select a reminder for editing
-    pub async fn start_edit(&self) -> Result<(), RequestError> {
-        match tz::get_user_timezone(self.db, self.user_id).await {
+    pub async fn start_edit(&self, tag_arg: &str) -> Result<(), RequestError> {
+        let tag = match get_tag_filter(tag_arg) {
+            Ok(tag) => tag,
+            Err(response) => return self.reply(response).await,
+        };
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
             Ok(Some(user_timezone)) => {
                 let markup = self
-                    .get_markup_for_reminders_page_editing(0, user_timezone)
+                    .get_markup_for_reminders_page_editing(
+                        0,
+                        tag.as_deref(),
+                        user_timezone,
+                    )
                     .await;
                 self.start_alter(TgResponse::ChooseEditReminder, markup)
                     .await
@@ -154,11 +1172,19 @@ impl TgMessageController<'_> {
     }
 
     /// Send a markup to select a reminder for pausing
-    pub async fn start_pause(&self) -> Result<(), RequestError> {
-        match tz::get_user_timezone(self.db, self.user_id).await {
+    pub async fn start_pause(&self, tag_arg: &str) -> Result<(), RequestError> {
+        let tag = match get_tag_filter(tag_arg) {
+            Ok(tag) => tag,
+            Err(response) => return self.reply(response).await,
+        };
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
             Ok(Some(user_timezone)) => {
                 let markup = self
-                    .get_markup_for_reminders_page_pausing(0, user_timezone)
+                    .get_markup_for_reminders_page_pausing(
+                        0,
+                        tag.as_deref(),
+                        user_timezone,
+                    )
                     .await;
                 self.start_alter(TgResponse::ChoosePauseReminder, markup)
                     .await
@@ -171,31 +1197,73 @@ impl TgMessageController<'_> {
     async fn set_reminder(
         &self,
         text: &str,
+        entities: &[MessageEntity],
         silent_success: bool,
+        reply_to: Option<&ReplyTo<'_>>,
+        attachment: Option<&Attachment>,
     ) -> Result<ReminderSetResult, RequestError> {
         let user_id_raw = self.user_id.0;
-        match tz::get_user_timezone(self.db, self.user_id).await {
+        // Members mentioned first in a group are the reminder's recipients
+        let (recipients, reminder_text) = if self.chat_id.is_user() {
+            (vec![], text)
+        } else {
+            parsers::split_recipients(text, entities)
+        };
+        let recipients = Recipient::to_column(&recipients);
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
             Ok(Some(user_timezone)) => {
                 if let Some(cron_reminder) = parsers::parse_cron_reminder(
-                    text,
+                    reminder_text,
                     self.chat_id.0,
                     user_id_raw,
                     user_timezone,
                 )
                 .await
                 {
-                    match self
-                        .db
-                        .insert_cron_reminder(cron_reminder.clone())
-                        .await
-                    {
-                        Ok(_) => {
+                    let cron_reminder = match reply_to {
+                        Some(reply_to) => cron_reminder::ActiveModel {
+                            desc: Set(
+                                reply_to.describe(cron_reminder.desc.as_ref())
+                            ),
+                            reply_to_message_id: Set(Some(reply_to.message_id)),
+                            reply_to_chat_id: Set(reply_to.chat_id),
+                            ..cron_reminder
+                        },
+                        None => cron_reminder,
+                    };
+                    let cron_reminder = cron_reminder::ActiveModel {
+                        recipients: Set(recipients),
+                        entities: Set(parsers::desc_entities(
+                            text,
+                            cron_reminder.desc.as_ref(),
+                            entities,
+                        )),
+                        media_type: Set(
+                            attachment.map(|x| x.media_type.code().to_owned())
+                        ),
+                        file_id: Set(attachment.map(|x| x.file_id.clone())),
+                        ..cron_reminder
+                    };
+                    match self.db.insert_cron_reminder(cron_reminder).await {
+                        Ok(cron_reminder) => {
                             if !silent_success {
                                 let rem_str = cron_reminder
-                                    .to_unescaped_string(user_timezone);
-                                self.reply(TgResponse::SuccessPeriodicInsert(
-                                    rem_str,
-                                ))
+                                    .to_unescaped_string(
+                                        user_timezone,
+                                        self.time_format,
+                                        self.locale,
+                                    );
+                                let markup = self
+                                    .get_markup_for_next_occurrences(
+                                        &cron_reminder,
+                                    );
+                                tg::send_markup(
+                                    &TgResponse::SuccessPeriodicInsert(rem_str)
+                                        .localize(self.locale),
+                                    markup,
+                                    self.bot,
+                                    self.chat_id,
+                                )
                                 .await?;
                             };
                             Ok(ReminderSetResult::Reminder(Box::new(
@@ -209,20 +1277,63 @@ impl TgMessageController<'_> {
                         }
                     }
                 } else if let Some(reminder) = parsers::parse_reminder(
-                    text,
+                    reminder_text,
                     self.chat_id.0,
                     user_id_raw,
                     user_timezone,
+                    self.get_overlap_policy().await,
+                    self.locale,
                 )
                 .await
                 {
+                    let reminder = match reply_to {
+                        Some(reply_to) => reminder::ActiveModel {
+                            desc: Set(reply_to.describe(reminder.desc.as_ref())),
+                            reply_to_message_id: Set(Some(reply_to.message_id)),
+                            reply_to_chat_id: Set(reply_to.chat_id),
+                            ..reminder
+                        },
+                        None => reminder,
+                    };
+                    let reminder = reminder::ActiveModel {
+                        recipients: Set(recipients),
+                        entities: Set(parsers::desc_entities(
+                            text,
+                            reminder.desc.as_ref(),
+                            entities,
+                        )),
+                        media_type: Set(
+                            attachment.map(|x| x.media_type.code().to_owned())
+                        ),
+                        file_id: Set(attachment.map(|x| x.file_id.clone())),
+                        ..reminder
+                    };
                     match self.db.insert_reminder(reminder.clone()).await {
                         Ok(reminder) => {
                             if !silent_success {
-                                let rem_str =
-                                    reminder.to_unescaped_string(user_timezone);
-                                self.reply(TgResponse::SuccessInsert(rem_str))
+                                let text = TgResponse::SuccessInsert(
+                                    reminder.to_unescaped_string(
+                                        user_timezone,
+                                        self.time_format,
+                                        self.locale,
+                                    ),
+                                )
+                                .localize(self.locale);
+                                if reminder.is_recurring() {
+                                    let markup = self
+                                        .get_markup_for_next_occurrences(
+                                            &reminder,
+                                        );
+                                    tg::send_markup(
+                                        &text,
+                                        markup,
+                                        self.bot,
+                                        self.chat_id,
+                                    )
                                     .await?;
+                                } else {
+                                    self.reply(&text).await?;
+                                }
                             }
                             Ok(ReminderSetResult::Reminder(Box::new(reminder)))
                         }
@@ -246,6 +1357,131 @@ impl TgMessageController<'_> {
         }
     }
 
+    /// Save the action on the reminder (soft-deleted if replaced
+    /// by a new one or deleted) to be able to undo it
+    async fn save_undo(
+        &self,
+        action: UndoAction,
+        (reminder_type, reminder_id): (&str, i64),
+        new_reminder: Option<(&str, i64)>,
+    ) -> Option<i64> {
+        let paused = match action {
+            UndoAction::Pause(paused) => Some(paused),
+            _ => None,
+        };
+        let undo = reminder_undo::ActiveModel {
+            id: NotSet,
+            chat_id: Set(self.chat_id.0),
+            action: Set(action.as_str().to_owned()),
+            reminder_type: Set(reminder_type.to_owned()),
+            reminder_id: Set(reminder_id),
+            paused: Set(paused),
+            new_reminder_type: Set(
+                new_reminder.map(|(rem_type, _)| rem_type.to_owned())
+            ),
+            new_reminder_id: Set(new_reminder.map(|(_, rem_id)| rem_id)),
+            time: Set(parsers::now_time()),
+        };
+        self.db
+            .insert_reminder_undo(undo)
+            .await
+            .map_err(|err| log::error!("{}", err))
+            .ok()
+    }
+
+    /// Reply with an undo button if the action can be undone
+    async fn reply_with_undo(
+        &self,
+        response: TgResponse,
+        undo_id: Option<i64>,
+    ) -> Result<(), RequestError> {
+        match undo_id {
+            Some(undo_id) => {
+                tg::send_markup(
+                    &response.localize(self.locale),
+                    self.get_markup_for_undo(undo_id),
+                    self.bot,
+                    self.chat_id,
+                )
+                .await
+            }
+            None => self.reply(response).await,
+        }
+    }
+
+    async fn apply_undo(
+        &self,
+        undo: Option<reminder_undo::Model>,
+    ) -> TgResponse {
+        let undo = match undo {
+            Some(undo) => undo,
+            None => return TgResponse::NothingToUndo,
+        };
+        let user_timezone =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => user_timezone,
+                _ => return TgResponse::NoChosenTimezone,
+            };
+        if let Err(err) = self.db.apply_reminder_undo(&undo).await {
+            log::error!("{}", err);
+            return TgResponse::FailedUndo;
+        }
+        let restored_reminder: Option<Box<dyn GenericReminder + Send>> =
+            match undo.reminder_type.as_str() {
+                "rem" => self.db.get_reminder(undo.reminder_id).await.map(
+                    |rem| -> Option<Box<dyn GenericReminder + Send>> {
+                        Some(Box::new(rem?.into_active_model()))
+                    },
+                ),
+                _ => self.db.get_cron_reminder(undo.reminder_id).await.map(
+                    |rem| -> Option<Box<dyn GenericReminder + Send>> {
+                        Some(Box::new(rem?.into_active_model()))
+                    },
+                ),
+            }
+            .unwrap_or_else(|err| {
+                log::error!("{}", err);
+                None
+            });
+        match restored_reminder {
+            Some(restored_reminder) => {
+                TgResponse::SuccessUndo(restored_reminder.to_unescaped_string(
+                    user_timezone,
+                    self.time_format,
+                    self.locale,
+                ))
+            }
+            None => TgResponse::FailedUndo,
+        }
+    }
+
+    /// Undo the last deletion, edit or pause in the chat
+    pub async fn undo_last(&self) -> Result<(), RequestError> {
+        let response = match self
+            .db
+            .get_last_chat_reminder_undo(self.chat_id.0, undo_period_start())
+            .await
+        {
+            Ok(undo) => self.apply_undo(undo).await,
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::FailedUndo
+            }
+        };
+        self.reply(response).await
+    }
+
+    fn get_markup_for_undo(&self, undo_id: i64) -> InlineKeyboardMarkup {
+        InlineKeyboardMarkup::default().append_row(vec![
+            InlineKeyboardButton::new(
+                i18n::tr(self.locale, "button-undo"),
+                InlineKeyboardButtonKind::CallbackData(
+                    "undo::".to_owned() + &undo_id.to_string(),
+                ),
+            ),
+        ])
+    }
+
     pub async fn incorrect_request(&self) -> Result<(), RequestError> {
         self.reply(TgResponse::IncorrectRequest).await
     }
@@ -274,12 +1510,18 @@ impl TgMessageController<'_> {
     pub async fn delete_reminder_set_page(
         &self,
         page_num: usize,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
+        let tag = self.find_tag(tag_key).await;
         if let Ok(Some(user_timezone)) =
-            tz::get_user_timezone(self.db, self.user_id).await
+            tz::get_timezone(self.db, self.chat_id, self.user_id).await
         {
             let markup = self
-                .get_markup_for_reminders_page_deletion(page_num, user_timezone)
+                .get_markup_for_reminders_page_deletion(
+                    page_num,
+                    tag.as_deref(),
+                    user_timezone,
+                )
                 .await;
             self.alter_reminder_set_page(markup).await
         } else {
@@ -290,12 +1532,18 @@ impl TgMessageController<'_> {
     pub async fn edit_reminder_set_page(
         &self,
         page_num: usize,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
+        let tag = self.find_tag(tag_key).await;
         if let Ok(Some(user_timezone)) =
-            tz::get_user_timezone(self.db, self.user_id).await
+            tz::get_timezone(self.db, self.chat_id, self.user_id).await
         {
             let markup = self
-                .get_markup_for_reminders_page_editing(page_num, user_timezone)
+                .get_markup_for_reminders_page_editing(
+                    page_num,
+                    tag.as_deref(),
+                    user_timezone,
+                )
                 .await;
             self.alter_reminder_set_page(markup).await
         } else {
@@ -306,12 +1554,40 @@ impl TgMessageController<'_> {
     pub async fn pause_reminder_set_page(
         &self,
         page_num: usize,
+        tag_key: Option<&str>,
+    ) -> Result<(), RequestError> {
+        let tag = self.find_tag(tag_key).await;
+        if let Ok(Some(user_timezone)) =
+            tz::get_timezone(self.db, self.chat_id, self.user_id).await
+        {
+            let markup = self
+                .get_markup_for_reminders_page_pausing(
+                    page_num,
+                    tag.as_deref(),
+                    user_timezone,
+                )
+                .await;
+            self.alter_reminder_set_page(markup).await
+        } else {
+            self.reply(TgResponse::NoChosenTimezone).await
+        }
+    }
+
+    pub async fn next_reminder_set_page(
+        &self,
+        page_num: usize,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
+        let tag = self.find_tag(tag_key).await;
         if let Ok(Some(user_timezone)) =
-            tz::get_user_timezone(self.db, self.user_id).await
+            tz::get_timezone(self.db, self.chat_id, self.user_id).await
         {
             let markup = self
-                .get_markup_for_reminders_page_pausing(page_num, user_timezone)
+                .get_markup_for_reminders_page_next(
+                    page_num,
+                    tag.as_deref(),
+                    user_timezone,
+                )
                 .await;
             self.alter_reminder_set_page(markup).await
         } else {
@@ -319,6 +1595,85 @@ impl TgMessageController<'_> {
         }
     }
 
+    /// Switch the history's page
+    pub async fn history_set_page(
+        &self,
+        page_num: u64,
+    ) -> Result<(), RequestError> {
+        match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+            Ok(Some(user_timezone)) => {
+                match self.get_history_page(page_num, user_timezone).await {
+                    Ok((text, markup)) => {
+                        tg::edit_text_and_markup(
+                            &text,
+                            markup,
+                            self.bot,
+                            self.msg_id,
+                            self.chat_id,
+                        )
+                        .await
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        self.reply(TgResponse::QueryingError).await
+                    }
+                }
+            }
+            _ => self.reply(TgResponse::NoChosenTimezone).await,
+        }
+    }
+
+    async fn get_history_page(
+        &self,
+        num: u64,
+        user_timezone: Tz,
+    ) -> Result<(String, InlineKeyboardMarkup), db::Error> {
+        let (deliveries, num_pages) = self
+            .db
+            .get_chat_reminder_deliveries_page(
+                self.chat_id.0,
+                num,
+                HISTORY_PAGE_SIZE,
+            )
+            .await?;
+        let text = if deliveries.is_empty() {
+            TgResponse::EmptyHistory.localize(self.locale)
+        } else {
+            std::iter::once(TgResponse::HistoryHeader.localize(self.locale))
+                .chain(deliveries.iter().map(|delivery| {
+                    format::format_reminder_delivery(
+                        delivery,
+                        user_timezone,
+                        self.time_format,
+                        self.locale,
+                    )
+                }))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let mut move_buttons = vec![];
+        if num > 0 {
+            move_buttons.push(InlineKeyboardButton::new(
+                "⬅️",
+                InlineKeyboardButtonKind::CallbackData(
+                    "history::page::".to_owned() + &(num - 1).to_string(),
+                ),
+            ))
+        }
+        if num + 1 < num_pages {
+            move_buttons.push(InlineKeyboardButton::new(
+                "➡️",
+                InlineKeyboardButtonKind::CallbackData(
+                    "history::page::".to_owned() + &(num + 1).to_string(),
+                ),
+            ))
+        }
+        Ok((
+            text,
+            InlineKeyboardMarkup::default().append_row(move_buttons),
+        ))
+    }
+
     pub fn get_markup_for_tz_page_idx(
         &self,
         num: usize,
@@ -365,43 +1720,165 @@ impl TgMessageController<'_> {
         markup.append_row(move_buttons)
     }
 
-    async fn get_markup_for_reminders_page_alteration(
+    #[allow(clippy::too_many_arguments)]
+    async fn get_markup_for_reminders_page_alteration(
+        &self,
+        num: usize,
+        tag: Option<&str>,
+        cb_prefix: &str,
+        user_timezone: Tz,
+        exclude_reminders: bool,
+        exclude_cron_reminders: bool,
+        multi_select: bool,
+    ) -> InlineKeyboardMarkup {
+        let mut markup = InlineKeyboardMarkup::default();
+        let mut last_rem_page: bool = false;
+        let sorted_reminders = self
+            .db
+            .get_sorted_reminders(
+                self.chat_id.0,
+                tag,
+                exclude_reminders,
+                exclude_cron_reminders,
+            )
+            .await;
+        if let Some(reminders) = sorted_reminders
+            .ok()
+            .as_ref()
+            .and_then(|rems| rems.chunks(REMINDERS_PAGE_SIZE).nth(num))
+        {
+            for chunk in reminders.chunks(1) {
+                let mut row = vec![];
+                for rem in chunk {
+                    let rem_str = rem.to_unescaped_string(
+                        user_timezone,
+                        self.time_format,
+                        self.locale,
+                    );
+                    row.push(InlineKeyboardButton::new(
+                        rem_str,
+                        InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                            format!(
+                                "{}::{}_alt::{}",
+                                cb_prefix,
+                                rem.get_type(),
+                                rem.get_id().unwrap()
+                            ),
+                            tag,
+                        )),
+                    ))
+                }
+                markup = markup.append_row(row);
+            }
+        } else {
+            last_rem_page = true;
+        }
+        let mut move_buttons = vec![];
+        if num > 0 {
+            move_buttons.push(InlineKeyboardButton::new(
+                "⬅️",
+                InlineKeyboardButtonKind::CallbackData(get_page_cb_data(
+                    cb_prefix,
+                    num - 1,
+                    tag,
+                )),
+            ))
+        }
+        if !last_rem_page {
+            move_buttons.push(InlineKeyboardButton::new(
+                "➡️",
+                InlineKeyboardButtonKind::CallbackData(get_page_cb_data(
+                    cb_prefix,
+                    num + 1,
+                    tag,
+                )),
+            ))
+        }
+        markup = markup.append_row(move_buttons);
+        if multi_select {
+            markup = markup.append_row(vec![InlineKeyboardButton::new(
+                i18n::tr(self.locale, "button-select-multiple"),
+                InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                    format!("{}::sel_page::{}", cb_prefix, num),
+                    tag,
+                )),
+            )]);
+        }
+        markup
+    }
+
+    /// Reminders (type and id) on the page of a reminders keyboard
+    async fn get_reminders_page(
+        &self,
+        num: usize,
+        tag: Option<&str>,
+    ) -> Result<Vec<(&'static str, i64)>, db::Error> {
+        Ok(self
+            .db
+            .get_sorted_reminders(self.chat_id.0, tag, false, false)
+            .await?
+            .chunks(REMINDERS_PAGE_SIZE)
+            .nth(num)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|rem| rem.get_id().map(|id| (rem.get_type(), id)))
+            .collect())
+    }
+
+    /// Markup to check the reminders to apply the action to all of them
+    async fn get_markup_for_reminders_page_selection(
         &self,
         num: usize,
-        cb_prefix: &str,
+        action: BulkAction,
+        tag: Option<&str>,
         user_timezone: Tz,
-        exclude_reminders: bool,
-        exclude_cron_reminders: bool,
     ) -> InlineKeyboardMarkup {
+        let cb_prefix = action.cb_prefix();
         let mut markup = InlineKeyboardMarkup::default();
         let mut last_rem_page: bool = false;
+        let selection = self
+            .db
+            .get_reminder_selection(self.chat_id.0, self.msg_id.0)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("{}", err);
+                vec![]
+            });
         let sorted_reminders = self
             .db
-            .get_sorted_reminders(
-                self.chat_id.0,
-                exclude_reminders,
-                exclude_cron_reminders,
-            )
+            .get_sorted_reminders(self.chat_id.0, tag, false, false)
             .await;
         if let Some(reminders) = sorted_reminders
             .ok()
             .as_ref()
-            .and_then(|rems| rems.chunks(45).nth(num))
+            .and_then(|rems| rems.chunks(REMINDERS_PAGE_SIZE).nth(num))
         {
-            for chunk in reminders.chunks(1) {
-                let mut row = vec![];
-                for rem in chunk {
-                    let rem_str = rem.to_unescaped_string(user_timezone);
-                    row.push(InlineKeyboardButton::new(
-                        rem_str,
-                        InlineKeyboardButtonKind::CallbackData(
-                            cb_prefix.to_owned()
-                                + &format!("::{}_alt::", rem.get_type())
-                                + &rem.get_id().unwrap().to_string(),
+            for rem in reminders {
+                let rem_id = rem.get_id().unwrap();
+                let selected = selection.iter().any(|(rem_type, id)| {
+                    rem_type == rem.get_type() && *id == rem_id
+                });
+                markup = markup.append_row(vec![InlineKeyboardButton::new(
+                    format!(
+                        "{} {}",
+                        if selected { "✅" } else { "⬜" },
+                        rem.to_unescaped_string(
+                            user_timezone,
+                            self.time_format,
+                            self.locale
+                        )
+                    ),
+                    InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                        format!(
+                            "{}::sel::{}::{}::{}",
+                            cb_prefix,
+                            num,
+                            rem.get_type(),
+                            rem_id
                         ),
-                    ))
-                }
-                markup = markup.append_row(row);
+                        tag,
+                    )),
+                )]);
             }
         } else {
             last_rem_page = true;
@@ -410,33 +1887,93 @@ impl TgMessageController<'_> {
         if num > 0 {
             move_buttons.push(InlineKeyboardButton::new(
                 "⬅️",
-                InlineKeyboardButtonKind::CallbackData(
-                    cb_prefix.to_owned() + "::page::" + &(num - 1).to_string(),
-                ),
+                InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                    format!("{}::sel_page::{}", cb_prefix, num - 1),
+                    tag,
+                )),
             ))
         }
         if !last_rem_page {
             move_buttons.push(InlineKeyboardButton::new(
                 "➡️",
-                InlineKeyboardButtonKind::CallbackData(
-                    cb_prefix.to_owned() + "::page::" + &(num + 1).to_string(),
-                ),
+                InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                    format!("{}::sel_page::{}", cb_prefix, num + 1),
+                    tag,
+                )),
             ))
         }
-        markup.append_row(move_buttons)
+        let apply_text = i18n::tr(
+            self.locale,
+            match action {
+                BulkAction::Delete => "button-delete-selected",
+                BulkAction::Pause => "button-pause-selected",
+            },
+        );
+        markup
+            .append_row(move_buttons)
+            .append_row(vec![InlineKeyboardButton::new(
+                i18n::tr(self.locale, "button-select-all"),
+                InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                    format!("{}::sel_all::{}", cb_prefix, num),
+                    tag,
+                )),
+            )])
+            .append_row(vec![
+                InlineKeyboardButton::new(
+                    apply_text,
+                    InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                        cb_prefix.to_owned() + "::sel_apply",
+                        tag,
+                    )),
+                ),
+                InlineKeyboardButton::new(
+                    i18n::tr(self.locale, "button-cancel"),
+                    InlineKeyboardButtonKind::CallbackData(with_tag_key(
+                        cb_prefix.to_owned() + "::sel_cancel",
+                        tag,
+                    )),
+                ),
+            ])
+    }
+
+    /// Switch the selection markup's page
+    pub async fn selection_set_page(
+        &self,
+        action: BulkAction,
+        page_num: usize,
+        tag: Option<&str>,
+    ) -> Result<(), RequestError> {
+        if let Ok(Some(user_timezone)) =
+            tz::get_timezone(self.db, self.chat_id, self.user_id).await
+        {
+            let markup = self
+                .get_markup_for_reminders_page_selection(
+                    page_num,
+                    action,
+                    tag,
+                    user_timezone,
+                )
+                .await;
+            self.alter_reminder_set_page(markup).await
+        } else {
+            self.reply(TgResponse::NoChosenTimezone).await
+        }
     }
 
     pub async fn get_markup_for_reminders_page_deletion(
         &self,
         num: usize,
+        tag: Option<&str>,
         user_timezone: Tz,
     ) -> InlineKeyboardMarkup {
         self.get_markup_for_reminders_page_alteration(
             num,
+            tag,
             "delrem",
             user_timezone,
             false,
             false,
+            true,
         )
         .await
     }
@@ -444,14 +1981,17 @@ impl TgMessageController<'_> {
     pub async fn get_markup_for_reminders_page_editing(
         &self,
         num: usize,
+        tag: Option<&str>,
         user_timezone: Tz,
     ) -> InlineKeyboardMarkup {
         self.get_markup_for_reminders_page_alteration(
             num,
+            tag,
             "editrem",
             user_timezone,
             false,
             false,
+            false,
         )
         .await
     }
@@ -459,144 +1999,541 @@ impl TgMessageController<'_> {
     pub async fn get_markup_for_reminders_page_pausing(
         &self,
         num: usize,
+        tag: Option<&str>,
         user_timezone: Tz,
     ) -> InlineKeyboardMarkup {
         self.get_markup_for_reminders_page_alteration(
             num,
+            tag,
             "pauserem",
             user_timezone,
             false,
             false,
+            true,
+        )
+        .await
+    }
+
+    pub async fn get_markup_for_reminders_page_next(
+        &self,
+        num: usize,
+        tag: Option<&str>,
+        user_timezone: Tz,
+    ) -> InlineKeyboardMarkup {
+        self.get_markup_for_reminders_page_alteration(
+            num,
+            tag,
+            "nextrem",
+            user_timezone,
+            false,
+            false,
+            false,
         )
         .await
     }
 
+    /// Replace the reminder with the one set by the text, keeping
+    /// the message it replies to and its attachment unless a new one is sent
     pub async fn replace_reminder(
         &self,
         text: &str,
+        entities: &[MessageEntity],
         rem_id: i64,
+        attachment: Option<&Attachment>,
     ) -> Result<(), RequestError> {
-        let response = match tz::get_user_timezone(self.db, self.user_id).await
-        {
-            Ok(Some(user_timezone)) => {
-                match self.db.get_reminder(rem_id).await {
-                    Ok(Some(old_reminder)) => {
-                        match self.set_reminder(text, true).await {
-                            Ok(ReminderSetResult::Reminder(new_reminder)) => {
-                                match self.db.delete_reminder(rem_id).await {
-                                    Ok(()) => TgResponse::SuccessEdit(
-                                        old_reminder
-                                            .into_active_model()
-                                            .to_unescaped_string(user_timezone),
-                                        new_reminder
-                                            .to_unescaped_string(user_timezone),
-                                    ),
-                                    Err(err) => {
-                                        log::error!("{}", err);
-                                        TgResponse::FailedEdit
+        let (response, undo_id) =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => {
+                    match self.db.get_reminder(rem_id).await {
+                        Ok(Some(old_reminder)) => {
+                            let kept_attachment = Attachment::from_columns(
+                                old_reminder.media_type.as_deref(),
+                                old_reminder.file_id.as_deref(),
+                            );
+                            match self
+                                .set_reminder(
+                                    text,
+                                    entities,
+                                    true,
+                                    get_kept_reply_to(
+                                        old_reminder.reply_to_chat_id,
+                                        old_reminder.reply_to_message_id,
+                                    )
+                                    .as_ref(),
+                                    attachment.or(kept_attachment.as_ref()),
+                                )
+                                .await
+                            {
+                                Ok(ReminderSetResult::Reminder(
+                                    new_reminder,
+                                )) => {
+                                    match self
+                                        .db
+                                        .mark_reminder_as_deleted(rem_id)
+                                        .await
+                                    {
+                                        Ok(()) => {
+                                            let undo_id = self
+                                                .save_undo(
+                                                    UndoAction::Edit,
+                                                    ("rem", rem_id),
+                                                    new_reminder.get_id().map(
+                                                        |id| {
+                                                            (
+                                                                new_reminder
+                                                                    .get_type(),
+                                                                id,
+                                                            )
+                                                        },
+                                                    ),
+                                                )
+                                                .await;
+                                            (
+                                                TgResponse::SuccessEdit(
+                                                    old_reminder
+                                                        .into_active_model()
+                                                        .to_unescaped_string(
+                                                            user_timezone,
+                                                            self.time_format,
+                                                            self.locale,
+                                                        ),
+                                                    new_reminder
+                                                        .to_unescaped_string(
+                                                            user_timezone,
+                                                            self.time_format,
+                                                            self.locale,
+                                                        ),
+                                                ),
+                                                undo_id,
+                                            )
+                                        }
+                                        Err(err) => {
+                                            log::error!("{}", err);
+                                            (TgResponse::FailedEdit, None)
+                                        }
                                     }
                                 }
+                                Err(err) => {
+                                    log::error!("{}", err);
+                                    (TgResponse::FailedEdit, None)
+                                }
+                                _ => (TgResponse::FailedEdit, None),
                             }
-                            Err(err) => {
-                                log::error!("{}", err);
-                                TgResponse::FailedEdit
-                            }
-                            _ => TgResponse::FailedEdit,
                         }
-                    }
-                    Err(err) => {
-                        log::error!("{}", err);
-                        TgResponse::FailedEdit
-                    }
-                    _ => {
-                        log::error!("missing reminder with id: {}", rem_id);
-                        TgResponse::FailedEdit
+                        Err(err) => {
+                            log::error!("{}", err);
+                            (TgResponse::FailedEdit, None)
+                        }
+                        _ => {
+                            log::error!("missing reminder with id: {}", rem_id);
+                            (TgResponse::FailedEdit, None)
+                        }
                     }
                 }
-            }
-            _ => TgResponse::NoChosenTimezone,
-        };
+                _ => (TgResponse::NoChosenTimezone, None),
+            };
 
-        self.reply(response).await
+        self.reply_with_undo(response, undo_id).await
     }
 
+    /// Replace the reminder with the one set by the text, keeping
+    /// the message it replies to and its attachment unless a new one is sent
     pub async fn replace_cron_reminder(
         &self,
         text: &str,
+        entities: &[MessageEntity],
         cron_rem_id: i64,
+        attachment: Option<&Attachment>,
     ) -> Result<(), RequestError> {
-        let response = match tz::get_user_timezone(self.db, self.user_id).await
-        {
-            Ok(Some(user_timezone)) => {
-                match self.db.get_cron_reminder(cron_rem_id).await {
-                    Ok(Some(old_cron_reminder)) => {
-                        match self.set_reminder(text, true).await {
-                            Ok(ReminderSetResult::Reminder(
-                                new_cron_reminder,
-                            )) => {
-                                match self
-                                    .db
-                                    .delete_cron_reminder(cron_rem_id)
-                                    .await
-                                {
-                                    Ok(()) => TgResponse::SuccessEdit(
-                                        old_cron_reminder
-                                            .into_active_model()
-                                            .to_unescaped_string(user_timezone),
-                                        new_cron_reminder
-                                            .to_unescaped_string(user_timezone),
-                                    ),
-                                    Err(err) => {
-                                        log::error!("{}", err);
-                                        TgResponse::FailedEdit
+        let (response, undo_id) =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => {
+                    match self.db.get_cron_reminder(cron_rem_id).await {
+                        Ok(Some(old_cron_reminder)) => {
+                            let kept_attachment = Attachment::from_columns(
+                                old_cron_reminder.media_type.as_deref(),
+                                old_cron_reminder.file_id.as_deref(),
+                            );
+                            match self
+                                .set_reminder(
+                                    text,
+                                    entities,
+                                    true,
+                                    get_kept_reply_to(
+                                        old_cron_reminder.reply_to_chat_id,
+                                        old_cron_reminder.reply_to_message_id,
+                                    )
+                                    .as_ref(),
+                                    attachment.or(kept_attachment.as_ref()),
+                                )
+                                .await
+                            {
+                                Ok(ReminderSetResult::Reminder(
+                                    new_cron_reminder,
+                                )) => {
+                                    match self
+                                        .db
+                                        .mark_cron_reminder_as_deleted(
+                                            cron_rem_id,
+                                        )
+                                        .await
+                                    {
+                                        Ok(()) => {
+                                            let undo_id = self
+                                                .save_undo(
+                                                    UndoAction::Edit,
+                                                    ("cron_rem", cron_rem_id),
+                                                    new_cron_reminder
+                                                        .get_id()
+                                                        .map(|id| {
+                                                            (
+                                                            new_cron_reminder
+                                                                .get_type(),
+                                                            id,
+                                                        )
+                                                        }),
+                                                )
+                                                .await;
+                                            (
+                                                TgResponse::SuccessEdit(
+                                                    old_cron_reminder
+                                                        .into_active_model()
+                                                        .to_unescaped_string(
+                                                            user_timezone,
+                                                            self.time_format,
+                                                            self.locale,
+                                                        ),
+                                                    new_cron_reminder
+                                                        .to_unescaped_string(
+                                                            user_timezone,
+                                                            self.time_format,
+                                                            self.locale,
+                                                        ),
+                                                ),
+                                                undo_id,
+                                            )
+                                        }
+                                        Err(err) => {
+                                            log::error!("{}", err);
+                                            (TgResponse::FailedEdit, None)
+                                        }
                                     }
                                 }
+                                Err(err) => {
+                                    log::error!("{}", err);
+                                    (TgResponse::FailedEdit, None)
+                                }
+                                _ => (TgResponse::FailedEdit, None),
                             }
-                            Err(err) => {
-                                log::error!("{}", err);
-                                TgResponse::FailedEdit
-                            }
-                            _ => TgResponse::FailedEdit,
                         }
-                    }
-                    Err(err) => {
-                        log::error!("{}", err);
-                        TgResponse::FailedEdit
-                    }
-                    _ => {
-                        log::error!(
-                            "missing cron reminder with id: {}",
-                            cron_rem_id
-                        );
-                        TgResponse::FailedEdit
+                        Err(err) => {
+                            log::error!("{}", err);
+                            (TgResponse::FailedEdit, None)
+                        }
+                        _ => {
+                            log::error!(
+                                "missing cron reminder with id: {}",
+                                cron_rem_id
+                            );
+                            (TgResponse::FailedEdit, None)
+                        }
                     }
                 }
-            }
-            _ => TgResponse::NoChosenTimezone,
-        };
+                _ => (TgResponse::NoChosenTimezone, None),
+            };
 
-        self.reply(response).await
+        self.reply_with_undo(response, undo_id).await
     }
 
     pub async fn set_or_edit_reminder(
         &self,
         text: &str,
+        entities: &[MessageEntity],
+        reply_to: Option<ReplyTo<'_>>,
+        attachment: Option<&Attachment>,
     ) -> Result<(), RequestError> {
         match (
             self.get_edit_reminder().await,
             self.get_edit_cron_reminder().await,
         ) {
-            (Ok(Some(edit_reminder)), _) => {
-                self.replace_reminder(text, edit_reminder.id).await
+            (Ok(Some(edit_reminder)), _) => match edit_reminder
+                .edit_mode
+                .as_deref()
+                .and_then(EditMode::from_code)
+            {
+                Some(edit_mode) => {
+                    self.edit_reminder_part(
+                        text,
+                        entities,
+                        edit_reminder,
+                        edit_mode,
+                    )
+                    .await
+                }
+                None => {
+                    self.replace_reminder(
+                        text,
+                        entities,
+                        edit_reminder.id,
+                        attachment,
+                    )
+                    .await
+                }
+            },
+            (_, Ok(Some(edit_cron_reminder))) => match edit_cron_reminder
+                .edit_mode
+                .as_deref()
+                .and_then(EditMode::from_code)
+            {
+                Some(edit_mode) => {
+                    self.edit_cron_reminder_part(
+                        text,
+                        entities,
+                        edit_cron_reminder,
+                        edit_mode,
+                    )
+                    .await
+                }
+                None => {
+                    self.replace_cron_reminder(
+                        text,
+                        entities,
+                        edit_cron_reminder.id,
+                        attachment,
+                    )
+                    .await
+                }
+            },
+            _ => {
+                let forwarded = match reply_to {
+                    None => self.get_pending_forward().await,
+                    Some(_) => None,
+                };
+                let reply_to = reply_to.or_else(|| {
+                    forwarded
+                        .as_ref()
+                        .map(|forwarded| forwarded.reply_to(self.chat_id.0))
+                });
+                let result = self
+                    .set_reminder(
+                        text,
+                        entities,
+                        false,
+                        reply_to.as_ref(),
+                        attachment,
+                    )
+                    .await?;
+                if let (ReminderSetResult::Reminder(_), Some(_)) =
+                    (result, forwarded)
+                {
+                    self.db
+                        .delete_user_setting::<settings::PendingForward>(
+                            self.user_id.0 as i64,
+                        )
+                        .await
+                        .unwrap_or_else(|err| log::error!("{}", err));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Message forwarded to the bot in a private chat waiting for a reminder
+    async fn get_pending_forward(&self) -> Option<parsers::ForwardedMessage> {
+        if !self.chat_id.is_user() {
+            return None;
+        }
+        self.db
+            .get_user_setting::<settings::PendingForward>(self.user_id.0 as i64)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("{}", err);
+                None
+            })
+    }
+
+    /// Set a reminder about the forwarded message if its caption is one,
+    /// otherwise the reminder sent next is set about the message
+    pub async fn set_forwarded_reminder(
+        &self,
+        msg_id: MessageId,
+        text: Option<&str>,
+        caption: Option<&str>,
+        entities: &[MessageEntity],
+    ) -> Result<(), RequestError> {
+        let reply_to = ReplyTo {
+            chat_id: Some(self.chat_id.0),
+            message_id: msg_id.0,
+            text,
+        };
+        match caption.filter(|caption| {
+            grammar::parse_reminder(caption, self.locale)
+                .is_ok_and(|reminder| reminder.pattern.is_some())
+        }) {
+            Some(caption) => {
+                self.set_or_edit_reminder(
+                    caption,
+                    entities,
+                    Some(reply_to),
+                    None,
+                )
+                .await
             }
-            (_, Ok(Some(edit_cron_reminder))) => {
-                self.replace_cron_reminder(text, edit_cron_reminder.id)
+            None => {
+                let forwarded = parsers::ForwardedMessage {
+                    message_id: msg_id.0,
+                    text: reply_to.describe(""),
+                };
+                match self
+                    .db
+                    .set_user_setting::<settings::PendingForward>(
+                        self.user_id.0 as i64,
+                        &forwarded,
+                    )
                     .await
+                {
+                    Ok(()) => self.reply(TgResponse::ReplyToForwarded).await,
+                    Err(err) => {
+                        log::error!("{}", err);
+                        self.reply(TgResponse::FailedInsert).await
+                    }
+                }
             }
-            _ => self.set_reminder(text, false).await.map(|_| ()),
         }
     }
 
+    /// Replace the chosen part of the reminder being edited
+    async fn edit_reminder_part(
+        &self,
+        text: &str,
+        entities: &[MessageEntity],
+        old_reminder: reminder::Model,
+        edit_mode: EditMode,
+    ) -> Result<(), RequestError> {
+        let (response, undo_id) =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => match parsers::parse_reminder_edit(
+                    old_reminder.clone(),
+                    edit_mode,
+                    text,
+                    entities,
+                    user_timezone,
+                    self.locale,
+                ) {
+                    Some(new_reminder) => {
+                        match self
+                            .db
+                            .replace_reminder(old_reminder.id, new_reminder)
+                            .await
+                        {
+                            Ok(new_reminder) => {
+                                let undo_id = self
+                                    .save_undo(
+                                        UndoAction::Edit,
+                                        ("rem", old_reminder.id),
+                                        new_reminder
+                                            .get_id()
+                                            .map(|id| ("rem", id)),
+                                    )
+                                    .await;
+                                (
+                                    TgResponse::SuccessEdit(
+                                        old_reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.time_format,
+                                                self.locale,
+                                            ),
+                                        new_reminder.to_unescaped_string(
+                                            user_timezone,
+                                            self.time_format,
+                                            self.locale,
+                                        ),
+                                    ),
+                                    undo_id,
+                                )
+                            }
+                            Err(err) => {
+                                log::error!("{}", err);
+                                (TgResponse::FailedEdit, None)
+                            }
+                        }
+                    }
+                    None => (TgResponse::FailedEdit, None),
+                },
+                _ => (TgResponse::NoChosenTimezone, None),
+            };
+        self.reply_with_undo(response, undo_id).await
+    }
+
+    /// Replace the chosen part of the cron reminder being edited
+    async fn edit_cron_reminder_part(
+        &self,
+        text: &str,
+        entities: &[MessageEntity],
+        old_cron_reminder: cron_reminder::Model,
+        edit_mode: EditMode,
+    ) -> Result<(), RequestError> {
+        let (response, undo_id) =
+            match tz::get_timezone(self.db, self.chat_id, self.user_id).await {
+                Ok(Some(user_timezone)) => {
+                    match parsers::parse_cron_reminder_edit(
+                        old_cron_reminder.clone(),
+                        edit_mode,
+                        text,
+                        entities,
+                        user_timezone,
+                    ) {
+                        Some(new_cron_reminder) => match self
+                            .db
+                            .replace_cron_reminder(
+                                old_cron_reminder.id,
+                                new_cron_reminder,
+                            )
+                            .await
+                        {
+                            Ok(new_cron_reminder) => {
+                                let undo_id = self
+                                    .save_undo(
+                                        UndoAction::Edit,
+                                        ("cron_rem", old_cron_reminder.id),
+                                        new_cron_reminder
+                                            .get_id()
+                                            .map(|id| ("cron_rem", id)),
+                                    )
+                                    .await;
+                                (
+                                    TgResponse::SuccessEdit(
+                                        old_cron_reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.time_format,
+                                                self.locale,
+                                            ),
+                                        new_cron_reminder.to_unescaped_string(
+                                            user_timezone,
+                                            self.time_format,
+                                            self.locale,
+                                        ),
+                                    ),
+                                    undo_id,
+                                )
+                            }
+                            Err(err) => {
+                                log::error!("{}", err);
+                                (TgResponse::FailedEdit, None)
+                            }
+                        },
+                        None => (TgResponse::FailedEdit, None),
+                    }
+                }
+                _ => (TgResponse::NoChosenTimezone, None),
+            };
+        self.reply_with_undo(response, undo_id).await
+    }
+
     pub async fn get_edit_reminder(
         &self,
     ) -> Result<Option<reminder::Model>, db::Error> {
@@ -605,26 +2542,160 @@ impl TgMessageController<'_> {
 
     pub async fn get_edit_cron_reminder(
         &self,
-    ) -> Result<Option<cron_reminder::Model>, db::Error> {
-        self.db.get_edit_cron_reminder(self.chat_id.0).await
+    ) -> Result<Option<cron_reminder::Model>, db::Error> {
+        self.db.get_edit_cron_reminder(self.chat_id.0).await
+    }
+
+    /// Set the group's timezone used for the reminders set in it
+    async fn set_chat_timezone(
+        &self,
+        tz_name: &str,
+    ) -> Result<(), RequestError> {
+        if !self.is_chat_admin().await? {
+            return self.reply(TgResponse::NotChatAdmin).await;
+        }
+        let response = match self
+            .db
+            .set_chat_setting::<settings::Timezone>(
+                self.chat_id.0,
+                &tz_name.to_owned(),
+            )
+            .await
+        {
+            Ok(()) => TgResponse::ChatTimezone(tz_name.to_owned()),
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::FailedSetTimezone(tz_name.to_owned())
+            }
+        };
+        self.reply(response).await
+    }
+
+    /// Set the user's timezone and, if it changes, ask whether
+    /// the reminders keep their local times or their moments,
+    /// in a group set the group's timezone
+    pub async fn set_timezone(
+        &self,
+        tz_name: &str,
+    ) -> Result<(), RequestError> {
+        if !self.chat_id.is_user() {
+            return self.set_chat_timezone(tz_name).await;
+        }
+        let old_timezone = tz::get_user_timezone(self.db, self.user_id)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("{}", err);
+                None
+            });
+        match self
+            .db
+            .insert_or_update_user_timezone(self.user_id.0 as i64, tz_name)
+            .await
+        {
+            Ok(()) => {
+                self.reply(TgResponse::ChosenTimezone(tz_name.to_owned()))
+                    .await?;
+                match old_timezone {
+                    Some(old_timezone) if old_timezone.name() != tz_name => {
+                        tg::send_markup(
+                            &TgResponse::ReanchorQuestion(
+                                old_timezone.name().to_owned(),
+                                tz_name.to_owned(),
+                            )
+                            .localize(self.locale),
+                            self.get_markup_for_reanchor(old_timezone),
+                            self.bot,
+                            self.chat_id,
+                        )
+                        .await
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.reply(TgResponse::FailedSetTimezone(tz_name.to_owned()))
+                    .await
+            }
+        }
+    }
+
+    fn get_markup_for_reanchor(
+        &self,
+        old_timezone: Tz,
+    ) -> InlineKeyboardMarkup {
+        let button = |key, reanchor: tz::Reanchor| {
+            InlineKeyboardButton::new(
+                i18n::tr(self.locale, key),
+                InlineKeyboardButtonKind::CallbackData(format!(
+                    "reanchor::{}::{}",
+                    reanchor.code(),
+                    old_timezone.name()
+                )),
+            )
+        };
+        InlineKeyboardMarkup::default().append_row(vec![
+            button("button-keep-wall-clock", tz::Reanchor::WallClock),
+            button("button-keep-instants", tz::Reanchor::Instants),
+        ])
     }
 
-    pub async fn set_timezone(
+    /// Move the user's reminders from the old timezone to the current one,
+    /// `false` if they failed to move
+    pub async fn reanchor_reminders(
         &self,
-        tz_name: &str,
-    ) -> Result<(), RequestError> {
+        reanchor: tz::Reanchor,
+        old_timezone: Tz,
+    ) -> Result<bool, RequestError> {
+        let timezone = match tz::get_user_timezone(self.db, self.user_id).await
+        {
+            Ok(Some(timezone)) => timezone,
+            _ => {
+                self.reply(TgResponse::NoChosenTimezone).await?;
+                return Ok(false);
+            }
+        };
+        let now = parsers::now_time();
         let response = match self
             .db
-            .insert_or_update_user_timezone(self.user_id.0 as i64, tz_name)
+            .update_user_reminders(
+                self.user_id.0 as i64,
+                |rem| {
+                    tz::reanchor_reminder(
+                        rem,
+                        old_timezone,
+                        timezone,
+                        reanchor,
+                        now,
+                    )
+                },
+                |cron_rem| {
+                    tz::reanchor_cron_reminder(
+                        cron_rem,
+                        old_timezone,
+                        timezone,
+                        reanchor,
+                        now,
+                    )
+                },
+            )
             .await
         {
-            Ok(()) => TgResponse::ChosenTimezone(tz_name.to_owned()),
+            Ok(_) => TgResponse::Reanchored(
+                reanchor,
+                match reanchor {
+                    tz::Reanchor::WallClock => timezone.name().to_owned(),
+                    tz::Reanchor::Instants => old_timezone.name().to_owned(),
+                },
+            ),
             Err(err) => {
                 log::error!("{}", err);
-                TgResponse::FailedSetTimezone(tz_name.to_owned())
+                TgResponse::FailedReanchor
             }
         };
-        self.reply(response).await
+        let reanchored = matches!(response, TgResponse::Reanchored(..));
+        self.reply(response).await?;
+        Ok(reanchored)
     }
 }
 
@@ -637,6 +2708,15 @@ impl TgCallbackController<'_> {
         self.acknowledge_callback().await
     }
 
+    async fn answer_callback_query_with_undo(
+        &self,
+        response: TgResponse,
+        undo_id: Option<i64>,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl.reply_with_undo(response, undo_id).await?;
+        self.acknowledge_callback().await
+    }
+
     async fn acknowledge_callback(&self) -> Result<(), RequestError> {
         self.msg_ctl
             .bot
@@ -646,6 +2726,168 @@ impl TgCallbackController<'_> {
             .map(|_| ())
     }
 
+    /// Answer the callback with an alert instead of a message in the chat
+    async fn alert_callback(
+        &self,
+        response: TgResponse,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl
+            .bot
+            .answer_callback_query(self.cb_id)
+            .text(response.to_unescaped_string(self.msg_ctl.locale))
+            .show_alert(true)
+            .send()
+            .await
+            .map(|_| ())
+    }
+
+    /// Delivery of the reminder in the message if it's in a private chat
+    /// or the user is its author or one of its recipients in a group,
+    /// other users are alerted
+    async fn get_own_delivery(
+        &self,
+        user: &User,
+    ) -> Result<Option<reminder_delivery::Model>, RequestError> {
+        let delivery = match self
+            .msg_ctl
+            .db
+            .get_reminder_delivery_by_message(
+                self.msg_ctl.chat_id.0,
+                self.msg_ctl.msg_id.0,
+            )
+            .await
+        {
+            Ok(Some(delivery)) => delivery,
+            Ok(None) => {
+                self.alert_callback(TgResponse::ReminderNotFound).await?;
+                return Ok(None);
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.alert_callback(TgResponse::QueryingError).await?;
+                return Ok(None);
+            }
+        };
+        // Anyone the reminder is for can act on it in a group
+        if self.msg_ctl.chat_id.is_user()
+            || delivery.user_id == Some(user.id.0 as i64)
+            || Recipient::from_column(delivery.recipients.as_deref())
+                .iter()
+                .any(|recipient| recipient.is(user))
+        {
+            Ok(Some(delivery))
+        } else {
+            self.alert_callback(TgResponse::NotRecipient).await?;
+            Ok(None)
+        }
+    }
+
+    /// Remove the snooze and done buttons of the delivered reminder
+    async fn remove_delivery_markup(&self) -> Result<(), RequestError> {
+        tg::edit_markup(
+            InlineKeyboardMarkup::default(),
+            self.msg_ctl.bot,
+            self.msg_ctl.msg_id,
+            self.msg_ctl.chat_id,
+        )
+        .await
+    }
+
+    /// Mark the reminder delivered in the message as done
+    pub async fn mark_delivery_done(
+        &self,
+        user: &User,
+    ) -> Result<(), RequestError> {
+        if self.get_own_delivery(user).await?.is_none() {
+            return Ok(());
+        }
+        self.remove_delivery_markup().await?;
+        self.answer_callback_query(TgResponse::ReminderDone(user.full_name()))
+            .await
+    }
+
+    /// Remind about the reminder delivered in the message again
+    /// in the given number of minutes replying to the message
+    pub async fn snooze_delivery(
+        &self,
+        user: &User,
+        minutes: i64,
+    ) -> Result<(), RequestError> {
+        let delivery = match self.get_own_delivery(user).await? {
+            Some(delivery) => delivery,
+            None => return Ok(()),
+        };
+        let reminder = reminder::ActiveModel {
+            id: NotSet,
+            chat_id: Set(delivery.chat_id),
+            time: Set(parsers::now_time() + Duration::minutes(minutes)),
+            desc: Set(delivery.desc),
+            edit: Set(false),
+            user_id: Set(delivery.user_id.or(Some(user.id.0 as i64))),
+            paused: Set(false),
+            pattern: Set(None),
+            urgent: Set(false),
+            edit_mode: Set(None),
+            reply_to_message_id: Set(Some(delivery.message_id)),
+            reply_to_chat_id: Set(None),
+            media_type: Set(None),
+            file_id: Set(None),
+            entities: Set(None),
+            recipients: Set(delivery.recipients),
+            deleted_time: Set(None),
+        };
+        let response = match self.msg_ctl.db.insert_reminder(reminder).await {
+            Ok(_) => {
+                self.remove_delivery_markup().await?;
+                TgResponse::ReminderSnoozed(user.full_name(), minutes)
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::FailedSnooze
+            }
+        };
+        self.answer_callback_query(response).await
+    }
+
+    async fn enter_new_reminder(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl.enter_new_reminder(rem_type, rem_id).await?;
+        self.acknowledge_callback().await
+    }
+
+    pub async fn copy_reminder_text(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl.copy_reminder_text(rem_type, rem_id).await?;
+        self.acknowledge_callback().await
+    }
+
+    pub async fn enter_reminder_part(
+        &self,
+        edit_mode: EditMode,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl
+            .enter_reminder_part(edit_mode, rem_type, rem_id)
+            .await?;
+        self.acknowledge_callback().await
+    }
+
+    pub async fn next_occurrences(
+        &self,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<(), RequestError> {
+        self.msg_ctl.next_occurrences(rem_type, rem_id).await?;
+        self.acknowledge_callback().await
+    }
+
     pub async fn set_timezone(
         &self,
         tz_name: &str,
@@ -654,91 +2896,243 @@ impl TgCallbackController<'_> {
         self.acknowledge_callback().await
     }
 
+    /// Apply the choice of what the reminders keep on timezone change
+    /// and drop the buttons to not apply it twice
+    pub async fn reanchor_reminders(
+        &self,
+        reanchor: tz::Reanchor,
+        old_timezone: Tz,
+    ) -> Result<(), RequestError> {
+        // The choice can be retried if the reminders failed to update
+        if self
+            .msg_ctl
+            .reanchor_reminders(reanchor, old_timezone)
+            .await?
+        {
+            tg::edit_markup(
+                InlineKeyboardMarkup::default(),
+                self.msg_ctl.bot,
+                self.msg_ctl.msg_id,
+                self.msg_ctl.chat_id,
+            )
+            .await?;
+        }
+        self.acknowledge_callback().await
+    }
+
+    /// Change the setting chosen in the settings menu
+    pub async fn change_setting(
+        &self,
+        setting: &str,
+    ) -> Result<(), RequestError> {
+        let msg_ctl = &self.msg_ctl;
+        match setting {
+            "timezone" => msg_ctl.choose_timezone().await?,
+            "quiet" => msg_ctl.reply(TgResponse::QuietHoursUsage).await?,
+            "language" => msg_ctl.reply(TgResponse::LanguageUsage).await?,
+            "notifications" => {
+                self.cycle_setting::<settings::SilentNotifications>(|silent| {
+                    !silent.unwrap_or(false)
+                })
+                .await?
+            }
+            "date_format" => {
+                self.cycle_setting::<settings::DateFormat>(|order| match order
+                    .unwrap_or_default()
+                {
+                    DateOrder::DayMonth => DateOrder::MonthDay,
+                    DateOrder::MonthDay => DateOrder::DayMonth,
+                })
+                .await?
+            }
+            "clock" => {
+                self.cycle_setting::<settings::TwelveHourClock>(|twelve_hour| {
+                    !twelve_hour.unwrap_or(false)
+                })
+                .await?
+            }
+            "snooze" => {
+                self.cycle_setting::<settings::DefaultSnooze>(|minutes| {
+                    let minutes = minutes.unwrap_or(tg::DEFAULT_SNOOZE_MINUTES);
+                    tg::SNOOZE_MINUTES
+                        .into_iter()
+                        .find(|&x| x > minutes)
+                        .unwrap_or(tg::SNOOZE_MINUTES[0])
+                })
+                .await?
+            }
+            "overlap" => {
+                self.cycle_setting::<settings::ClockChangeOverlap>(|overlap| {
+                    match overlap.unwrap_or_default() {
+                        OverlapPolicy::Once => OverlapPolicy::Twice,
+                        OverlapPolicy::Twice => OverlapPolicy::Once,
+                    }
+                })
+                .await?
+            }
+            _ => {}
+        }
+        self.acknowledge_callback().await
+    }
+
+    /// Change the user's setting to the value following the current one
+    /// and show it in the settings menu
+    async fn cycle_setting<S: settings::Setting>(
+        &self,
+        next: impl FnOnce(Option<S::Value>) -> S::Value,
+    ) -> Result<(), RequestError> {
+        let msg_ctl = &self.msg_ctl;
+        let user_id = msg_ctl.user_id.0 as i64;
+        let changed = match msg_ctl.db.get_user_setting::<S>(user_id).await {
+            Ok(value) => {
+                msg_ctl
+                    .db
+                    .set_user_setting::<S>(user_id, &next(value))
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        match changed {
+            Ok(()) => {
+                if let Ok(markup) = msg_ctl.get_markup_for_settings().await {
+                    tg::edit_markup(
+                        markup,
+                        msg_ctl.bot,
+                        msg_ctl.msg_id,
+                        msg_ctl.chat_id,
+                    )
+                    .await?;
+                }
+                Ok(())
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                msg_ctl.reply(TgResponse::FailedSetSetting).await
+            }
+        }
+    }
+
     pub async fn delete_reminder(
         &self,
         rem_id: i64,
+        reset_keyboard: bool,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
-        let response =
-            match tz::get_user_timezone(self.msg_ctl.db, self.msg_ctl.user_id)
-                .await
-            {
+        let (response, undo_id) =
+            match self.msg_ctl.get_reminders_timezone().await {
                 Ok(Some(user_timezone)) => {
                     match self.msg_ctl.db.get_reminder(rem_id).await {
                         Ok(Some(reminder)) => {
-                            match self.msg_ctl.db.delete_reminder(rem_id).await
+                            match self
+                                .msg_ctl
+                                .db
+                                .mark_reminder_as_deleted(rem_id)
+                                .await
                             {
-                                Ok(()) => TgResponse::SuccessDelete(
-                                    reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(()) => (
+                                    TgResponse::SuccessDelete(
+                                        reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    self.msg_ctl
+                                        .save_undo(
+                                            UndoAction::Delete,
+                                            ("rem", rem_id),
+                                            None,
+                                        )
+                                        .await,
                                 ),
                                 Err(err) => {
                                     log::error!("{}", err);
-                                    TgResponse::FailedDelete
+                                    (TgResponse::FailedDelete, None)
                                 }
                             }
                         }
                         Err(err) => {
                             log::error!("{}", err);
-                            TgResponse::FailedDelete
+                            (TgResponse::FailedDelete, None)
                         }
                         _ => {
                             log::error!("missing reminder with id: {}", rem_id);
-                            TgResponse::FailedDelete
+                            (TgResponse::FailedDelete, None)
                         }
                     }
                 }
-                _ => TgResponse::NoChosenTimezone,
+                _ => (TgResponse::NoChosenTimezone, None),
             };
-        self.msg_ctl.delete_reminder_set_page(0).await?;
-        self.answer_callback_query(response).await
+        if reset_keyboard {
+            self.msg_ctl.delete_reminder_set_page(0, tag_key).await?;
+        }
+        self.answer_callback_query_with_undo(response, undo_id)
+            .await
     }
 
     pub async fn delete_cron_reminder(
         &self,
         cron_rem_id: i64,
+        reset_keyboard: bool,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
-        let response =
-            match tz::get_user_timezone(self.msg_ctl.db, self.msg_ctl.user_id)
-                .await
-            {
+        let (response, undo_id) =
+            match self.msg_ctl.get_reminders_timezone().await {
                 Ok(Some(user_timezone)) => {
                     match self.msg_ctl.db.get_cron_reminder(cron_rem_id).await {
                         Ok(Some(cron_reminder)) => {
                             match self
                                 .msg_ctl
                                 .db
-                                .delete_cron_reminder(cron_rem_id)
+                                .mark_cron_reminder_as_deleted(cron_rem_id)
                                 .await
                             {
-                                Ok(()) => TgResponse::SuccessDelete(
-                                    cron_reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(()) => (
+                                    TgResponse::SuccessDelete(
+                                        cron_reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    self.msg_ctl
+                                        .save_undo(
+                                            UndoAction::Delete,
+                                            ("cron_rem", cron_rem_id),
+                                            None,
+                                        )
+                                        .await,
                                 ),
                                 Err(err) => {
                                     log::error!("{}", err);
-                                    TgResponse::FailedDelete
+                                    (TgResponse::FailedDelete, None)
                                 }
                             }
                         }
                         Err(err) => {
                             log::error!("{}", err);
-                            TgResponse::FailedDelete
+                            (TgResponse::FailedDelete, None)
                         }
                         _ => {
                             log::error!(
                                 "missing cron reminder with id: {}",
                                 cron_rem_id
                             );
-                            TgResponse::FailedDelete
+                            (TgResponse::FailedDelete, None)
                         }
                     }
                 }
-                _ => TgResponse::NoChosenTimezone,
+                _ => (TgResponse::NoChosenTimezone, None),
             };
-        self.msg_ctl.delete_reminder_set_page(0).await?;
-        self.answer_callback_query(response).await
+        if reset_keyboard {
+            self.msg_ctl.delete_reminder_set_page(0, tag_key).await?;
+        }
+        self.answer_callback_query_with_undo(response, undo_id)
+            .await
     }
 
     pub async fn edit_reminder(&self, rem_id: i64) -> Result<(), RequestError> {
@@ -747,9 +3141,9 @@ impl TgCallbackController<'_> {
             .db
             .reset_reminders_edit(self.msg_ctl.chat_id.0)
             .await
-            .and(self.msg_ctl.db.mark_reminder_as_edit(rem_id).await)
+            .and(self.msg_ctl.db.mark_reminder_as_edit(rem_id, None).await)
         {
-            Ok(()) => TgResponse::EnterNewReminder,
+            Ok(()) => return self.enter_new_reminder("rem", rem_id).await,
             Err(err) => {
                 log::error!("{}", err);
                 TgResponse::FailedEdit
@@ -770,10 +3164,12 @@ impl TgCallbackController<'_> {
             .and(
                 self.msg_ctl
                     .db
-                    .mark_cron_reminder_as_edit(cron_rem_id)
+                    .mark_cron_reminder_as_edit(cron_rem_id, None)
                     .await,
             ) {
-            Ok(()) => TgResponse::EnterNewReminder,
+            Ok(()) => {
+                return self.enter_new_reminder("cron_rem", cron_rem_id).await
+            }
             Err(err) => {
                 log::error!("{}", err);
                 TgResponse::FailedEdit
@@ -785,78 +3181,125 @@ impl TgCallbackController<'_> {
     pub async fn pause_reminder(
         &self,
         rem_id: i64,
+        reset_keyboard: bool,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
-        let response =
-            match tz::get_user_timezone(self.msg_ctl.db, self.msg_ctl.user_id)
-                .await
-            {
+        let (response, undo_id) =
+            match self.msg_ctl.get_reminders_timezone().await {
                 Ok(Some(user_timezone)) => {
                     match self.msg_ctl.db.get_reminder(rem_id).await {
                         Ok(Some(reminder)) => {
+                            let undo_id = self
+                                .msg_ctl
+                                .save_undo(
+                                    UndoAction::Pause(reminder.paused),
+                                    ("rem", rem_id),
+                                    None,
+                                )
+                                .await;
                             match self
                                 .msg_ctl
                                 .db
                                 .toggle_reminder_paused(rem_id)
                                 .await
                             {
-                                Ok(true) => TgResponse::SuccessPause(
-                                    reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(true) => (
+                                    TgResponse::SuccessPause(
+                                        reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    undo_id,
                                 ),
-                                Ok(false) => TgResponse::SuccessResume(
-                                    reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(false) => (
+                                    TgResponse::SuccessResume(
+                                        reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    undo_id,
                                 ),
                                 Err(err) => {
                                     log::error!("{}", err);
-                                    TgResponse::FailedPause
+                                    (TgResponse::FailedPause, None)
                                 }
                             }
                         }
                         _ => {
                             log::error!("missing reminder with id: {}", rem_id);
-                            TgResponse::FailedPause
+                            (TgResponse::FailedPause, None)
                         }
                     }
                 }
-                _ => TgResponse::NoChosenTimezone,
+                _ => (TgResponse::NoChosenTimezone, None),
             };
-        self.msg_ctl.pause_reminder_set_page(0).await?;
-        self.answer_callback_query(response).await
+        if reset_keyboard {
+            self.msg_ctl.pause_reminder_set_page(0, tag_key).await?;
+        }
+        self.answer_callback_query_with_undo(response, undo_id)
+            .await
     }
 
     pub async fn pause_cron_reminder(
         &self,
         cron_rem_id: i64,
+        reset_keyboard: bool,
+        tag_key: Option<&str>,
     ) -> Result<(), RequestError> {
-        let response =
-            match tz::get_user_timezone(self.msg_ctl.db, self.msg_ctl.user_id)
-                .await
-            {
+        let (response, undo_id) =
+            match self.msg_ctl.get_reminders_timezone().await {
                 Ok(Some(user_timezone)) => {
                     match self.msg_ctl.db.get_cron_reminder(cron_rem_id).await {
                         Ok(Some(cron_reminder)) => {
+                            let undo_id = self
+                                .msg_ctl
+                                .save_undo(
+                                    UndoAction::Pause(cron_reminder.paused),
+                                    ("cron_rem", cron_rem_id),
+                                    None,
+                                )
+                                .await;
                             match self
                                 .msg_ctl
                                 .db
                                 .toggle_cron_reminder_paused(cron_rem_id)
                                 .await
                             {
-                                Ok(true) => TgResponse::SuccessPause(
-                                    cron_reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(true) => (
+                                    TgResponse::SuccessPause(
+                                        cron_reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    undo_id,
                                 ),
-                                Ok(false) => TgResponse::SuccessResume(
-                                    cron_reminder
-                                        .into_active_model()
-                                        .to_unescaped_string(user_timezone),
+                                Ok(false) => (
+                                    TgResponse::SuccessResume(
+                                        cron_reminder
+                                            .into_active_model()
+                                            .to_unescaped_string(
+                                                user_timezone,
+                                                self.msg_ctl.time_format,
+                                                self.msg_ctl.locale,
+                                            ),
+                                    ),
+                                    undo_id,
                                 ),
                                 Err(err) => {
                                     log::error!("{}", err);
-                                    TgResponse::FailedPause
+                                    (TgResponse::FailedPause, None)
                                 }
                             }
                         }
@@ -865,13 +3308,231 @@ impl TgCallbackController<'_> {
                                 "missing cron reminder with id: {}",
                                 cron_rem_id
                             );
-                            TgResponse::FailedPause
+                            (TgResponse::FailedPause, None)
                         }
                     }
                 }
-                _ => TgResponse::NoChosenTimezone,
+                _ => (TgResponse::NoChosenTimezone, None),
             };
-        self.msg_ctl.pause_reminder_set_page(0).await?;
+        if reset_keyboard {
+            self.msg_ctl.pause_reminder_set_page(0, tag_key).await?;
+        }
+        self.answer_callback_query_with_undo(response, undo_id)
+            .await
+    }
+
+    /// Delete or pause one of the selected reminders, saving its undo entry.
+    /// Returns false if the reminder is already deleted.
+    async fn apply_bulk_action_to_reminder(
+        &self,
+        action: BulkAction,
+        rem_type: &str,
+        rem_id: i64,
+    ) -> Result<bool, db::Error> {
+        let db = self.msg_ctl.db;
+        let paused = match rem_type {
+            "rem" => db.get_reminder(rem_id).await?.map(|x| x.paused),
+            "cron_rem" => db.get_cron_reminder(rem_id).await?.map(|x| x.paused),
+            _ => {
+                log::error!("unknown reminder type: {}", rem_type);
+                None
+            }
+        };
+        let paused = match paused {
+            Some(paused) => paused,
+            None => return Ok(false),
+        };
+        match (action, rem_type) {
+            (BulkAction::Delete, "rem") => {
+                db.mark_reminder_as_deleted(rem_id).await
+            }
+            (BulkAction::Delete, _) => {
+                db.mark_cron_reminder_as_deleted(rem_id).await
+            }
+            (BulkAction::Pause, "rem") => {
+                db.set_reminder_paused(rem_id, true).await
+            }
+            (BulkAction::Pause, _) => {
+                db.set_cron_reminder_paused(rem_id, true).await
+            }
+        }?;
+        let undo_action = match action {
+            BulkAction::Delete => UndoAction::Delete,
+            BulkAction::Pause => UndoAction::Pause(paused),
+        };
+        self.msg_ctl
+            .save_undo(undo_action, (rem_type, rem_id), None)
+            .await;
+        Ok(true)
+    }
+
+    /// Leave the selection mode of the keyboard
+    async fn reset_selection(
+        &self,
+        action: BulkAction,
+        tag_key: Option<&str>,
+    ) -> Result<(), RequestError> {
+        if let Err(err) = self
+            .msg_ctl
+            .db
+            .clear_reminder_selection(
+                self.msg_ctl.chat_id.0,
+                self.msg_ctl.msg_id.0,
+            )
+            .await
+        {
+            log::error!("{}", err);
+        }
+        match action {
+            BulkAction::Delete => {
+                self.msg_ctl.delete_reminder_set_page(0, tag_key).await
+            }
+            BulkAction::Pause => {
+                self.msg_ctl.pause_reminder_set_page(0, tag_key).await
+            }
+        }
+    }
+
+    /// Handle the buttons of a keyboard in the selection mode
+    /// filtered by the tag with the given key (if any)
+    pub async fn handle_selection_query(
+        &self,
+        action: BulkAction,
+        query: SelectionQuery,
+        tag_key: Option<&str>,
+    ) -> Result<(), RequestError> {
+        let (chat_id, msg_id) = (self.msg_ctl.chat_id.0, self.msg_ctl.msg_id.0);
+        let db = self.msg_ctl.db;
+        let tag = self.msg_ctl.find_tag(tag_key).await;
+        match query {
+            SelectionQuery::SetPage(page_num) => {
+                self.msg_ctl
+                    .selection_set_page(action, page_num, tag.as_deref())
+                    .await?;
+                self.acknowledge_callback().await
+            }
+            SelectionQuery::Toggle(page_num, rem_type, rem_id) => {
+                let result =
+                    match db.get_reminder_selection(chat_id, msg_id).await {
+                        Ok(selection) => {
+                            let selected = !selection
+                                .contains(&(rem_type.clone(), rem_id));
+                            db.set_reminders_selected(
+                                chat_id,
+                                msg_id,
+                                &[(&rem_type, rem_id)],
+                                selected,
+                            )
+                            .await
+                        }
+                        Err(err) => Err(err),
+                    };
+                if let Err(err) = result {
+                    log::error!("{}", err);
+                }
+                self.msg_ctl
+                    .selection_set_page(action, page_num, tag.as_deref())
+                    .await?;
+                self.acknowledge_callback().await
+            }
+            SelectionQuery::TogglePage(page_num) => {
+                let result = match (
+                    self.msg_ctl
+                        .get_reminders_page(page_num, tag.as_deref())
+                        .await,
+                    db.get_reminder_selection(chat_id, msg_id).await,
+                ) {
+                    (Ok(page), Ok(selection)) => {
+                        let all_selected =
+                            page.iter().all(|&(rem_type, id)| {
+                                selection.contains(&(rem_type.to_owned(), id))
+                            });
+                        db.set_reminders_selected(
+                            chat_id,
+                            msg_id,
+                            &page,
+                            !all_selected,
+                        )
+                        .await
+                    }
+                    (Err(err), _) | (_, Err(err)) => Err(err),
+                };
+                if let Err(err) = result {
+                    log::error!("{}", err);
+                }
+                self.msg_ctl
+                    .selection_set_page(action, page_num, tag.as_deref())
+                    .await?;
+                self.acknowledge_callback().await
+            }
+            SelectionQuery::Apply => {
+                let response =
+                    match db.get_reminder_selection(chat_id, msg_id).await {
+                        Ok(selection) if selection.is_empty() => {
+                            TgResponse::NothingSelected
+                        }
+                        Ok(selection) => {
+                            let mut count = 0;
+                            for (rem_type, rem_id) in selection {
+                                match self
+                                    .apply_bulk_action_to_reminder(
+                                        action, &rem_type, rem_id,
+                                    )
+                                    .await
+                                {
+                                    Ok(true) => count += 1,
+                                    Ok(false) => {}
+                                    Err(err) => log::error!("{}", err),
+                                }
+                            }
+                            // Selected reminders could be deleted meanwhile
+                            match action {
+                                _ if count == 0 => TgResponse::NothingSelected,
+                                BulkAction::Delete => {
+                                    TgResponse::SuccessBulkDelete(count)
+                                }
+                                BulkAction::Pause => {
+                                    TgResponse::SuccessBulkPause(count)
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            log::error!("{}", err);
+                            match action {
+                                BulkAction::Delete => TgResponse::FailedDelete,
+                                BulkAction::Pause => TgResponse::FailedPause,
+                            }
+                        }
+                    };
+                self.reset_selection(action, tag_key).await?;
+                self.answer_callback_query(response).await
+            }
+            SelectionQuery::Cancel => {
+                self.reset_selection(action, tag_key).await?;
+                self.acknowledge_callback().await
+            }
+        }
+    }
+
+    /// Undo an action by the undo button under its success message
+    pub async fn undo(&self, undo_id: i64) -> Result<(), RequestError> {
+        let response = match self.msg_ctl.db.get_reminder_undo(undo_id).await {
+            Ok(undo) => {
+                self.msg_ctl
+                    .apply_undo(undo.filter(|undo| {
+                        undo.chat_id == self.msg_ctl.chat_id.0
+                            && undo.time >= undo_period_start()
+                    }))
+                    .await
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                TgResponse::FailedUndo
+            }
+        };
+        self.msg_ctl
+            .alter_reminder_set_page(InlineKeyboardMarkup::default())
+            .await?;
         self.answer_callback_query(response).await
     }
 }
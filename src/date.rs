@@ -1,5 +1,8 @@
 use crate::serializers::{DateInterval, Interval};
-use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use chrono::{
+    Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+};
+use chrono_tz::Tz;
 use chronoutil::{is_leap_year, shift_months, shift_years};
 use nonempty::NonEmpty;
 
@@ -26,23 +29,116 @@ pub fn add_interval(time: NaiveDateTime, interval: &Interval) -> NaiveDateTime {
         + chrono::Duration::seconds(interval.seconds as i64)
 }
 
-pub fn add_date_interval(
-    date: NaiveDate,
+/// Number of days in the month counted from year 0
+fn days_in_month(month_index: i32) -> u32 {
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) as u32 + 1;
+    normalise_day(year, month, 31)
+}
+
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// Whether any of `count` months from `start` spaced by `step` months
+/// is February of a common year. Februaries come every `period` months
+/// of the sequence and the leap years repeat every 400 years, so only
+/// the Februaries of a single cycle are checked
+fn meets_common_february(start: i32, step: i32, count: i32) -> bool {
+    let is_february = |i: &i32| (start + i * step).rem_euclid(12) == 1;
+    let Some(first) = (0..count.min(12)).find(is_february) else {
+        return false;
+    };
+    let period = (1..=12).find(|p| p * step % 12 == 0).unwrap_or(12);
+    let first_year = (start + first * step).div_euclid(12);
+    let year_step = period * step / 12;
+    let februaries = (count - 1 - first) / period + 1;
+    (0..februaries.min(400)).any(|i| !is_leap_year(first_year + i * year_step))
+}
+
+/// Day of `from` shifted by the years and months interval `steps` times.
+/// The day sticks to the shortest month met on the way, including the one
+/// between shifting the years and the months. Month lengths repeat every
+/// 12 months, so only the first ones met are checked besides Februaries
+fn shifted_day(from: NaiveDate, years: i32, months: i32, steps: i32) -> u32 {
+    let mut day = from.day();
+    if day <= 28 {
+        return day;
+    }
+    let step = years * 12 + months;
+    // Months met after shifting the years and after shifting the months
+    for start in [month_index(from) + years * 12, month_index(from) + step] {
+        day = (0..steps.min(12))
+            .map(|i| days_in_month(start + i * step))
+            .fold(day, u32::min);
+        if day == 29 && meets_common_february(start, step, steps) {
+            day = 28;
+        }
+    }
+    day
+}
+
+/// Average length of a month in the 400-year Gregorian cycle, in days
+/// as a fraction
+const MONTH_DAYS: (i64, i64) = (146_097, 4_800);
+
+/// The first date not earlier than `date` of `from` shifted by the
+/// interval a whole number of times, the years and months of the interval
+/// being added repeatedly and the weeks and days once for each time
+pub fn nearest_interval_date(
+    from: NaiveDate,
     interval: &DateInterval,
-) -> NaiveDate {
-    add_interval(
-        date.and_hms_opt(0, 0, 0).unwrap(),
-        &Interval {
-            years: interval.years,
-            months: interval.months,
-            weeks: interval.weeks,
-            days: interval.days,
-            hours: 0,
-            minutes: 0,
-            seconds: 0,
-        },
-    )
-    .date()
+    date: NaiveDate,
+) -> Option<NaiveDate> {
+    if from >= date {
+        return Some(from);
+    }
+    let months = interval.years * 12 + interval.months as i32;
+    let days = interval.weeks as i64 * 7 + interval.days as i64;
+    let nth_date = |steps: i32| {
+        let index = month_index(from) + steps * months;
+        NaiveDate::from_ymd_opt(
+            index.div_euclid(12),
+            index.rem_euclid(12) as u32 + 1,
+            shifted_day(from, interval.years, interval.months as i32, steps),
+        )?
+        .checked_add_signed(Duration::days(steps as i64 * days))
+    };
+    match (months, days) {
+        (0, 0) => None,
+        (0, days) if days > 0 => {
+            let steps = ((date - from).num_days() + days - 1) / days;
+            Some(from + Duration::days(steps * days))
+        }
+        (months, 0) if months > 0 => {
+            // The last date not past the month of `date` is either
+            // the nearest one or is followed by it
+            let steps = (month_index(date) - month_index(from)) / months;
+            let nearest_date = nth_date(steps)?;
+            if nearest_date >= date {
+                Some(nearest_date)
+            } else {
+                nth_date(steps + 1)
+            }
+        }
+        (months, days) if months > 0 => {
+            // The dates differ from the ones of the average step by a few
+            // days only, less than a step, so starting a step before
+            // the estimate the nearest date is at most three steps away
+            let (numer, denom) = MONTH_DAYS;
+            let estimate = (date - from).num_days() * denom
+                / (months as i64 * numer + days * denom);
+            let mut steps = (i32::try_from(estimate).ok()? - 1).max(0);
+            loop {
+                let nearest_date = nth_date(steps)?;
+                if nearest_date >= date {
+                    return Some(nearest_date);
+                }
+                steps += 1;
+            }
+        }
+        _ => None,
+    }
 }
 
 pub fn find_nearest_weekday(
@@ -55,12 +151,46 @@ pub fn find_nearest_weekday(
     date
 }
 
+/// UTC time of the first occurrence of the local time of day after `after`
+pub fn next_time_of_day(
+    time: NaiveTime,
+    user_timezone: Tz,
+    after: NaiveDateTime,
+) -> NaiveDateTime {
+    let mut date = user_timezone.from_utc_datetime(&after).date_naive();
+    loop {
+        let local = date.and_time(time);
+        // Shift the time by an hour if it's skipped by DST
+        let utc = user_timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                user_timezone
+                    .from_local_datetime(&(local + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|dt| dt.naive_utc());
+        match utc {
+            Some(utc) if utc > after => return utc,
+            _ => date += chrono::Duration::days(1),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use chrono::{NaiveDateTime, NaiveTime, Timelike};
+    use chrono::Timelike;
+    use proptest::prelude::*;
     use test_case::test_case;
 
+    fn utc(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
     #[derive(Debug, PartialEq)]
     struct Time(i32, u32, u32, u32, u32, u32);
 
@@ -105,4 +235,202 @@ mod test {
             result.second(),
         )
     }
+
+    #[test_case(utc(10, 4, 0) => utc(10, 5, 0) ; "later today" )]
+    #[test_case(utc(10, 5, 0) => utc(11, 5, 0) ; "right now" )]
+    #[test_case(utc(10, 22, 0) => utc(11, 5, 0) ; "tomorrow" )]
+    fn test_next_time_of_day(after: NaiveDateTime) -> NaiveDateTime {
+        // 08:00 in Moscow
+        next_time_of_day(
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            chrono_tz::Europe::Moscow,
+            after,
+        )
+    }
+
+    #[test]
+    fn test_next_time_of_day_dst_gap() {
+        // 02:30 doesn't exist in Berlin on the 31st of March 2024
+        assert_eq!(
+            next_time_of_day(
+                NaiveTime::from_hms_opt(2, 30, 0).unwrap(),
+                chrono_tz::Europe::Berlin,
+                utc(30, 12, 0),
+            ),
+            utc(31, 1, 30)
+        );
+    }
+
+    fn add_date_interval(
+        date: NaiveDate,
+        interval: &DateInterval,
+    ) -> NaiveDate {
+        add_interval(
+            date.and_hms_opt(0, 0, 0).unwrap(),
+            &Interval {
+                years: interval.years,
+                months: interval.months,
+                weeks: interval.weeks,
+                days: interval.days,
+                hours: 0,
+                minutes: 0,
+                seconds: 0,
+            },
+        )
+        .date()
+    }
+
+    /// Reference stepping through every interval from the start
+    fn stepped_interval_date(
+        from: NaiveDate,
+        interval: &DateInterval,
+        date: NaiveDate,
+    ) -> NaiveDate {
+        let months = DateInterval {
+            weeks: 0,
+            days: 0,
+            ..*interval
+        };
+        let days = interval.weeks as i64 * 7 + interval.days as i64;
+        let (mut steps, mut months_date, mut nearest_date) = (0, from, from);
+        while nearest_date < date {
+            months_date = add_date_interval(months_date, &months);
+            steps += 1;
+            nearest_date = months_date + Duration::days(steps * days);
+        }
+        nearest_date
+    }
+
+    fn any_date() -> impl Strategy<Value = NaiveDate> {
+        (1990..2100, 1..=12u32, 1..=31u32).prop_map(|(year, month, day)| {
+            let day = normalise_day(year, month, day);
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        })
+    }
+
+    /// Dates at the end of a month which stick to shorter months
+    fn any_month_end_date() -> impl Strategy<Value = NaiveDate> {
+        (1990..2100, 1..=12u32, 29..=31u32).prop_map(|(year, month, day)| {
+            let day = normalise_day(year, month, day);
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        })
+    }
+
+    fn any_date_interval() -> impl Strategy<Value = DateInterval> {
+        prop_oneof![
+            (1..30u32, 0..4u32).prop_map(|(days, weeks)| DateInterval {
+                years: 0,
+                months: 0,
+                weeks,
+                days,
+            }),
+            (0..3i32, 0..25u32)
+                .prop_filter("empty interval", |&(years, months)| {
+                    years != 0 || months != 0
+                })
+                .prop_map(|(years, months)| DateInterval {
+                    years,
+                    months,
+                    weeks: 0,
+                    days: 0,
+                }),
+            (1..25u32, 0..2u32, 1..30u32).prop_map(|(months, weeks, days)| {
+                DateInterval {
+                    years: 0,
+                    months,
+                    weeks,
+                    days,
+                }
+            }),
+            (0..2i32, 0..13u32, 0..2u32, 0..10u32)
+                .prop_filter("empty interval", |&(y, mo, w, d)| {
+                    y != 0 || mo != 0 || w != 0 || d != 0
+                })
+                .prop_map(|(years, months, weeks, days)| DateInterval {
+                    years,
+                    months,
+                    weeks,
+                    days,
+                }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_nearest_interval_date_matches_stepping(
+            from in prop_oneof![any_date(), any_month_end_date()],
+            interval in any_date_interval(),
+            offset in -100..20000i64,
+        ) {
+            let date = from + Duration::days(offset);
+            prop_assert_eq!(
+                nearest_interval_date(from, &interval, date),
+                Some(stepped_interval_date(from, &interval, date))
+            );
+        }
+    }
+
+    #[test_case(2000, 1, 31, 1 => (2000, 2, 29) ; "clipped to leap february")]
+    #[test_case(2000, 1, 31, 62 => (2000, 4, 29) ; "stays clipped")]
+    #[test_case(2000, 2, 29, 400 => (2001, 4, 28) ; "over a year")]
+    #[test_case(2000, 1, 30, 10000 => (2027, 6, 28) ; "many steps")]
+    fn test_nearest_monthly_date(
+        year: i32,
+        month: u32,
+        day: u32,
+        offset: i64,
+    ) -> (i32, u32, u32) {
+        nearest_date_by_offset(year, month, day, offset, 1, 0)
+    }
+
+    #[test_case(2000, 2, 29, 48, 36000 => (2100, 2, 28) ; "leap februaries until a common one")]
+    #[test_case(2000, 3, 31, 12, 3000 => (2009, 3, 31) ; "every year")]
+    #[test_case(2000, 1, 31, 2, 3000 => (2008, 5, 30) ; "every two months")]
+    fn test_nearest_long_interval_date(
+        year: i32,
+        month: u32,
+        day: u32,
+        months: u32,
+        offset: i64,
+    ) -> (i32, u32, u32) {
+        nearest_date_by_offset(year, month, day, offset, months, 0)
+    }
+
+    #[test_case(2000, 1, 1, 1, 1, 65 => (2000, 4, 4) ; "no clipping")]
+    #[test_case(2000, 1, 31, 1, 2, 32 => (2000, 4, 2) ; "clipped month")]
+    #[test_case(2000, 1, 31, 1, 2, 3000 => (2008, 5, 1) ; "many steps")]
+    fn test_nearest_mixed_interval_date(
+        year: i32,
+        month: u32,
+        day: u32,
+        months: u32,
+        days: u32,
+        offset: i64,
+    ) -> (i32, u32, u32) {
+        nearest_date_by_offset(year, month, day, offset, months, days)
+    }
+
+    fn nearest_date_by_offset(
+        year: i32,
+        month: u32,
+        day: u32,
+        offset: i64,
+        months: u32,
+        days: u32,
+    ) -> (i32, u32, u32) {
+        let from = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let interval = DateInterval {
+            years: 0,
+            months,
+            weeks: 0,
+            days,
+        };
+        let date = nearest_interval_date(
+            from,
+            &interval,
+            from + Duration::days(offset),
+        )
+        .unwrap();
+        (date.year(), date.month(), date.day())
+    }
 }
@@ -0,0 +1,119 @@
+use crate::generic_reminder::GenericReminder;
+use chrono::{Duration, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+/// Limit of a single reminder's occurrences listed in a digest,
+/// e.g. for reminders firing every minute
+const MAX_DAY_OCCURRENCES: usize = 50;
+
+/// UTC time of the start of the next day in the timezone
+pub fn end_of_day(user_timezone: Tz, time: NaiveDateTime) -> NaiveDateTime {
+    let next_date =
+        user_timezone.from_utc_datetime(&time).date_naive() + Duration::days(1);
+    let midnight = next_date.and_time(NaiveTime::MIN);
+    user_timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|dt| dt.naive_utc())
+        .unwrap_or(midnight)
+}
+
+/// Firings of the reminder within `[from, until)`
+pub fn reminder_occurrences(
+    reminder: &dyn GenericReminder,
+    user_timezone: Tz,
+    from: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Vec<NaiveDateTime> {
+    reminder
+        .occurrences(user_timezone)
+        .take_while(|&time| time < until)
+        .filter(|&time| time >= from)
+        .take(MAX_DAY_OCCURRENCES)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::{cron_reminder, reminder};
+    use chrono::NaiveDate;
+    use sea_orm::IntoActiveModel;
+
+    fn utc(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cron_reminder_occurrences() {
+        let cron_reminder = cron_reminder::Model {
+            id: 1,
+            chat_id: 1,
+            user_id: Some(1),
+            cron_expr: "0 */4 * * *".to_owned(),
+            time: utc(10, 8, 0),
+            desc: "".to_owned(),
+            edit: false,
+            paused: false,
+            urgent: false,
+            timezone: None,
+            edit_mode: None,
+            reply_to_message_id: None,
+            reply_to_chat_id: None,
+            media_type: None,
+            file_id: None,
+            entities: None,
+            recipients: None,
+            deleted_time: None,
+        };
+        assert_eq!(
+            reminder_occurrences(
+                &cron_reminder.into_active_model(),
+                chrono_tz::UTC,
+                utc(10, 6, 0),
+                end_of_day(chrono_tz::UTC, utc(10, 6, 0)),
+            ),
+            vec![
+                utc(10, 8, 0),
+                utc(10, 12, 0),
+                utc(10, 16, 0),
+                utc(10, 20, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reminder_occurrences_one_time() {
+        let reminder = reminder::Model {
+            id: 1,
+            chat_id: 1,
+            user_id: Some(1),
+            time: utc(11, 8, 0),
+            desc: "".to_owned(),
+            edit: false,
+            paused: false,
+            pattern: None,
+            urgent: false,
+            edit_mode: None,
+            reply_to_message_id: None,
+            reply_to_chat_id: None,
+            media_type: None,
+            file_id: None,
+            entities: None,
+            recipients: None,
+            deleted_time: None,
+        };
+        let reminder = reminder.into_active_model();
+        let occurrences = |from, until| {
+            reminder_occurrences(&reminder, chrono_tz::UTC, from, until)
+        };
+        assert!(occurrences(utc(10, 6, 0), utc(11, 0, 0)).is_empty());
+        assert_eq!(
+            occurrences(utc(11, 6, 0), utc(12, 0, 0)),
+            vec![utc(11, 8, 0)]
+        );
+    }
+}
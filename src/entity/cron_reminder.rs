@@ -15,6 +15,16 @@ pub struct Model {
     pub edit: bool,
     pub user_id: Option<i64>,
     pub paused: bool,
+    pub urgent: bool,
+    pub timezone: Option<String>,
+    pub edit_mode: Option<String>,
+    pub reply_to_message_id: Option<i32>,
+    pub reply_to_chat_id: Option<i64>,
+    pub media_type: Option<String>,
+    pub file_id: Option<String>,
+    pub entities: Option<String>,
+    pub recipients: Option<String>,
+    pub deleted_time: Option<NaiveDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
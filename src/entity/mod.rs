@@ -1,7 +1,13 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.10.2
 
-pub mod prelude;
-
+pub mod chat_digest;
+pub mod chat_settings;
 pub mod cron_reminder;
 pub mod reminder;
-pub mod user_timezone;
+pub mod reminder_delivery;
+pub mod reminder_search;
+pub mod reminder_selection;
+pub mod reminder_tag;
+pub mod reminder_undo;
+pub mod user_quiet_hours;
+pub mod user_settings;
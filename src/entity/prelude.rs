@@ -1,5 +0,0 @@
-//! `SeaORM` Entity. Generated by sea-orm-codegen 0.10.2
-
-pub use super::cron_reminder::Entity as CronReminder;
-pub use super::reminder::Entity as Reminder;
-pub use super::user_timezone::Entity as UserTimezone;
@@ -15,6 +15,15 @@ pub struct Model {
     pub user_id: Option<i64>,
     pub paused: bool,
     pub pattern: Option<String>,
+    pub urgent: bool,
+    pub edit_mode: Option<String>,
+    pub reply_to_message_id: Option<i32>,
+    pub reply_to_chat_id: Option<i64>,
+    pub media_type: Option<String>,
+    pub file_id: Option<String>,
+    pub entities: Option<String>,
+    pub recipients: Option<String>,
+    pub deleted_time: Option<NaiveDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -7,10 +7,10 @@ pub enum Error {
     Parse(String),
     CronParse(cron_parser::ParseError),
     TeloxideRequest(teloxide::RequestError),
-    UnmatchedQuery(teloxide::types::CallbackQuery),
-    NoQueryData(teloxide::types::CallbackQuery),
-    NoQueryMessage(teloxide::types::CallbackQuery),
-    UserNotFound(teloxide::types::Message),
+    UnmatchedQuery(Box<teloxide::types::CallbackQuery>),
+    NoQueryData(Box<teloxide::types::CallbackQuery>),
+    NoQueryMessage(Box<teloxide::types::CallbackQuery>),
+    UserNotFound(Box<teloxide::types::Message>),
 }
 
 impl fmt::Display for Error {
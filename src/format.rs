@@ -1,17 +1,141 @@
-use crate::entity::cron_reminder;
+use crate::db;
+use crate::entity::{cron_reminder, reminder_delivery};
 use crate::generic_reminder::GenericReminder;
+use crate::i18n::{self, Locale};
+use crate::parsers::format_reminder_ref;
+use crate::settings;
+use chrono::{Datelike, Duration, NaiveDateTime, TimeZone, Timelike};
 use chrono_tz::Tz;
 use sea_orm::{ActiveModelTrait, IntoActiveModel};
+use std::fmt;
+use std::str::FromStr;
+use teloxide::types::{MessageEntity, MessageEntityKind, UserId};
+use teloxide::utils::markdown::{
+    bold, code_inline, escape, escape_code, escape_link_url,
+};
+
+/// Order of the day and month in shown dates
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DateOrder {
+    /// 31.12
+    #[default]
+    DayMonth,
+    /// 12/31
+    MonthDay,
+}
+
+impl FromStr for DateOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "d.m" => Ok(Self::DayMonth),
+            "m/d" => Ok(Self::MonthDay),
+            _ => Err(format!("unknown date order: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for DateOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DayMonth => write!(f, "d.m"),
+            Self::MonthDay => write!(f, "m/d"),
+        }
+    }
+}
+
+/// How dates and times are shown to the user
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeFormat {
+    pub date_order: DateOrder,
+    pub twelve_hour_clock: bool,
+}
+
+impl TimeFormat {
+    /// Format chosen by the user in the settings
+    pub async fn of_user(
+        db: &db::Database,
+        user_id: UserId,
+    ) -> Result<Self, db::Error> {
+        let user_id = user_id.0 as i64;
+        Ok(Self {
+            date_order: db
+                .get_user_setting::<settings::DateFormat>(user_id)
+                .await?
+                .unwrap_or_default(),
+            twelve_hour_clock: db
+                .get_user_setting::<settings::TwelveHourClock>(user_id)
+                .await?
+                .unwrap_or(false),
+        })
+    }
+
+    /// Day and month, e.g. 31.12 or 12/31
+    pub fn day_month(&self, date: &impl Datelike) -> String {
+        match self.date_order {
+            DateOrder::DayMonth => {
+                format!("{:02}.{:02}", date.day(), date.month())
+            }
+            DateOrder::MonthDay => {
+                format!("{:02}/{:02}", date.month(), date.day())
+            }
+        }
+    }
+
+    /// Date with the year, e.g. 31.12.2024 or 12/31/2024
+    pub fn date(&self, date: &impl Datelike) -> String {
+        let separator = match self.date_order {
+            DateOrder::DayMonth => '.',
+            DateOrder::MonthDay => '/',
+        };
+        format!("{}{}{}", self.day_month(date), separator, date.year())
+    }
+
+    /// Time of the day, e.g. 23:05 or 11:05 PM
+    pub fn time(&self, time: &impl Timelike) -> String {
+        if self.twelve_hour_clock {
+            let (pm, hour) = time.hour12();
+            format!(
+                "{}:{:02} {}",
+                hour,
+                time.minute(),
+                if pm { "PM" } else { "AM" }
+            )
+        } else {
+            format!("{:02}:{:02}", time.hour(), time.minute())
+        }
+    }
+
+    /// Date with the year and time
+    pub fn date_time(&self, time: &(impl Datelike + Timelike)) -> String {
+        format!("{} {}", self.date(time), self.time(time))
+    }
+}
 
 pub fn format_reminder<T: ActiveModelTrait + GenericReminder>(
     reminder: &T,
     user_timezone: Tz,
+    time_format: TimeFormat,
+    locale: Locale,
 ) -> String {
+    let recipients = reminder.recipients();
     match reminder.user_id() {
-        Some(user_id) if reminder.chat_id().is_group() => {
-            reminder.to_string_with_mention(user_timezone, user_id.0 as i64)
-        }
-        _ => reminder.to_string(user_timezone),
+        _ if reminder.is_group() && !recipients.is_empty() => reminder
+            .to_string_with_recipients(
+                user_timezone,
+                time_format,
+                &recipients,
+                locale,
+            ),
+        Some(user_id) if reminder.is_group() => reminder
+            .to_string_with_mention(
+                user_timezone,
+                time_format,
+                user_id.0 as i64,
+                locale,
+            ),
+        _ => reminder.to_string(user_timezone, time_format, locale),
     }
 }
 
@@ -19,18 +143,748 @@ pub fn format_cron_reminder(
     reminder: &cron_reminder::Model,
     next_reminder: &Option<cron_reminder::Model>,
     user_timezone: Tz,
+    time_format: TimeFormat,
+    locale: Locale,
 ) -> String {
-    let formatted_reminder =
-        format_reminder(&reminder.clone().into_active_model(), user_timezone);
+    let formatted_reminder = format_reminder(
+        &reminder.clone().into_active_model(),
+        user_timezone,
+        time_format,
+        locale,
+    );
     match next_reminder {
         Some(next_reminder) => format!(
-            "{}\n\nNext time → {}",
+            "{}\n\n{}",
             formatted_reminder,
-            next_reminder
-                .clone()
-                .into_active_model()
-                .serialize_time(user_timezone)
+            escape(&i18n::tr_with(
+                locale,
+                "next-time",
+                [(
+                    "time",
+                    next_reminder
+                        .clone()
+                        .into_active_model()
+                        .serialize_time_unescaped(user_timezone, time_format)
+                        .into()
+                )]
+            ))
         ),
         None => formatted_reminder,
     }
 }
+
+pub fn format_tag(name: &str, count: i64) -> String {
+    format!("{} — {}", bold(&escape(&format!("#{}", name))), count)
+}
+
+pub fn format_reminder_delivery(
+    delivery: &reminder_delivery::Model,
+    user_timezone: Tz,
+    time_format: TimeFormat,
+    locale: Locale,
+) -> String {
+    let fmt_time =
+        |time| time_format.date_time(&user_timezone.from_utc_datetime(time));
+    let formatted_delivery = format!(
+        r"{} <{}\>",
+        escape(&fmt_time(&delivery.sent_time)),
+        bold(&escape(&delivery.desc)),
+    );
+    if delivery.sent_time - delivery.time >= Duration::minutes(1) {
+        format!(
+            "{} {}",
+            formatted_delivery,
+            escape(&i18n::tr_with(
+                locale,
+                "scheduled-for",
+                [("time", fmt_time(&delivery.time).into())]
+            ))
+        )
+    } else {
+        formatted_delivery
+    }
+}
+
+/// Numbered list of a reminder's upcoming firings
+pub fn format_occurrences(
+    times: &[NaiveDateTime],
+    user_timezone: Tz,
+    time_format: TimeFormat,
+    locale: Locale,
+) -> String {
+    times
+        .iter()
+        .enumerate()
+        .map(|(i, time)| {
+            let time = user_timezone.from_utc_datetime(time);
+            escape(&format!(
+                "{}. {} {}",
+                i + 1,
+                i18n::weekday_name(locale, time.weekday()),
+                time_format.date_time(&time)
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Agenda of the day with all the times of each reminder on one line,
+/// cut to fit into a message
+pub fn format_digest(
+    header: &str,
+    entries: &[(Vec<NaiveDateTime>, String)],
+    user_timezone: Tz,
+    time_format: TimeFormat,
+    max_len: usize,
+) -> String {
+    let mut text = header.to_owned();
+    for (times, desc) in entries {
+        let line = format!(
+            r"{} <{}\>",
+            escape(
+                &times
+                    .iter()
+                    .map(|time| time_format
+                        .time(&user_timezone.from_utc_datetime(time)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            bold(&escape(desc)),
+        );
+        // Leave room for the ellipsis
+        if text_len(&text) + text_len(&line) + 3 > max_len {
+            text += "\n…";
+            break;
+        }
+        text += "\n";
+        text += &line;
+    }
+    text
+}
+
+/// Group of reminders under a heading in the list
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ListGroup {
+    Today,
+    Tomorrow,
+    ThisWeek,
+    Later,
+    Recurring,
+    Paused,
+}
+
+impl ListGroup {
+    pub fn of(
+        reminder: &dyn GenericReminder,
+        user_timezone: Tz,
+        now: NaiveDateTime,
+    ) -> Self {
+        if reminder.is_paused() {
+            return Self::Paused;
+        }
+        if reminder.is_recurring() {
+            return Self::Recurring;
+        }
+        let today = user_timezone.from_utc_datetime(&now).date_naive();
+        let date = user_timezone
+            .from_utc_datetime(&reminder.get_time())
+            .date_naive();
+        let next_week = today
+            + Duration::days(7 - today.weekday().num_days_from_monday() as i64);
+        if date <= today {
+            Self::Today
+        } else if date == today + Duration::days(1) {
+            Self::Tomorrow
+        } else if date < next_week {
+            Self::ThisWeek
+        } else {
+            Self::Later
+        }
+    }
+
+    fn heading(&self, locale: Locale) -> String {
+        let key = match self {
+            Self::Today => "list-group-today",
+            Self::Tomorrow => "list-group-tomorrow",
+            Self::ThisWeek => "list-group-this-week",
+            Self::Later => "list-group-later",
+            Self::Recurring => "list-group-recurring",
+            Self::Paused => "list-group-paused",
+        };
+        i18n::tr(locale, key)
+    }
+}
+
+/// Markers of an entity in MarkdownV2 and whether it's code
+/// whose content is escaped differently and can't be inside other entities
+struct Markup {
+    open: String,
+    close: String,
+    code: bool,
+}
+
+impl Markup {
+    fn of(kind: &MessageEntityKind) -> Option<Self> {
+        let markup = |open: &str, close: &str| Self {
+            open: open.to_owned(),
+            close: close.to_owned(),
+            code: false,
+        };
+        match kind {
+            // The description is bold as a whole
+            MessageEntityKind::Bold => None,
+            MessageEntityKind::Italic => Some(markup("_", "_")),
+            MessageEntityKind::Underline => Some(markup("__", "__")),
+            MessageEntityKind::Strikethrough => Some(markup("~", "~")),
+            MessageEntityKind::Spoiler => Some(markup("||", "||")),
+            MessageEntityKind::Code => Some(Self {
+                code: true,
+                ..markup("`", "`")
+            }),
+            MessageEntityKind::Pre { language } => Some(Self {
+                code: true,
+                ..markup(
+                    &format!("```{}\n", language.as_deref().unwrap_or("")),
+                    "\n```",
+                )
+            }),
+            MessageEntityKind::TextLink { url } => Some(markup(
+                "[",
+                &format!("]({})", escape_link_url(url.as_str())),
+            )),
+            MessageEntityKind::TextMention { user } => {
+                Some(markup("[", &format!("](tg://user?id={})", user.id)))
+            }
+            MessageEntityKind::CustomEmoji { custom_emoji_id } => Some(markup(
+                "![",
+                &format!("](tg://emoji?id={})", custom_emoji_id),
+            )),
+            // Mentions, hashtags, links and the like are recognized anew
+            _ => None,
+        }
+    }
+}
+
+/// Append the marker separating underscores of adjacent entities
+/// which would be read as another entity otherwise
+fn push_marker(s: &mut String, marker: &str) {
+    if s.ends_with('_') && marker.starts_with('_') {
+        s.push('\r');
+    }
+    s.push_str(marker);
+}
+
+/// Bold description in MarkdownV2 with its formatting entities
+/// whose offsets are in UTF-16 code units as Telegram counts them
+pub fn format_description(desc: &str, entities: &[MessageEntity]) -> String {
+    let mut markups: Vec<(usize, usize, Markup)> = entities
+        .iter()
+        .filter_map(|entity| {
+            Some((
+                entity.offset,
+                entity.offset + entity.length,
+                Markup::of(&entity.kind)?,
+            ))
+        })
+        .collect();
+    if markups.is_empty() {
+        return bold(&escape(desc));
+    }
+    // Outer entities are opened first
+    markups.sort_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)));
+    let desc_len = text_len(desc);
+    markups.insert(
+        0,
+        (
+            0,
+            desc_len,
+            Markup {
+                open: "*".to_owned(),
+                close: "*".to_owned(),
+                code: false,
+            },
+        ),
+    );
+    // Byte offsets of the UTF-16 offsets
+    let mut byte_offsets: Vec<usize> = desc
+        .char_indices()
+        .flat_map(|(i, c)| std::iter::repeat_n(i, c.len_utf16()))
+        .collect();
+    byte_offsets.push(desc.len());
+    let mut bounds: Vec<usize> = markups
+        .iter()
+        .flat_map(|(start, end, _)| [*start, *end])
+        .filter(|&offset| offset <= desc_len)
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut s = String::new();
+    let mut open: Vec<usize> = vec![];
+    for segment in bounds.windows(2) {
+        let (from, until) = (segment[0], segment[1]);
+        let covering = |i: &usize| {
+            let (start, end, _) = &markups[*i];
+            *start <= from && until <= *end
+        };
+        let active: Vec<usize> = match (0..markups.len())
+            .filter(covering)
+            .find(|i| markups[*i].2.code)
+        {
+            Some(code) => vec![code],
+            None => (0..markups.len()).filter(covering).collect(),
+        };
+        let kept = open.iter().zip(&active).take_while(|(x, y)| x == y).count();
+        for i in open.drain(kept..).rev() {
+            push_marker(&mut s, &markups[i].2.close);
+        }
+        for &i in &active[kept..] {
+            push_marker(&mut s, &markups[i].2.open);
+        }
+        open = active;
+        let text = &desc[byte_offsets[from]..byte_offsets[until]];
+        match open.last() {
+            Some(&i) if markups[i].2.code => s += &escape_code(text),
+            _ => s += &escape(text),
+        }
+    }
+    for i in open.into_iter().rev() {
+        push_marker(&mut s, &markups[i].2.close);
+    }
+    s
+}
+
+/// Length of a text as Telegram counts it
+fn text_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Markers opening and closing the MarkdownV2 entity starting at `i`,
+/// the closing one of a link being its URL part
+fn markdown_entity_at(chars: &[char], i: usize) -> Option<(String, String)> {
+    let rest: String = chars[i..].iter().take(3).collect();
+    let symmetric = ["||", "__", "*", "_", "~", "`"]
+        .into_iter()
+        .find(|marker| rest.starts_with(marker));
+    if rest.starts_with("```") {
+        let open: String = match chars[i..].iter().position(|&c| c == '\n') {
+            Some(len) => chars[i..=i + len].iter().collect(),
+            None => chars[i..].iter().collect(),
+        };
+        Some((open, "\n```".to_owned()))
+    } else if let Some(marker) = symmetric {
+        Some((marker.to_owned(), marker.to_owned()))
+    } else if rest.starts_with('[') || rest.starts_with("![") {
+        // Brackets and parentheses inside links are escaped
+        let unescaped = |from: usize, target: char| {
+            let mut j = from;
+            while j < chars.len() && chars[j] != target {
+                j += if chars[j] == '\\' { 2 } else { 1 };
+            }
+            (j < chars.len()).then_some(j)
+        };
+        let text_end = unescaped(i, ']')?;
+        let url_end = unescaped(text_end, ')')?;
+        let open = if rest.starts_with('!') { "![" } else { "[" };
+        Some((open.to_owned(), chars[text_end..=url_end].iter().collect()))
+    } else {
+        None
+    }
+}
+
+/// Split a MarkdownV2 line into parts of at most `max_len` length,
+/// keeping escape sequences and link URLs whole and closing and reopening
+/// the entities cut in the middle
+fn split_markdown_line(line: &str, max_len: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let at = |i: usize, s: &str| {
+        chars[i..]
+            .iter()
+            .copied()
+            .take(s.chars().count())
+            .eq(s.chars())
+    };
+    let mut parts = vec![];
+    let mut part = String::new();
+    // Opening and closing markers of the entities open at the position
+    let mut open: Vec<(String, String)> = vec![];
+    // Whether the part has text besides the markers reopening entities
+    let mut has_text = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let mut open_after = open.clone();
+        let token: String = if chars[i] == '\\' {
+            chars[i..].iter().take(2).collect()
+        } else if let Some((_, close)) =
+            open.last().filter(|(_, close)| at(i, close))
+        {
+            open_after.pop();
+            close.clone()
+        } else if open.last().is_some_and(|(open, _)| open.starts_with('`')) {
+            // Only escape sequences and the closing marker are special in code
+            chars[i].to_string()
+        } else if let Some(entity) = markdown_entity_at(&chars, i) {
+            let token = entity.0.clone();
+            open_after.push(entity);
+            token
+        } else {
+            chars[i].to_string()
+        };
+        i += token.chars().count();
+        // Leave room for the markers closing the entities
+        let closing_len: usize =
+            open_after.iter().map(|(_, close)| text_len(close)).sum();
+        if has_text
+            && text_len(&part) + text_len(&token) + closing_len > max_len
+        {
+            for (_, close) in open.iter().rev() {
+                part += close;
+            }
+            parts.push(std::mem::take(&mut part));
+            for (marker, _) in &open {
+                part += marker;
+            }
+            has_text = false;
+        }
+        has_text |= open_after.len() == open.len();
+        part += &token;
+        open = open_after;
+    }
+    if !part.is_empty() {
+        parts.push(part);
+    }
+    parts
+}
+
+/// Group the sorted reminders and split them into pages with the header,
+/// each page fitting into a message and having at most `page_size` entries.
+/// Entries start with the reminder's reference accepted by `/next`
+#[allow(clippy::too_many_arguments)]
+pub fn format_reminders_list_pages(
+    header: &str,
+    reminders: &[Box<dyn GenericReminder>],
+    user_timezone: Tz,
+    time_format: TimeFormat,
+    now: NaiveDateTime,
+    page_size: usize,
+    max_len: usize,
+    locale: Locale,
+) -> Vec<String> {
+    let mut groups: Vec<(ListGroup, Vec<String>)> = vec![];
+    for reminder in reminders {
+        let group = ListGroup::of(reminder.as_ref(), user_timezone, now);
+        let reminder_ref = format_reminder_ref(
+            reminder.get_type(),
+            reminder.get_id().unwrap_or_default(),
+        );
+        let line = format!(
+            "{} {}",
+            code_inline(&reminder_ref),
+            reminder.to_string(user_timezone, time_format, locale)
+        );
+        match groups.iter_mut().find(|(x, _)| *x == group) {
+            Some((_, lines)) => lines.push(line),
+            None => groups.push((group, vec![line])),
+        }
+    }
+    groups.sort_by_key(|(group, _)| *group);
+
+    let mut pages = vec![];
+    let mut page = header.to_owned();
+    let mut page_entries = 0;
+    let mut page_group = None;
+    for (group, lines) in groups {
+        let heading = format!("\n\n{}", bold(&escape(&group.heading(locale))));
+        let line_max_len =
+            max_len.saturating_sub(text_len(header) + text_len(&heading) + 1);
+        for line in lines {
+            for part in split_markdown_line(&line, line_max_len) {
+                let mut addition = format!("\n{}", part);
+                if page_group != Some(group) {
+                    addition = heading.clone() + &addition;
+                }
+                if page_entries > 0
+                    && (page_entries >= page_size
+                        || text_len(&page) + text_len(&addition) > max_len)
+                {
+                    pages.push(std::mem::replace(&mut page, header.to_owned()));
+                    page_entries = 0;
+                    addition = format!("{}\n{}", heading, part);
+                }
+                page += &addition;
+                page_entries += 1;
+                page_group = Some(group);
+            }
+        }
+    }
+    pages.push(page);
+    pages
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::{cron_reminder, reminder};
+    use chrono::NaiveDate;
+    use sea_orm::ActiveValue::Set;
+    use test_case::test_case;
+
+    fn reminder(time: NaiveDateTime, desc: &str) -> Box<dyn GenericReminder> {
+        Box::new(reminder::ActiveModel {
+            id: Set(0),
+            chat_id: Set(1),
+            user_id: Set(Some(1)),
+            time: Set(time),
+            desc: Set(desc.to_owned()),
+            edit: Set(false),
+            paused: Set(false),
+            pattern: Set(None),
+            urgent: Set(false),
+            edit_mode: Set(None),
+            reply_to_message_id: Set(None),
+            reply_to_chat_id: Set(None),
+            media_type: Set(None),
+            file_id: Set(None),
+            entities: Set(None),
+            recipients: Set(None),
+            deleted_time: Set(None),
+        })
+    }
+
+    fn now() -> NaiveDateTime {
+        // Wednesday
+        NaiveDate::from_ymd_opt(2024, 5, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test_case(None => "[🔔](tg://user?id=7)" ; "author" )]
+    #[test_case(Some(r#"[{"username":"alice"},{"user":{"id":42,"name":"Bob"}}]"#) => "🔔 @alice [Bob](tg://user?id=42)" ; "recipients" )]
+    fn test_format_reminder_mentions(recipients: Option<&str>) -> String {
+        let rem = reminder::ActiveModel {
+            id: Set(0),
+            chat_id: Set(-5),
+            user_id: Set(Some(7)),
+            time: Set(now()),
+            desc: Set("submit timesheets".to_owned()),
+            edit: Set(false),
+            paused: Set(false),
+            pattern: Set(None),
+            urgent: Set(false),
+            edit_mode: Set(None),
+            reply_to_message_id: Set(None),
+            reply_to_chat_id: Set(None),
+            media_type: Set(None),
+            file_id: Set(None),
+            entities: Set(None),
+            recipients: Set(recipients.map(ToOwned::to_owned)),
+            deleted_time: Set(None),
+        };
+        format_reminder(&rem, chrono_tz::UTC, TimeFormat::default(), Locale::En)
+            .lines()
+            .next()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test_case(-30 => ListGroup::Today ; "overdue" )]
+    #[test_case(60 => ListGroup::Today ; "today" )]
+    #[test_case(24 * 60 => ListGroup::Tomorrow ; "tomorrow" )]
+    #[test_case(4 * 24 * 60 => ListGroup::ThisWeek ; "sunday" )]
+    #[test_case(5 * 24 * 60 => ListGroup::Later ; "next monday" )]
+    fn test_list_group(minutes: i64) -> ListGroup {
+        let rem = reminder(now() + Duration::minutes(minutes), "x");
+        ListGroup::of(rem.as_ref(), chrono_tz::UTC, now())
+    }
+
+    #[test]
+    fn test_list_group_paused() {
+        let rem = reminder::ActiveModel {
+            paused: Set(true),
+            pattern: Set(None),
+            ..Default::default()
+        };
+        assert_eq!(
+            ListGroup::of(&rem, chrono_tz::UTC, now()),
+            ListGroup::Paused
+        );
+    }
+
+    #[test_case(DateOrder::DayMonth, false, 0, 5 => "05.01.2024 00:05" ; "day month 24 hour midnight" )]
+    #[test_case(DateOrder::MonthDay, true, 0, 5 => "01/05/2024 12:05 AM" ; "month day 12 hour midnight" )]
+    #[test_case(DateOrder::MonthDay, true, 12, 0 => "01/05/2024 12:00 PM" ; "noon" )]
+    #[test_case(DateOrder::DayMonth, true, 23, 30 => "05.01.2024 11:30 PM" ; "evening" )]
+    fn test_time_format(
+        date_order: DateOrder,
+        twelve_hour_clock: bool,
+        hour: u32,
+        minute: u32,
+    ) -> String {
+        let time_format = TimeFormat {
+            date_order,
+            twelve_hour_clock,
+        };
+        time_format.date_time(
+            &NaiveDate::from_ymd_opt(2024, 1, 5)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_format_occurrences() {
+        assert_eq!(
+            format_occurrences(
+                &[now(), now() + Duration::days(1)],
+                chrono_tz::Europe::Moscow,
+                TimeFormat::default(),
+                Locale::En
+            ),
+            "1\\. Wed 15\\.05\\.2024 15:00\n2\\. Thu 16\\.05\\.2024 15:00"
+        );
+        assert_eq!(
+            format_occurrences(
+                &[now()],
+                chrono_tz::Europe::Moscow,
+                TimeFormat::default(),
+                Locale::Ru
+            ),
+            "1\\. Ср 15\\.05\\.2024 15:00"
+        );
+        let us_format = TimeFormat {
+            date_order: DateOrder::MonthDay,
+            twelve_hour_clock: true,
+        };
+        assert_eq!(
+            format_occurrences(&[now()], chrono_tz::UTC, us_format, Locale::En),
+            "1\\. Wed 05/15/2024 12:00 PM"
+        );
+    }
+
+    #[test]
+    fn test_serialize_time_in_reminder_timezone() {
+        let rem = cron_reminder::ActiveModel {
+            time: Set(now() + Duration::hours(1)),
+            timezone: Set(Some("Europe/Berlin".to_owned())),
+            ..Default::default()
+        };
+        assert_eq!(
+            rem.serialize_time_unescaped(
+                chrono_tz::Europe::Moscow,
+                TimeFormat::default()
+            ),
+            "15.05 16:00 (15.05 15:00 Europe/Berlin)"
+        );
+        assert_eq!(
+            rem.serialize_time_unescaped(
+                chrono_tz::Europe::Berlin,
+                TimeFormat::default()
+            ),
+            "15.05 15:00"
+        );
+    }
+
+    fn entity(
+        kind: MessageEntityKind,
+        offset: usize,
+        length: usize,
+    ) -> MessageEntity {
+        MessageEntity {
+            kind,
+            offset,
+            length,
+        }
+    }
+
+    #[test_case("buy milk", vec![] => "*buy milk*" ; "plain" )]
+    #[test_case("buy milk.", vec![entity(MessageEntityKind::Italic, 4, 4)] => r"*buy _milk_\.*" ; "italic" )]
+    #[test_case("buy milk", vec![entity(MessageEntityKind::Bold, 4, 4)] => "*buy milk*" ; "bold" )]
+    #[test_case("see docs", vec![entity(MessageEntityKind::TextLink { url: "https://example.com/a)".parse().unwrap() }, 4, 4)] => r"*see [docs](https://example.com/a\))*" ; "text link" )]
+    #[test_case("run ls now", vec![entity(MessageEntityKind::Code, 4, 2)] => "*run *`ls`* now*" ; "code" )]
+    #[test_case("a`b", vec![entity(MessageEntityKind::Code, 0, 3)] => "`a\\`b`" ; "code only" )]
+    #[test_case("ab", vec![entity(MessageEntityKind::Italic, 0, 1), entity(MessageEntityKind::Underline, 1, 1)] => "*_a_\r__b__*" ; "adjacent underscores" )]
+    #[test_case("ab", vec![entity(MessageEntityKind::Italic, 0, 2), entity(MessageEntityKind::Underline, 0, 1)] => "*_\r__a__b_*" ; "nested" )]
+    #[test_case("🥛 milk", vec![entity(MessageEntityKind::Strikethrough, 3, 4)] => "*🥛 ~milk~*" ; "utf16 offset" )]
+    #[test_case("call @me", vec![entity(MessageEntityKind::Mention, 5, 3)] => "*call @me*" ; "mention" )]
+    fn test_format_description(
+        desc: &str,
+        entities: Vec<MessageEntity>,
+    ) -> String {
+        format_description(desc, &entities)
+    }
+
+    #[test_case(r"ab\.cd", 3 => vec![r"ab".to_owned(), r"\.c".to_owned(), "d".to_owned()] ; "escape sequence" )]
+    #[test_case("a*bcd*", 4 => vec!["a*b*".to_owned(), "*cd*".to_owned()] ; "bold entity" )]
+    #[test_case("abc", 10 => vec!["abc".to_owned()] ; "short line" )]
+    #[test_case("_abcd_", 4 => vec!["_ab_".to_owned(), "_cd_".to_owned()] ; "italic entity" )]
+    #[test_case("__abcd__", 6 => vec!["__ab__".to_owned(), "__cd__".to_owned()] ; "underline entity" )]
+    #[test_case("~abcd~", 4 => vec!["~ab~".to_owned(), "~cd~".to_owned()] ; "strikethrough entity" )]
+    #[test_case("||abcd||", 6 => vec!["||ab||".to_owned(), "||cd||".to_owned()] ; "spoiler entity" )]
+    #[test_case("*_a_\r__bcd__*", 8 => vec!["*_a_\r*".to_owned(), "*__bc__*".to_owned(), "*__d__*".to_owned()] ; "adjacent underscores" )]
+    #[test_case(r"*a`bc\`d`*", 6 => vec!["*a`b`*".to_owned(), "*`c`*".to_owned(), r"*`\``*".to_owned(), "*`d`*".to_owned()] ; "code entity" )]
+    #[test_case("```rust\nab\\`cd\n```", 15 => vec!["```rust\nab\n```".to_owned(), "```rust\n\\`c\n```".to_owned(), "```rust\nd\n```".to_owned()] ; "pre entity" )]
+    #[test_case(r"[abcd](https://e\)x)", 18 => vec![r"[ab](https://e\)x)".to_owned(), r"[cd](https://e\)x)".to_owned()] ; "text link" )]
+    #[test_case(
+        "*_Prepare the quarterly report_: __check figures__, ~call the accountant~, ||update slides|| and *`send`* [them to the team](https://example.com/team) before the review meeting*", 40
+        => vec![
+            "*_Prepare the quarterly report_: __ch__*".to_owned(),
+            "*__eck figures__, ~call the accountant~*".to_owned(),
+            "*, ||update slides|| and *`send`* *".to_owned(),
+            "*[them to th](https://example.com/team)*".to_owned(),
+            "*[e team](https://example.com/team) bef*".to_owned(),
+            "*ore the review meeting*".to_owned(),
+        ] ; "long formatted description"
+    )]
+    fn test_split_markdown_line(line: &str, max_len: usize) -> Vec<String> {
+        split_markdown_line(line, max_len)
+    }
+
+    #[test]
+    fn test_format_reminders_list_pages() {
+        let reminders: Vec<_> = (0..6)
+            .map(|i| reminder(now() + Duration::days(i), &i.to_string()))
+            .collect();
+        let pages = format_reminders_list_pages(
+            "List:",
+            &reminders,
+            chrono_tz::UTC,
+            TimeFormat::default(),
+            now(),
+            2,
+            4096,
+            Locale::En,
+        );
+        assert_eq!(pages.len(), 3);
+        assert!(pages[0].starts_with("List:\n\n*Today*\n`0` "));
+        assert!(pages[0].contains("\n\n*Tomorrow*\n"));
+        // The heading is repeated on the page continuing the group
+        assert!(pages[1].starts_with("List:\n\n*This week*\n"));
+        assert!(pages[2].starts_with("List:\n\n*This week*\n"));
+        assert!(pages[2].contains("\n\n*Later*\n"));
+        for page in pages {
+            assert!(text_len(&page) <= 4096);
+        }
+    }
+
+    #[test]
+    fn test_format_reminders_list_pages_long_line() {
+        let reminders = vec![reminder(now(), &"a.".repeat(3000))];
+        let pages = format_reminders_list_pages(
+            "List:",
+            &reminders,
+            chrono_tz::UTC,
+            TimeFormat::default(),
+            now(),
+            20,
+            4096,
+            Locale::En,
+        );
+        assert!(pages.len() > 1);
+        for page in pages {
+            assert!(text_len(&page) <= 4096);
+            assert_eq!(page.matches('*').count() % 2, 0);
+        }
+    }
+}
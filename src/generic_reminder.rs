@@ -1,5 +1,9 @@
 use crate::entity::{cron_reminder, reminder};
+use crate::format::{format_description, TimeFormat};
+use crate::i18n::{Locale, Localized};
+use crate::parsers::{cron_occurrences, Recipient};
 use crate::serializers::Pattern;
+use crate::tz::{format_timezone_word, parse_timezone};
 use chrono::prelude::*;
 use chrono::Utc;
 use chrono_tz::Tz;
@@ -7,8 +11,16 @@ use serde_json::from_str;
 use std::cmp::Ord;
 use std::cmp::Ordering;
 use teloxide::types::ChatId;
+use teloxide::types::MessageEntity;
 use teloxide::types::UserId;
-use teloxide::utils::markdown::{bold, escape};
+use teloxide::utils::markdown::escape;
+
+/// Formatting entities of a description stored in a reminder
+fn parse_entities(entities: Option<&str>) -> Vec<MessageEntity> {
+    entities
+        .and_then(|entities| from_str(entities).ok())
+        .unwrap_or_default()
+}
 
 /// Interface to grab reminders of different types together
 /// to format, display, sort or get attributes
@@ -16,32 +28,96 @@ pub trait GenericReminder {
     fn get_time(&self) -> NaiveDateTime;
     fn get_id(&self) -> Option<i64>;
     fn get_type(&self) -> &'static str;
-    fn to_string(&self, user_timezone: Tz) -> String;
+    fn is_paused(&self) -> bool;
+    /// Whether the reminder will fire again after the next time
+    fn is_recurring(&self) -> bool;
+    /// Times of the reminder's firings starting with the next one
+    fn occurrences(
+        &self,
+        user_timezone: Tz,
+    ) -> Box<dyn Iterator<Item = NaiveDateTime>>;
+    fn to_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        locale: Locale,
+    ) -> String;
     fn to_string_with_mention(
         &self,
         user_timezone: Tz,
+        time_format: TimeFormat,
         user_id: i64,
+        locale: Locale,
     ) -> String {
         format!(
             "[🔔](tg://user?id={})\n{}",
             user_id,
-            self.to_string(user_timezone),
+            self.to_string(user_timezone, time_format, locale),
+        )
+    }
+    fn to_string_with_recipients(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        recipients: &[Recipient],
+        locale: Locale,
+    ) -> String {
+        format!(
+            "🔔 {}\n{}",
+            recipients
+                .iter()
+                .map(Recipient::mention)
+                .collect::<Vec<_>>()
+                .join(" "),
+            self.to_string(user_timezone, time_format, locale),
         )
     }
-    fn to_unescaped_string(&self, user_timezone: Tz) -> String;
-    fn serialize_time_unescaped(&self, user_timezone: Tz) -> String {
-        let time = user_timezone.from_utc_datetime(&self.get_time());
-        let now = Utc::now().with_timezone(&user_timezone);
-        let mut s = String::new();
-        if time.date_naive() != now.date_naive() {
-            s += &format!("{:02}.{:02} ", time.day(), time.month());
+    fn to_unescaped_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        locale: Locale,
+    ) -> String;
+    /// Text creating the same reminder when it's sent to the bot
+    fn to_text(&self, user_timezone: Tz) -> String;
+    /// Timezone of the reminder's pattern if it's set
+    fn get_timezone(&self) -> Option<Tz>;
+    /// Time in the user's timezone followed by the time
+    /// in the reminder's one if they differ
+    fn serialize_time_unescaped(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+    ) -> String {
+        let serialize = |timezone: Tz| {
+            let time = timezone.from_utc_datetime(&self.get_time());
+            let now = Utc::now().with_timezone(&timezone);
+            let mut s = String::new();
+            if time.date_naive() != now.date_naive() {
+                s += &format!("{} ", time_format.day_month(&time));
+            }
+            s + &time_format.time(&time)
+        };
+        match self.get_timezone() {
+            Some(timezone) if timezone != user_timezone => format!(
+                "{} ({} {})",
+                serialize(user_timezone),
+                serialize(timezone),
+                timezone.name()
+            ),
+            _ => serialize(user_timezone),
         }
-        s + &format!("{:02}:{:02}", time.hour(), time.minute())
     }
-    fn serialize_time(&self, user_timezone: Tz) -> String {
-        escape(&self.serialize_time_unescaped(user_timezone))
+    fn serialize_time(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+    ) -> String {
+        escape(&self.serialize_time_unescaped(user_timezone, time_format))
     }
     fn user_id(&self) -> Option<UserId>;
+    /// Group members the reminder is assigned to
+    fn recipients(&self) -> Vec<Recipient>;
     fn chat_id(&self) -> ChatId;
     fn is_group(&self) -> bool {
         let chat_id = self.chat_id();
@@ -49,6 +125,35 @@ pub trait GenericReminder {
     }
 }
 
+/// Date and time of the UTC time in the timezone as a one-time pattern
+fn format_time_point(time: NaiveDateTime, timezone: Tz) -> String {
+    let time = timezone.from_utc_datetime(&time);
+    if time.second() == 0 {
+        time.format("%d.%m.%Y %H:%M").to_string()
+    } else {
+        time.format("%d.%m.%Y %H:%M:%S").to_string()
+    }
+}
+
+/// Pattern followed by its timezone if it's not the user's one
+fn with_timezone(pattern: String, timezone: Tz, user_timezone: Tz) -> String {
+    if timezone == user_timezone {
+        pattern
+    } else {
+        format!("{} {}", pattern, format_timezone_word(timezone))
+    }
+}
+
+fn join_text(urgent: bool, pattern: &str, desc: &str) -> String {
+    let s = format!("{} {}", pattern, desc);
+    let s = s.trim_end();
+    if urgent {
+        format!("! {}", s)
+    } else {
+        s.to_owned()
+    }
+}
+
 impl GenericReminder for reminder::ActiveModel {
     fn get_time(&self) -> NaiveDateTime {
         self.time.clone().unwrap()
@@ -62,22 +167,74 @@ impl GenericReminder for reminder::ActiveModel {
         "rem"
     }
 
-    fn to_unescaped_string(&self, user_timezone: Tz) -> String {
+    fn is_paused(&self) -> bool {
+        self.paused.clone().unwrap()
+    }
+
+    fn is_recurring(&self) -> bool {
+        match self.pattern.clone().unwrap() {
+            Some(ref s) => from_str::<Pattern>(s)
+                .map(|pattern| pattern.is_recurring())
+                .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        self.pattern
+            .clone()
+            .unwrap()
+            .and_then(|s| from_str::<Pattern>(&s).ok())
+            .map(|pattern| pattern.timezone())
+    }
+
+    fn occurrences(
+        &self,
+        _user_timezone: Tz,
+    ) -> Box<dyn Iterator<Item = NaiveDateTime>> {
+        let time = self.get_time();
+        let pattern: Option<Pattern> = self
+            .pattern
+            .clone()
+            .unwrap()
+            .and_then(|s| from_str(&s).ok());
+        Box::new(
+            std::iter::once(time).chain(
+                pattern
+                    .map(|pattern| pattern.occurrences(time))
+                    .into_iter()
+                    .flatten(),
+            ),
+        )
+    }
+
+    fn to_unescaped_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        locale: Locale,
+    ) -> String {
         let main_part = format!(
             r"{} <{}>",
-            self.serialize_time_unescaped(user_timezone),
+            self.serialize_time_unescaped(user_timezone, time_format),
             self.desc.clone().unwrap(),
         );
         let s = match self.pattern.clone().unwrap() {
             Some(ref s) => {
                 let pattern: Pattern = from_str(s).unwrap();
-                match pattern.to_string().as_str() {
-                    "" => main_part,
-                    s => format!(r"{} [{}]", main_part, s),
+                if pattern.is_recurring() {
+                    format!(r"{} [{}]", main_part, Localized(&pattern, locale))
+                } else {
+                    main_part
                 }
             }
             None => main_part,
         };
+        let s = if self.urgent.clone().unwrap() {
+            format!("❗ {}", s)
+        } else {
+            s
+        };
         if self.paused.clone().unwrap() {
             format!("⏸ {}", s)
         } else {
@@ -85,22 +242,68 @@ impl GenericReminder for reminder::ActiveModel {
         }
     }
 
-    fn to_string(&self, user_timezone: Tz) -> String {
+    fn to_text(&self, user_timezone: Tz) -> String {
+        let pattern: Option<Pattern> = self
+            .pattern
+            .clone()
+            .unwrap()
+            .and_then(|s| from_str(&s).ok());
+        let pattern_part = match pattern {
+            Some(ref pattern @ Pattern::Recurrence(_)) => with_timezone(
+                pattern.to_string(),
+                pattern.timezone(),
+                user_timezone,
+            ),
+            // The rest of a countdown is relative to its start,
+            // so only the next time is kept
+            Some(ref pattern @ Pattern::Countdown(_)) => with_timezone(
+                format_time_point(self.get_time(), pattern.timezone()),
+                pattern.timezone(),
+                user_timezone,
+            ),
+            None => format_time_point(self.get_time(), user_timezone),
+        };
+        join_text(
+            self.urgent.clone().unwrap(),
+            &pattern_part,
+            &self.desc.clone().unwrap(),
+        )
+    }
+
+    fn to_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        locale: Locale,
+    ) -> String {
         let main_part = format!(
             r"{} <{}\>",
-            self.serialize_time(user_timezone),
-            bold(&escape(&self.desc.clone().unwrap())),
+            self.serialize_time(user_timezone, time_format),
+            format_description(
+                &self.desc.clone().unwrap(),
+                &parse_entities(self.entities.clone().unwrap().as_deref())
+            ),
         );
         let s = match self.pattern.clone().unwrap() {
             Some(ref s) => {
                 let pattern: Pattern = from_str(s).unwrap();
-                match pattern.to_string().as_str() {
-                    "" => main_part,
-                    s => format!(r"{} \[{}\]", main_part, escape(s)),
+                if pattern.is_recurring() {
+                    format!(
+                        r"{} \[{}\]",
+                        main_part,
+                        escape(&Localized(&pattern, locale).to_string())
+                    )
+                } else {
+                    main_part
                 }
             }
             None => main_part,
         };
+        let s = if self.urgent.clone().unwrap() {
+            format!("❗ {}", s)
+        } else {
+            s
+        };
         if self.paused.clone().unwrap() {
             format!("⏸ {}", s)
         } else {
@@ -112,6 +315,10 @@ impl GenericReminder for reminder::ActiveModel {
         self.user_id.clone().unwrap().map(|id| UserId(id as u64))
     }
 
+    fn recipients(&self) -> Vec<Recipient> {
+        Recipient::from_column(self.recipients.clone().unwrap().as_deref())
+    }
+
     fn chat_id(&self) -> ChatId {
         ChatId(self.chat_id.clone().unwrap())
     }
@@ -130,13 +337,50 @@ impl GenericReminder for cron_reminder::ActiveModel {
         "cron_rem"
     }
 
-    fn to_unescaped_string(&self, user_timezone: Tz) -> String {
+    fn is_paused(&self) -> bool {
+        self.paused.clone().unwrap()
+    }
+
+    fn is_recurring(&self) -> bool {
+        true
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        self.timezone
+            .clone()
+            .unwrap()
+            .and_then(|name| name.parse().ok())
+    }
+
+    fn occurrences(
+        &self,
+        user_timezone: Tz,
+    ) -> Box<dyn Iterator<Item = NaiveDateTime>> {
+        let time = self.get_time();
+        Box::new(std::iter::once(time).chain(cron_occurrences(
+            &self.cron_expr.clone().unwrap(),
+            self.get_timezone().unwrap_or(user_timezone),
+            time,
+        )))
+    }
+
+    fn to_unescaped_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        _locale: Locale,
+    ) -> String {
         let s = format!(
             "{} <{}> [{}]",
-            self.serialize_time_unescaped(user_timezone),
+            self.serialize_time_unescaped(user_timezone, time_format),
             self.desc.clone().unwrap(),
             self.cron_expr.clone().unwrap()
         );
+        let s = if self.urgent.clone().unwrap() {
+            format!("❗ {}", s)
+        } else {
+            s
+        };
         if self.paused.clone().unwrap() {
             format!("⏸ {}", s)
         } else {
@@ -144,13 +388,44 @@ impl GenericReminder for cron_reminder::ActiveModel {
         }
     }
 
-    fn to_string(&self, user_timezone: Tz) -> String {
+    fn to_text(&self, _user_timezone: Tz) -> String {
+        let cron_expr = self.cron_expr.clone().unwrap();
+        let pattern_part = match self.timezone.clone().unwrap() {
+            Some(timezone) => match parse_timezone(&timezone) {
+                Some(timezone) => {
+                    format!("{} {}", cron_expr, format_timezone_word(timezone))
+                }
+                None => format!("{} {}", cron_expr, timezone),
+            },
+            None => cron_expr,
+        };
+        join_text(
+            self.urgent.clone().unwrap(),
+            &pattern_part,
+            &self.desc.clone().unwrap(),
+        )
+    }
+
+    fn to_string(
+        &self,
+        user_timezone: Tz,
+        time_format: TimeFormat,
+        _locale: Locale,
+    ) -> String {
         let s = format!(
             r"{} <{}\> \[{}\]",
-            self.serialize_time(user_timezone),
-            bold(&escape(&self.desc.clone().unwrap())),
+            self.serialize_time(user_timezone, time_format),
+            format_description(
+                &self.desc.clone().unwrap(),
+                &parse_entities(self.entities.clone().unwrap().as_deref())
+            ),
             escape(&self.cron_expr.clone().unwrap())
         );
+        let s = if self.urgent.clone().unwrap() {
+            format!("❗ {}", s)
+        } else {
+            s
+        };
         if self.paused.clone().unwrap() {
             format!("⏸ {}", s)
         } else {
@@ -162,6 +437,10 @@ impl GenericReminder for cron_reminder::ActiveModel {
         self.user_id.clone().unwrap().map(|id| UserId(id as u64))
     }
 
+    fn recipients(&self) -> Vec<Recipient> {
+        Recipient::from_column(self.recipients.clone().unwrap().as_deref())
+    }
+
     fn chat_id(&self) -> ChatId {
         ChatId(self.chat_id.clone().unwrap())
     }
@@ -1,12 +1,73 @@
 use bitmask_enum::bitmask;
 use nonempty::{nonempty, NonEmpty};
 
-use pest::{iterators::Pair, Parser};
+use pest::{iterators::Pair, Parser, RuleType};
 
+use crate::i18n::Locale;
+use crate::tz::parse_timezone_word;
+
+/// Parser of reminders with English keywords, its rules are
+/// the ones the parsed reminders are built from
 #[derive(Parser)]
 #[grammar = "grammars/reminder.pest"]
+#[grammar = "grammars/keywords/english.pest"]
+#[grammar = "grammars/keywords/en.pest"]
 struct ReminderParser;
 
+/// Rule of a parser of reminders in some language
+trait LocalizedRule: RuleType {
+    /// The same rule of the English parser
+    fn unlocalized(self) -> Rule;
+}
+
+impl LocalizedRule for Rule {
+    fn unlocalized(self) -> Rule {
+        self
+    }
+}
+
+/// Parser of reminders with keywords of another language
+/// which differs only in the keyword rules, so the rules of its pairs
+/// are mapped to the English ones
+macro_rules! localized_parser {
+    ($lang:ident, $keywords:literal) => {
+        mod $lang {
+            #[derive(Parser)]
+            #[grammar = "grammars/reminder.pest"]
+            #[grammar = "grammars/keywords/english.pest"]
+            #[grammar = $keywords]
+            pub struct ReminderParser;
+
+            impl super::LocalizedRule for Rule {
+                fn unlocalized(self) -> super::Rule {
+                    localized_parser!(@map self, super::Rule,
+                        EOI, year, month, day, hour, minute, second,
+                        monday, tuesday, wednesday, thursday, friday,
+                        saturday, sunday, weekday_from, weekday_to,
+                        weekdays_range, interval_years, interval_months,
+                        interval_weeks, interval_days, interval_hours,
+                        interval_minutes, interval_seconds, interval,
+                        time_interval, date_interval, date_from, date_until,
+                        dates_point, dates_range, time_from, time_until,
+                        time_point, time_range, recurrence, countdown,
+                        timezone, description, urgent, reminder
+                    )
+                }
+            }
+        }
+    };
+    (@map $rule:expr, $target:ty, $($name:ident),*) => {
+        match $rule {
+            $(Rule::$name => <$target>::$name,)*
+            // the other rules are silent or not matched by the parser
+            _ => unreachable!(),
+        }
+    };
+}
+
+localized_parser!(ru, "grammars/keywords/ru.pest");
+localized_parser!(uk, "grammars/keywords/uk.pest");
+
 #[derive(Debug, Default)]
 pub struct HoleyDate {
     pub year: Option<i32>,
@@ -118,6 +179,8 @@ pub enum TimePattern {
 pub struct Recurrence {
     pub dates_patterns: NonEmpty<DatePattern>,
     pub time_patterns: Vec<TimePattern>,
+    /// Timezone of the dates and times instead of the user's one
+    pub timezone: Option<chrono_tz::Tz>,
 }
 
 #[derive(Debug, Default)]
@@ -135,22 +198,23 @@ pub enum ReminderPattern {
 pub struct Reminder {
     pub description: Option<Description>,
     pub pattern: Option<ReminderPattern>,
+    pub urgent: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct Description(pub String);
 
 trait Parse {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()>
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()>
     where
         Self: Sized;
 }
 
 impl Parse for HoleyDate {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut holey_date = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::year => {
                     holey_date.year =
                         Some(rec.as_str().parse().map_err(|_| ())?);
@@ -171,10 +235,10 @@ impl Parse for HoleyDate {
 }
 
 impl Parse for Interval {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut interval = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::interval_years => {
                     interval.years = rec.as_str().parse().map_err(|_| ())?;
                 }
@@ -218,10 +282,10 @@ impl Weekday {
 }
 
 impl Parse for Weekday {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         pair.into_inner()
             .next()
-            .map(|weekday| match weekday.as_rule() {
+            .map(|weekday| match weekday.as_rule().unlocalized() {
                 Rule::monday => Self::Monday,
                 Rule::tuesday => Self::Tuesday,
                 Rule::wednesday => Self::Wednesday,
@@ -249,7 +313,7 @@ impl Weekdays {
     }
 }
 impl Parse for Weekdays {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut weekdays = Self::none();
         let mut weekday_range = pair.into_inner();
         let mut weekday_from = weekday_range
@@ -272,10 +336,10 @@ impl Parse for Weekdays {
 }
 
 impl Parse for DateRange {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut date_range = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::date_from => {
                     date_range.from = HoleyDate::parse(rec)?;
                 }
@@ -308,10 +372,10 @@ impl Parse for DateRange {
 }
 
 impl Parse for Time {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut time = Self::default();
         for time_component in pair.into_inner() {
-            match time_component.as_rule() {
+            match time_component.as_rule().unlocalized() {
                 Rule::hour => {
                     time.hour =
                         time_component.as_str().parse().map_err(|_| ())?;
@@ -332,10 +396,10 @@ impl Parse for Time {
 }
 
 impl Parse for TimeInterval {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut time_interval = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::interval_hours => {
                     time_interval.hours =
                         rec.as_str().parse().map_err(|_| ())?;
@@ -356,10 +420,10 @@ impl Parse for TimeInterval {
 }
 
 impl Parse for DateInterval {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut date_interval = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::interval_years => {
                     date_interval.years =
                         rec.as_str().parse().map_err(|_| ())?;
@@ -384,10 +448,10 @@ impl Parse for DateInterval {
 }
 
 impl Parse for TimeRange {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut time_range = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::time_from => {
                     time_range.from = Some(Time::parse(rec)?);
                 }
@@ -411,15 +475,16 @@ impl Default for Recurrence {
         Self {
             dates_patterns: nonempty![DatePattern::Point(HoleyDate::default())],
             time_patterns: vec![],
+            timezone: None,
         }
     }
 }
 
 impl Parse for Recurrence {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut recurrence = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::dates_point => {
                     recurrence
                         .dates_patterns
@@ -440,6 +505,9 @@ impl Parse for Recurrence {
                         .time_patterns
                         .push(TimePattern::Range(TimeRange::parse(rec)?));
                 }
+                Rule::timezone => {
+                    recurrence.timezone = parse_timezone_word(rec.as_str());
+                }
                 _ => unreachable!(),
             }
         }
@@ -452,10 +520,10 @@ impl Parse for Recurrence {
 }
 
 impl Parse for Countdown {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut countdown = Self::default();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::interval => {
                     countdown.durations.push(Interval::parse(rec)?);
                 }
@@ -467,29 +535,48 @@ impl Parse for Countdown {
 }
 
 impl Parse for Description {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         Ok(Self(pair.as_str().to_string()))
     }
 }
 
 impl Parse for Reminder {
-    fn parse(pair: Pair<'_, Rule>) -> Result<Self, ()> {
+    fn parse<R: LocalizedRule>(pair: Pair<'_, R>) -> Result<Self, ()> {
         let mut reminder = Self::default();
+        let input = pair.as_str();
+        let offset = pair.as_span().start();
         for rec in pair.into_inner() {
-            match rec.as_rule() {
+            match rec.as_rule().unlocalized() {
                 Rule::description => {
-                    reminder.description = Some(Description::parse(rec)?);
+                    if reminder.description.is_none() {
+                        reminder.description = Some(Description::parse(rec)?);
+                    }
                 }
                 Rule::recurrence => {
-                    reminder.pattern = Some(ReminderPattern::Recurrence(
-                        Recurrence::parse(rec)?,
-                    ));
+                    let recurrence = Recurrence::parse(rec.clone())?;
+                    // the word after the time which is not a timezone
+                    // begins the description
+                    let word = rec.into_inner().find(|rec| {
+                        rec.as_rule().unlocalized() == Rule::timezone
+                    });
+                    if let (Some(word), None) = (word, &recurrence.timezone) {
+                        reminder.description = Some(Description(
+                            input[word.as_span().start() - offset..]
+                                .trim_end()
+                                .to_owned(),
+                        ));
+                    }
+                    reminder.pattern =
+                        Some(ReminderPattern::Recurrence(recurrence));
                 }
                 Rule::countdown => {
                     reminder.pattern = Some(ReminderPattern::Countdown(
                         Countdown::parse(rec)?,
                     ));
                 }
+                Rule::urgent => {
+                    reminder.urgent = true;
+                }
                 Rule::EOI => {}
                 _ => unreachable!(),
             }
@@ -498,9 +585,12 @@ impl Parse for Reminder {
     }
 }
 
-pub fn parse_reminder(s: &str) -> Result<Reminder, ()> {
+fn parse_with<P: Parser<R>, R: LocalizedRule>(
+    s: &str,
+    rule: R,
+) -> Result<Reminder, ()> {
     Reminder::parse(
-        ReminderParser::parse(Rule::reminder, s)
+        P::parse(rule, s)
             .map_err(|err| {
                 log::debug!("{}", err);
             })?
@@ -508,3 +598,108 @@ pub fn parse_reminder(s: &str) -> Result<Reminder, ()> {
             .ok_or(())?,
     )
 }
+
+/// Parse a reminder with the keywords of the locale's language,
+/// English keywords are accepted in any language
+pub fn parse_reminder(s: &str, locale: Locale) -> Result<Reminder, ()> {
+    match locale {
+        Locale::En => parse_with::<ReminderParser, _>(s, Rule::reminder),
+        Locale::Ru => {
+            parse_with::<ru::ReminderParser, _>(s, ru::Rule::reminder)
+        }
+        Locale::Uk => {
+            parse_with::<uk::ReminderParser, _>(s, uk::Rule::reminder)
+        }
+    }
+}
+
+/// Parse a pattern without a description or urgency mark
+/// in the locale's language
+pub fn parse_pattern(s: &str, locale: Locale) -> Result<ReminderPattern, ()> {
+    match parse_reminder(s, locale)? {
+        Reminder {
+            description: None,
+            pattern: Some(pattern),
+            urgent: false,
+        } => Ok(pattern),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    /// Parsed reminder in the locale is the same as the English one
+    fn assert_parsed_as_english(s: &str, locale: Locale, english: &str) {
+        let reminder = parse_reminder(english, Locale::En);
+        assert!(reminder.is_ok(), "{} is not parsed", english);
+        assert_eq!(
+            format!("{:?}", parse_reminder(s, locale)),
+            format!("{:?}", reminder),
+        );
+    }
+
+    #[test_case("каждый пн 9:00 планёрка", "every mon 9:00 планёрка" ; "every weekday" )]
+    #[test_case("Каждую среду 19:30 бассейн", "every wed 19:30 бассейн" ; "capitalized full weekday" )]
+    #[test_case("по вт,чт 8:00 зарядка", "on tue,thu 8:00 зарядка" ; "weekdays list" )]
+    #[test_case("по понедельникам-пятницам 7:00 подъём", "on mon-fri 7:00 подъём" ; "weekdays range" )]
+    #[test_case("во вторник 10:00 стоматолог", "on tue 10:00 стоматолог" ; "on weekday" )]
+    #[test_case("каждое воскресенье 12:00 обед", "every sun 12:00 обед" ; "sunday" )]
+    #[test_case("в 17:30 ужин", "at 17:30 ужин" ; "at time" )]
+    #[test_case("каждые 2нед 18:00 отчёт", "every 2w 18:00 отчёт" ; "date interval" )]
+    #[test_case("каждые 3мес 10:00 счётчики", "every 3months 10:00 счётчики" ; "months interval" )]
+    #[test_case("10:00-18:00 каждые 30мин размяться", "10:00-18:00 every 30m размяться" ; "time interval" )]
+    #[test_case("через 2ч позвонить", "in 2h позвонить" ; "countdown" )]
+    #[test_case("Через 1год2дня3часа4минуты5сек", "in 1y2d3h4m5s" ; "countdown all units" )]
+    #[test_case("через 10м чай", "in 10m чай" ; "minutes" )]
+    #[test_case("every fri 18:00 пятница", "every fri 18:00 пятница" ; "english fallback" )]
+    #[test_case("каждый mon at 9:00 мешанина", "every mon 9:00 мешанина" ; "mixed languages" )]
+    fn test_russian(s: &str, english: &str) {
+        assert_parsed_as_english(s, Locale::Ru, english);
+    }
+
+    #[test_case("кожного понеділка 9:00 нарада", "every mon 9:00 нарада" ; "every weekday" )]
+    #[test_case("Кожен пн 9:00 нарада", "every mon 9:00 нарада" ; "capitalized every" )]
+    #[test_case("у п'ятницю 18:00 кіно", "on fri 18:00 кіно" ; "on weekday" )]
+    #[test_case("по вт,чт 8:00 зарядка", "on tue,thu 8:00 зарядка" ; "weekdays list" )]
+    #[test_case("кожну неділю 12:00 обід", "every sun 12:00 обід" ; "sunday" )]
+    #[test_case("о 17:30 вечеря", "at 17:30 вечеря" ; "at time" )]
+    #[test_case("кожні 2тиж 18:00 звіт", "every 2w 18:00 звіт" ; "date interval" )]
+    #[test_case("10:00-18:00 кожні 30хв розім'ятися", "10:00-18:00 every 30m розім'ятися" ; "time interval" )]
+    #[test_case("через 2год зателефонувати", "in 2h зателефонувати" ; "countdown" )]
+    #[test_case("через 1рік2дні3години4хвилини5сек", "in 1y2d3h4m5s" ; "countdown all units" )]
+    #[test_case("in 2h зателефонувати", "in 2h зателефонувати" ; "english fallback" )]
+    fn test_ukrainian(s: &str, english: &str) {
+        assert_parsed_as_english(s, Locale::Uk, english);
+    }
+
+    #[test_case("every mon,wed-fri 9:00 meeting" ; "weekdays" )]
+    #[test_case("in 1y2mo3w4d5h6m7s cake" ; "countdown" )]
+    #[test_case("1.05-/2d at 10:00-12:00/30m water" ; "ranges" )]
+    fn test_english_in_every_locale(s: &str) {
+        for locale in Locale::ALL {
+            assert_parsed_as_english(s, locale, s);
+        }
+    }
+
+    #[test]
+    fn test_keywords_of_other_language() {
+        assert!(parse_reminder("через 2хв чай", Locale::Ru).is_err());
+        assert!(parse_reminder("через 2хв чай", Locale::Uk).is_ok());
+        // "год" is years in Russian and hours in Ukrainian
+        assert_parsed_as_english("через 2год", Locale::Ru, "in 2y");
+        assert_parsed_as_english("через 2год", Locale::Uk, "in 2h");
+    }
+
+    #[test_case("every mon 9:00", Locale::En => true ; "recurrence" )]
+    #[test_case("10:00 Europe/Berlin", Locale::En => true ; "timezone" )]
+    #[test_case("через 2ч", Locale::Ru => true ; "countdown" )]
+    #[test_case("every mon 9:00 meeting", Locale::En => false ; "description" )]
+    #[test_case("10:00 Nowhere", Locale::En => false ; "unknown timezone" )]
+    #[test_case("!10:00", Locale::En => false ; "urgent" )]
+    fn test_parse_pattern(s: &str, locale: Locale) -> bool {
+        parse_pattern(s, locale).is_ok()
+    }
+}
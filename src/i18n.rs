@@ -0,0 +1,249 @@
+use crate::db;
+use crate::settings;
+use chrono::Weekday;
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource, FluentValue};
+use std::fmt;
+use std::str::FromStr;
+use teloxide::types::UserId;
+
+/// Language of the bot's messages having its own catalog
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+    Uk,
+}
+
+impl Locale {
+    pub const ALL: [Self; 3] = [Self::En, Self::Ru, Self::Uk];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Ru => "ru",
+            Self::Uk => "uk",
+        }
+    }
+
+    /// Locale of Telegram's `language_code` (IETF language tag)
+    /// if there is a catalog for the language
+    pub fn from_language_code(language_code: &str) -> Option<Self> {
+        language_code
+            .split(['-', '_'])
+            .next()
+            .and_then(|language| language.parse().ok())
+    }
+
+    fn catalog(&self) -> &'static str {
+        match self {
+            Self::En => include_str!("locales/en.ftl"),
+            Self::Ru => include_str!("locales/ru.ftl"),
+            Self::Uk => include_str!("locales/uk.ftl"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown locale: {}", s))
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+lazy_static! {
+    /// Message catalogs in the order of `Locale::ALL`
+    static ref BUNDLES: Vec<FluentBundle<FluentResource>> = Locale::ALL
+        .iter()
+        .map(|locale| {
+            let resource = FluentResource::try_new(locale.catalog().to_owned())
+                .unwrap_or_else(|(_, errs)| {
+                    panic!("Failed to parse {} catalog: {:?}", locale, errs)
+                });
+            let mut bundle = FluentBundle::new_concurrent(vec![locale
+                .code()
+                .parse()
+                .expect("Invalid language identifier")]);
+            // Unicode isolation marks around arguments would break
+            // MarkdownV2 escaping and commands in the text
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .expect("Duplicate message in catalog");
+            bundle
+        })
+        .collect();
+}
+
+/// Message of the locale's catalog with the arguments substituted,
+/// the English one is used if the catalog lacks the message
+pub fn tr_args(locale: Locale, key: &str, args: Option<&FluentArgs>) -> String {
+    for locale in [locale, Locale::En] {
+        let bundle = &BUNDLES[locale as usize];
+        if let Some(pattern) = bundle.get_message(key).and_then(|x| x.value()) {
+            let mut errs = vec![];
+            let text = bundle.format_pattern(pattern, args, &mut errs);
+            for err in errs {
+                log::error!("Failed to format message {}: {}", key, err);
+            }
+            return text.into_owned();
+        }
+    }
+    log::error!("Missing message {}", key);
+    key.to_owned()
+}
+
+pub fn tr(locale: Locale, key: &str) -> String {
+    tr_args(locale, key, None)
+}
+
+/// Message with the named arguments substituted
+pub fn tr_with<'a>(
+    locale: Locale,
+    key: &str,
+    args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>,
+) -> String {
+    tr_args(locale, key, Some(&args.into_iter().collect()))
+}
+
+pub fn weekday_name(locale: Locale, weekday: Weekday) -> String {
+    let key = match weekday {
+        Weekday::Mon => "weekday-mon",
+        Weekday::Tue => "weekday-tue",
+        Weekday::Wed => "weekday-wed",
+        Weekday::Thu => "weekday-thu",
+        Weekday::Fri => "weekday-fri",
+        Weekday::Sat => "weekday-sat",
+        Weekday::Sun => "weekday-sun",
+    };
+    tr(locale, key)
+}
+
+/// Formatting of a value depending on the locale
+pub trait LocalizedDisplay {
+    fn fmt_localized(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        locale: Locale,
+    ) -> fmt::Result;
+}
+
+/// Value displayed in the locale
+pub struct Localized<'a, T: ?Sized>(pub &'a T, pub Locale);
+
+impl<T: LocalizedDisplay + ?Sized> fmt::Display for Localized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_localized(f, self.1)
+    }
+}
+
+/// Locale chosen by the user with /language, otherwise the one of
+/// the user's Telegram language which is remembered to be used
+/// when there is no `language_code` (e.g. in sent reminders)
+pub async fn get_user_locale(
+    db: &db::Database,
+    user_id: UserId,
+    language_code: Option<&str>,
+) -> Result<Locale, db::Error> {
+    let user_id = user_id.0 as i64;
+    if let Some(locale) =
+        db.get_user_setting::<settings::Language>(user_id).await?
+    {
+        return Ok(locale);
+    }
+    let known_locale = db
+        .get_user_setting::<settings::TelegramLanguage>(user_id)
+        .await?;
+    match language_code.and_then(Locale::from_language_code) {
+        Some(locale) if known_locale != Some(locale) => {
+            db.set_user_setting::<settings::TelegramLanguage>(user_id, &locale)
+                .await?;
+            Ok(locale)
+        }
+        Some(locale) => Ok(locale),
+        None => Ok(known_locale.unwrap_or_default()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("en" => Some(Locale::En) ; "english" )]
+    #[test_case("ru" => Some(Locale::Ru) ; "russian" )]
+    #[test_case("uk-UA" => Some(Locale::Uk) ; "ukrainian with region" )]
+    #[test_case("pt-br" => None ; "no catalog" )]
+    fn test_from_language_code(language_code: &str) -> Option<Locale> {
+        Locale::from_language_code(language_code)
+    }
+
+    #[test]
+    fn test_catalogs_have_all_messages() {
+        let keys = Locale::En
+            .catalog()
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(key, _)| key)
+            .filter(|key| !key.starts_with([' ', '#']));
+        for key in keys {
+            for locale in Locale::ALL {
+                assert!(
+                    BUNDLES[locale as usize].has_message(key),
+                    "{} lacks {}",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test_case(Locale::En, 1 => "🗑 Deleted 1 reminder, use /undo to restore them one by one" ; "en one" )]
+    #[test_case(Locale::En, 3 => "🗑 Deleted 3 reminders, use /undo to restore them one by one" ; "en other" )]
+    #[test_case(Locale::Ru, 1 => "🗑 Удалено 1 напоминание, восстановить их по одному можно командой /undo" ; "ru one" )]
+    #[test_case(Locale::Ru, 3 => "🗑 Удалено 3 напоминания, восстановить их по одному можно командой /undo" ; "ru few" )]
+    #[test_case(Locale::Ru, 11 => "🗑 Удалено 11 напоминаний, восстановить их по одному можно командой /undo" ; "ru many" )]
+    #[test_case(Locale::Uk, 21 => "🗑 Видалено 21 нагадування, відновити їх по одному можна командою /undo" ; "uk one" )]
+    #[test_case(Locale::Uk, 5 => "🗑 Видалено 5 нагадувань, відновити їх по одному можна командою /undo" ; "uk many" )]
+    fn test_plurals(locale: Locale, count: usize) -> String {
+        tr_with(locale, "success-bulk-delete", [("count", count.into())])
+    }
+
+    #[test]
+    fn test_multiline_message() {
+        assert_eq!(
+            tr_with(
+                Locale::En,
+                "chosen-timezone",
+                [("timezone", "UTC".into())]
+            ),
+            "Selected timezone UTC. Now you can set some reminders.\n\n\
+             You can get the commands I understand with /help."
+        );
+    }
+
+    #[test_case(Locale::En, true => "📅 Daily digest is sent at 08:00 even on days with nothing scheduled" ; "en always" )]
+    #[test_case(Locale::En, false => "📅 Daily digest is sent at 08:00" ; "en not always" )]
+    #[test_case(Locale::Ru, false => "📅 Ежедневная сводка отправляется в 08:00" ; "ru not always" )]
+    fn test_string_selector(locale: Locale, always: bool) -> String {
+        tr_with(
+            locale,
+            "digest-schedule",
+            [
+                ("time", "08:00".into()),
+                ("always", always.to_string().into()),
+            ],
+        )
+    }
+}
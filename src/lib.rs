@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate pest;
+extern crate pretty_env_logger;
+#[macro_use]
+extern crate pest_derive;
+
+pub mod bot;
+mod cli;
+mod controller;
+mod date;
+mod db;
+mod digest;
+mod entity;
+mod err;
+mod format;
+mod generic_reminder;
+mod grammar;
+mod i18n;
+mod migration;
+mod parsers;
+mod quiet;
+mod serializers;
+mod settings;
+mod tg;
+mod tz;
+
+pub use serializers::{DateDivisor, DateInterval, DateRange};
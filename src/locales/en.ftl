@@ -0,0 +1,195 @@
+# Messages of the bot in English
+
+language-name = English
+
+## Commands
+
+commands-header = Commands:
+cmd-list = list the set reminders, e.g. /list #work
+cmd-tags = list the tags of reminders
+cmd-search = search reminders, e.g. /search dentist
+cmd-delete = choose reminders to delete
+cmd-edit = choose reminders to edit
+cmd-cancel = cancel editing
+cmd-pause = choose reminders to pause
+cmd-quiet = set quiet hours, e.g. /quiet 23:00-07:30 or off
+cmd-next = show the next occurrences of a reminder
+cmd-digest = set the daily agenda, e.g. /digest 08:00 or off
+cmd-history = show the history of sent reminders
+cmd-undo = undo the last deletion, edit or pause
+cmd-set = set a new reminder
+cmd-settings = change your settings
+cmd-language = choose the language, e.g. /language ru
+cmd-settimezone = select a timezone, in a group the group's one
+cmd-timezone = show your timezone
+cmd-help = show this text
+cmd-start = start
+
+## Responses
+
+success-insert =
+    Added a reminder:
+    { $reminder }
+success-periodic-insert =
+    Added a periodic reminder:
+    { $reminder }
+failed-insert = Failed to create a reminder...
+incorrect-request = Incorrect request!
+reply-to-forwarded = Send the time to be reminded about the forwarded message, e.g. "in 2h"
+querying-error = Error occured while querying reminders...
+reminders-list-header = List of reminders:
+tagged-reminders-list-header = List of reminders tagged #{ $tag }:
+tags-list-header = Tags of reminders:
+no-tags = No tags yet, add #hashtags to reminders' descriptions to group them
+search-results-header = Found reminders:
+nothing-found = No reminders found
+empty-search-query = Enter what to search for, e.g. /search dentist
+select-timezone = Select your timezone:
+chosen-timezone =
+    Selected timezone { $timezone }. Now you can set some reminders.
+
+    You can get the commands I understand with /help.
+no-chosen-timezone = You've not selected timezone yet, please do it with /settimezone command
+failed-set-timezone = Failed to set timezone { $timezone }
+select-chat-timezone = Select the timezone of the group's reminders:
+chat-timezone = 🌍 The group's timezone is { $timezone }, reminders set here use it
+not-chat-admin = Only the group's administrators can change its timezone
+no-chat-timezone = Only the group's administrators can set its timezone. Until they do, your reminders here use your own timezone: set it with /settimezone in a private chat with me
+reanchor-question = Your timezone changed from { $old_timezone } to { $timezone }. What should your reminders keep?
+reanchored-wall-clock = 🕘 Reminders fire at the same local times in { $timezone }
+reanchored-instants = ⏱ Reminders fire at the same moments as in { $timezone }
+failed-reanchor = Failed to update the reminders...
+choose-delete-reminder = Choose a reminder to delete:
+success-delete = 🗑 Deleted a reminder: { $reminder }
+failed-delete = Failed to delete...
+choose-edit-reminder = Choose a reminder to edit:
+enter-new-reminder = Enter reminder to replace with:
+enter-new-text = Enter the new text of the reminder:
+enter-new-pattern = Enter the new time or pattern of the reminder without its text:
+enter-new-timezone = Enter the new timezone of the reminder, e.g. Europe/Berlin:
+copy-reminder-text = Tap the text to copy it, then edit and send it:
+success-edit =
+    📝 Replaced a reminder: { $old_reminder }
+    with ➡️ { $reminder }
+failed-edit = Failed to edit... You can try again or cancel editing with /cancel
+cancel-edit = Canceled editing
+failed-cancel-edit = Failed to canceled editing...
+choose-pause-reminder = Choose a reminder to pause/resume:
+success-pause = ⏸ Paused a reminder: { $reminder }
+success-resume = ▶️ Resumed a reminder: { $reminder }
+failed-pause = Failed to pause...
+success-bulk-delete =
+    🗑 Deleted { $count ->
+        [one] { $count } reminder
+       *[other] { $count } reminders
+    }, use /undo to restore them one by one
+success-bulk-pause =
+    ⏸ Paused { $count ->
+        [one] { $count } reminder
+       *[other] { $count } reminders
+    }, use /undo to resume them one by one
+nothing-selected = No reminders are selected
+digest-schedule =
+    📅 Daily digest is sent at { $time }{ $always ->
+        [true] {" "}even on days with nothing scheduled
+       *[false] {""}
+    }
+digest-disabled = Daily digest is off
+digest-usage =
+    Usage:
+    /digest 08:00 — get the day's agenda every morning
+    /digest 08:00 always — get it even on days with nothing scheduled
+    /digest off — disable the digest
+failed-set-digest = Failed to set the digest...
+digest-header = 📅 Agenda for today:
+empty-digest = 📅 Nothing is scheduled for today
+settings-header = ⚙️ Settings:
+failed-set-setting = Failed to change the setting...
+quiet-hours =
+    🌙 Quiet hours are { $start }–{ $end }, reminders { $silent ->
+        [true] are sent silently
+       *[false] are deferred until they end
+    } (mark a reminder with ! to always ring, e.g. !7:00 wake up)
+quiet-hours-disabled = Quiet hours are off
+quiet-hours-usage =
+    Usage:
+    /quiet 23:00-07:30 — defer reminders until the quiet hours end
+    /quiet 23:00-07:30 silent — send them without a notification sound
+    /quiet off — disable the quiet hours
+    Quiet hours apply to reminders in private chats, not in groups
+failed-set-quiet-hours = Failed to set the quiet hours...
+choose-next-reminder = Choose a reminder to show its next occurrences:
+next-occurrences-header = 🔮 Next occurrences (/next { $ref }) of { $reminder }
+next-usage = Usage: /next to choose a reminder or /next 12 with the reminder's number shown in /list
+reminder-not-found = The reminder is not found, it may have been deleted
+reminder-done = ✅ { $name } marked the reminder as done
+reminder-snoozed = ⏰ { $name } snoozed the reminder for { $minutes } min
+not-recipient = Only the reminder's author and recipients can do this
+failed-snooze = Failed to snooze the reminder...
+history-header = History of sent reminders:
+empty-history = No reminders have been sent yet
+success-undo = ↩️ Restored a reminder: { $reminder }
+failed-undo = Failed to undo...
+nothing-to-undo = Nothing to undo
+chosen-language = 🌐 The language is { language-name }
+language-usage = Usage: /language en, /language ru or /language uk, /language auto to follow the language of Telegram
+hello =
+    Hello! I'm remindee bot. My purpose is to remind you of whatever you ask and whenever you ask.
+
+    Examples:
+    17:30 go to restaurant => notify today at 5:30 PM
+    01.01 00:00 Happy New Year => notify at 1st of January at 12 AM
+    15:00 Europe/Berlin sync with Hans => notify at 3 PM Berlin time
+    9:00 tz:EST standup => notify at 9 AM New York time (mark abbreviations with tz:)
+    55 10 * * 1-5 meeting call => notify at 10:55 AM every weekday (CRON expression format)
+
+    Before we start, please either send me your location or manually select the timezone using the /settimezone command first.
+
+## Formatting of reminders
+
+next-time = Next time → { $time }
+scheduled-for = (scheduled for { $time })
+list-group-today = Today
+list-group-tomorrow = Tomorrow
+list-group-this-week = This week
+list-group-later = Later
+list-group-recurring = Recurring
+list-group-paused = Paused
+weekday-mon = Mon
+weekday-tue = Tue
+weekday-wed = Wed
+weekday-thu = Thu
+weekday-fri = Fri
+weekday-sat = Sat
+weekday-sun = Sun
+
+## Buttons
+
+button-next-occurrences = 🔮 Show next occurrences
+button-undo = ↩️ Undo
+button-select-multiple = ☑️ Select multiple
+button-select-all = ☑️ Select all on page
+button-delete-selected = 🗑 Delete selected
+button-pause-selected = ⏸ Pause selected
+button-keep-wall-clock = 🕘 Keep local times
+button-keep-instants = ⏱ Keep exact moments
+button-copy-text = 📋 Copy as text
+button-snooze = ⏰ { $minutes } min
+button-done = ✅ Done
+button-change-text = ✏️ Change text
+button-change-pattern = 🕘 Change time/pattern
+button-change-timezone = 🌍 Change timezone
+button-cancel = ✖️ Cancel
+button-timezone = 🌍 Timezone: { $timezone }
+button-timezone-not-selected = 🌍 Timezone: not selected
+button-notifications-silent = 🔕 Notifications: silent
+button-notifications-sound = 🔔 Notifications: with sound
+button-quiet-hours = 🌙 Quiet hours: { $start }–{ $end }
+button-quiet-hours-off = 🌙 Quiet hours: off
+button-date-format = 📅 Date format: { $example }
+button-clock-24 = 🕐 Clock: 24-hour
+button-clock-12 = 🕐 Clock: 12-hour
+button-default-snooze = ⏰ Snooze reminders by: { $minutes } min
+button-overlap-once = 🔂 Repeated hour on clock change: fire once
+button-overlap-twice = 🔁 Repeated hour on clock change: fire twice
+button-language = 🌐 Language: { language-name }
@@ -0,0 +1,199 @@
+# Сообщения бота на русском
+
+language-name = русский
+
+## Команды
+
+commands-header = Команды:
+cmd-list = список напоминаний, например /list #работа
+cmd-tags = список тегов напоминаний
+cmd-search = поиск напоминаний, например /search стоматолог
+cmd-delete = выбрать напоминания для удаления
+cmd-edit = выбрать напоминания для изменения
+cmd-cancel = отменить изменение
+cmd-pause = выбрать напоминания для паузы
+cmd-quiet = тихие часы, например /quiet 23:00-07:30 или off
+cmd-next = показать следующие срабатывания напоминания
+cmd-digest = ежедневная сводка, например /digest 08:00 или off
+cmd-history = история отправленных напоминаний
+cmd-undo = отменить последнее удаление, изменение или паузу
+cmd-set = создать напоминание
+cmd-settings = изменить настройки
+cmd-language = выбрать язык, например /language en
+cmd-settimezone = выбрать часовой пояс, в группе — часовой пояс группы
+cmd-timezone = показать ваш часовой пояс
+cmd-help = показать этот текст
+cmd-start = начать
+
+## Ответы
+
+success-insert =
+    Добавлено напоминание:
+    { $reminder }
+success-periodic-insert =
+    Добавлено периодическое напоминание:
+    { $reminder }
+failed-insert = Не удалось создать напоминание...
+incorrect-request = Некорректный запрос!
+reply-to-forwarded = Отправьте время, когда напомнить о пересланном сообщении, например "через 2ч"
+querying-error = Ошибка при получении напоминаний...
+reminders-list-header = Список напоминаний:
+tagged-reminders-list-header = Список напоминаний с тегом #{ $tag }:
+tags-list-header = Теги напоминаний:
+no-tags = Тегов пока нет, добавьте #хэштеги в описания напоминаний, чтобы сгруппировать их
+search-results-header = Найденные напоминания:
+nothing-found = Напоминания не найдены
+empty-search-query = Введите, что искать, например /search стоматолог
+select-timezone = Выберите ваш часовой пояс:
+chosen-timezone =
+    Выбран часовой пояс { $timezone }. Теперь можно создавать напоминания.
+
+    Список команд, которые я понимаю, можно получить с помощью /help.
+no-chosen-timezone = Вы ещё не выбрали часовой пояс, сделайте это командой /settimezone
+failed-set-timezone = Не удалось установить часовой пояс { $timezone }
+select-chat-timezone = Выберите часовой пояс напоминаний группы:
+chat-timezone = 🌍 Часовой пояс группы — { $timezone }, напоминания в ней используют его
+not-chat-admin = Изменить часовой пояс группы могут только её администраторы
+no-chat-timezone = Задать часовой пояс группы могут только её администраторы. Пока его нет, ваши напоминания здесь используют ваш часовой пояс: выберите его командой /settimezone в личном чате со мной
+reanchor-question = Часовой пояс изменён с { $old_timezone } на { $timezone }. Что сохранить у ваших напоминаний?
+reanchored-wall-clock = 🕘 Напоминания срабатывают в то же местное время в { $timezone }
+reanchored-instants = ⏱ Напоминания срабатывают в те же моменты, что и в { $timezone }
+failed-reanchor = Не удалось обновить напоминания...
+choose-delete-reminder = Выберите напоминание для удаления:
+success-delete = 🗑 Удалено напоминание: { $reminder }
+failed-delete = Не удалось удалить...
+choose-edit-reminder = Выберите напоминание для изменения:
+enter-new-reminder = Введите напоминание на замену:
+enter-new-text = Введите новый текст напоминания:
+enter-new-pattern = Введите новое время или расписание напоминания без текста:
+enter-new-timezone = Введите новый часовой пояс напоминания, например Europe/Moscow:
+copy-reminder-text = Нажмите на текст, чтобы скопировать его, затем измените и отправьте:
+success-edit =
+    📝 Заменено напоминание: { $old_reminder }
+    на ➡️ { $reminder }
+failed-edit = Не удалось изменить... Попробуйте ещё раз или отмените изменение командой /cancel
+cancel-edit = Изменение отменено
+failed-cancel-edit = Не удалось отменить изменение...
+choose-pause-reminder = Выберите напоминание для паузы или возобновления:
+success-pause = ⏸ Приостановлено напоминание: { $reminder }
+success-resume = ▶️ Возобновлено напоминание: { $reminder }
+failed-pause = Не удалось приостановить...
+success-bulk-delete =
+    🗑 { $count ->
+        [one] Удалено { $count } напоминание
+        [few] Удалено { $count } напоминания
+       *[many] Удалено { $count } напоминаний
+    }, восстановить их по одному можно командой /undo
+success-bulk-pause =
+    ⏸ { $count ->
+        [one] Приостановлено { $count } напоминание
+        [few] Приостановлено { $count } напоминания
+       *[many] Приостановлено { $count } напоминаний
+    }, возобновить их по одному можно командой /undo
+nothing-selected = Не выбрано ни одного напоминания
+digest-schedule =
+    📅 Ежедневная сводка отправляется в { $time }{ $always ->
+        [true] {" "}даже в дни без запланированных напоминаний
+       *[false] {""}
+    }
+digest-disabled = Ежедневная сводка выключена
+digest-usage =
+    Использование:
+    /digest 08:00 — получать план на день каждое утро
+    /digest 08:00 always — получать его даже в дни без напоминаний
+    /digest off — выключить сводку
+failed-set-digest = Не удалось настроить сводку...
+digest-header = 📅 План на сегодня:
+empty-digest = 📅 На сегодня ничего не запланировано
+settings-header = ⚙️ Настройки:
+failed-set-setting = Не удалось изменить настройку...
+quiet-hours =
+    🌙 Тихие часы: { $start }–{ $end }, напоминания { $silent ->
+        [true] приходят без звука
+       *[false] откладываются до их окончания
+    } (отметьте напоминание знаком !, чтобы оно звенело всегда, например !7:00 подъём)
+quiet-hours-disabled = Тихие часы выключены
+quiet-hours-usage =
+    Использование:
+    /quiet 23:00-07:30 — откладывать напоминания до конца тихих часов
+    /quiet 23:00-07:30 silent — присылать их без звука
+    /quiet off — выключить тихие часы
+    Тихие часы действуют на напоминания в личных чатах, но не в группах
+failed-set-quiet-hours = Не удалось настроить тихие часы...
+choose-next-reminder = Выберите напоминание, чтобы показать его следующие срабатывания:
+next-occurrences-header = 🔮 Следующие срабатывания (/next { $ref }) напоминания { $reminder }
+next-usage = Использование: /next, чтобы выбрать напоминание, или /next 12 с номером напоминания из /list
+reminder-not-found = Напоминание не найдено, возможно, оно было удалено
+reminder-done = ✅ { $name } отметил(а) напоминание выполненным
+reminder-snoozed = ⏰ { $name } отложил(а) напоминание на { $minutes } мин.
+not-recipient = Это могут сделать только автор и получатели напоминания
+failed-snooze = Не удалось отложить напоминание...
+history-header = История отправленных напоминаний:
+empty-history = Ещё не было отправлено ни одного напоминания
+success-undo = ↩️ Восстановлено напоминание: { $reminder }
+failed-undo = Не удалось отменить...
+nothing-to-undo = Нечего отменять
+chosen-language = 🌐 Выбран язык: { language-name }
+language-usage = Использование: /language en, /language ru или /language uk, /language auto, чтобы следовать языку Telegram
+hello =
+    Привет! Я бот remindee. Я напомню вам о чём угодно и когда угодно.
+
+    Примеры:
+    17:30 пойти в ресторан => напомнит сегодня в 17:30
+    01.01 00:00 С Новым годом => напомнит 1 января в 00:00
+    каждый пн 9:00 планёрка => напомнит каждый понедельник в 9:00
+    через 2ч позвонить => напомнит через 2 часа
+    15:00 Europe/Berlin созвон с Гансом => напомнит в 15:00 по берлинскому времени
+    9:00 tz:EST планёрка => напомнит в 9:00 по нью-йоркскому времени (сокращения отмечаются tz:)
+    55 10 * * 1-5 созвон => напомнит в 10:55 каждый будний день (формат CRON)
+
+    Прежде чем начать, отправьте мне своё местоположение или выберите часовой пояс вручную командой /settimezone.
+
+## Оформление напоминаний
+
+next-time = В следующий раз → { $time }
+scheduled-for = (запланировано на { $time })
+list-group-today = Сегодня
+list-group-tomorrow = Завтра
+list-group-this-week = На этой неделе
+list-group-later = Позже
+list-group-recurring = Повторяющиеся
+list-group-paused = Приостановленные
+weekday-mon = Пн
+weekday-tue = Вт
+weekday-wed = Ср
+weekday-thu = Чт
+weekday-fri = Пт
+weekday-sat = Сб
+weekday-sun = Вс
+
+## Кнопки
+
+button-next-occurrences = 🔮 Следующие срабатывания
+button-undo = ↩️ Отменить
+button-select-multiple = ☑️ Выбрать несколько
+button-select-all = ☑️ Выбрать все на странице
+button-delete-selected = 🗑 Удалить выбранные
+button-pause-selected = ⏸ Приостановить выбранные
+button-keep-wall-clock = 🕘 Местное время
+button-keep-instants = ⏱ Точные моменты
+button-copy-text = 📋 Скопировать текстом
+button-snooze = ⏰ { $minutes } мин.
+button-done = ✅ Готово
+button-change-text = ✏️ Изменить текст
+button-change-pattern = 🕘 Изменить время
+button-change-timezone = 🌍 Изменить часовой пояс
+button-cancel = ✖️ Отмена
+button-timezone = 🌍 Часовой пояс: { $timezone }
+button-timezone-not-selected = 🌍 Часовой пояс: не выбран
+button-notifications-silent = 🔕 Уведомления: без звука
+button-notifications-sound = 🔔 Уведомления: со звуком
+button-quiet-hours = 🌙 Тихие часы: { $start }–{ $end }
+button-quiet-hours-off = 🌙 Тихие часы: выключены
+button-date-format = 📅 Формат даты: { $example }
+button-clock-24 = 🕐 Часы: 24-часовые
+button-clock-12 = 🕐 Часы: 12-часовые
+button-default-snooze = ⏰ Отложить напоминание: на { $minutes } мин.
+button-overlap-once = 🔂 Повтор часа при переводе часов: один раз
+button-overlap-twice = 🔁 Повтор часа при переводе часов: дважды
+button-language = 🌐 Язык: { language-name }
@@ -0,0 +1,199 @@
+# Повідомлення бота українською
+
+language-name = українська
+
+## Команди
+
+commands-header = Команди:
+cmd-list = список нагадувань, наприклад /list #робота
+cmd-tags = список тегів нагадувань
+cmd-search = пошук нагадувань, наприклад /search стоматолог
+cmd-delete = вибрати нагадування для видалення
+cmd-edit = вибрати нагадування для зміни
+cmd-cancel = скасувати зміну
+cmd-pause = вибрати нагадування для паузи
+cmd-quiet = тихі години, наприклад /quiet 23:00-07:30 або off
+cmd-next = показати наступні спрацювання нагадування
+cmd-digest = щоденне зведення, наприклад /digest 08:00 або off
+cmd-history = історія надісланих нагадувань
+cmd-undo = скасувати останнє видалення, зміну чи паузу
+cmd-set = створити нагадування
+cmd-settings = змінити налаштування
+cmd-language = вибрати мову, наприклад /language en
+cmd-settimezone = вибрати часовий пояс, у групі — часовий пояс групи
+cmd-timezone = показати ваш часовий пояс
+cmd-help = показати цей текст
+cmd-start = почати
+
+## Відповіді
+
+success-insert =
+    Додано нагадування:
+    { $reminder }
+success-periodic-insert =
+    Додано періодичне нагадування:
+    { $reminder }
+failed-insert = Не вдалося створити нагадування...
+incorrect-request = Некоректний запит!
+reply-to-forwarded = Надішліть час, коли нагадати про переслане повідомлення, наприклад "через 2год"
+querying-error = Помилка під час отримання нагадувань...
+reminders-list-header = Список нагадувань:
+tagged-reminders-list-header = Список нагадувань з тегом #{ $tag }:
+tags-list-header = Теги нагадувань:
+no-tags = Тегів поки немає, додайте #хештеги в описи нагадувань, щоб згрупувати їх
+search-results-header = Знайдені нагадування:
+nothing-found = Нагадувань не знайдено
+empty-search-query = Введіть, що шукати, наприклад /search стоматолог
+select-timezone = Виберіть ваш часовий пояс:
+chosen-timezone =
+    Вибрано часовий пояс { $timezone }. Тепер можна створювати нагадування.
+
+    Список команд, які я розумію, можна отримати за допомогою /help.
+no-chosen-timezone = Ви ще не вибрали часовий пояс, зробіть це командою /settimezone
+failed-set-timezone = Не вдалося встановити часовий пояс { $timezone }
+select-chat-timezone = Виберіть часовий пояс нагадувань групи:
+chat-timezone = 🌍 Часовий пояс групи — { $timezone }, нагадування в ній використовують його
+not-chat-admin = Змінити часовий пояс групи можуть лише її адміністратори
+no-chat-timezone = Задати часовий пояс групи можуть лише її адміністратори. Поки його немає, ваші нагадування тут використовують ваш часовий пояс: виберіть його командою /settimezone в особистому чаті зі мною
+reanchor-question = Часовий пояс змінено з { $old_timezone } на { $timezone }. Що зберегти у ваших нагадувань?
+reanchored-wall-clock = 🕘 Нагадування спрацьовують у той самий місцевий час у { $timezone }
+reanchored-instants = ⏱ Нагадування спрацьовують у ті самі моменти, що й у { $timezone }
+failed-reanchor = Не вдалося оновити нагадування...
+choose-delete-reminder = Виберіть нагадування для видалення:
+success-delete = 🗑 Видалено нагадування: { $reminder }
+failed-delete = Не вдалося видалити...
+choose-edit-reminder = Виберіть нагадування для зміни:
+enter-new-reminder = Введіть нагадування на заміну:
+enter-new-text = Введіть новий текст нагадування:
+enter-new-pattern = Введіть новий час або розклад нагадування без тексту:
+enter-new-timezone = Введіть новий часовий пояс нагадування, наприклад Europe/Kyiv:
+copy-reminder-text = Натисніть на текст, щоб скопіювати його, потім змініть і надішліть:
+success-edit =
+    📝 Замінено нагадування: { $old_reminder }
+    на ➡️ { $reminder }
+failed-edit = Не вдалося змінити... Спробуйте ще раз або скасуйте зміну командою /cancel
+cancel-edit = Зміну скасовано
+failed-cancel-edit = Не вдалося скасувати зміну...
+choose-pause-reminder = Виберіть нагадування для паузи або відновлення:
+success-pause = ⏸ Призупинено нагадування: { $reminder }
+success-resume = ▶️ Відновлено нагадування: { $reminder }
+failed-pause = Не вдалося призупинити...
+success-bulk-delete =
+    🗑 { $count ->
+        [one] Видалено { $count } нагадування
+        [few] Видалено { $count } нагадування
+       *[many] Видалено { $count } нагадувань
+    }, відновити їх по одному можна командою /undo
+success-bulk-pause =
+    ⏸ { $count ->
+        [one] Призупинено { $count } нагадування
+        [few] Призупинено { $count } нагадування
+       *[many] Призупинено { $count } нагадувань
+    }, відновити їх по одному можна командою /undo
+nothing-selected = Не вибрано жодного нагадування
+digest-schedule =
+    📅 Щоденне зведення надсилається о { $time }{ $always ->
+        [true] {" "}навіть у дні без запланованих нагадувань
+       *[false] {""}
+    }
+digest-disabled = Щоденне зведення вимкнено
+digest-usage =
+    Використання:
+    /digest 08:00 — отримувати план на день щоранку
+    /digest 08:00 always — отримувати його навіть у дні без нагадувань
+    /digest off — вимкнути зведення
+failed-set-digest = Не вдалося налаштувати зведення...
+digest-header = 📅 План на сьогодні:
+empty-digest = 📅 На сьогодні нічого не заплановано
+settings-header = ⚙️ Налаштування:
+failed-set-setting = Не вдалося змінити налаштування...
+quiet-hours =
+    🌙 Тихі години: { $start }–{ $end }, нагадування { $silent ->
+        [true] надходять без звуку
+       *[false] відкладаються до їх завершення
+    } (позначте нагадування знаком !, щоб воно дзвеніло завжди, наприклад !7:00 підйом)
+quiet-hours-disabled = Тихі години вимкнено
+quiet-hours-usage =
+    Використання:
+    /quiet 23:00-07:30 — відкладати нагадування до кінця тихих годин
+    /quiet 23:00-07:30 silent — надсилати їх без звуку
+    /quiet off — вимкнути тихі години
+    Тихі години діють на нагадування в особистих чатах, але не в групах
+failed-set-quiet-hours = Не вдалося налаштувати тихі години...
+choose-next-reminder = Виберіть нагадування, щоб показати його наступні спрацювання:
+next-occurrences-header = 🔮 Наступні спрацювання (/next { $ref }) нагадування { $reminder }
+next-usage = Використання: /next, щоб вибрати нагадування, або /next 12 з номером нагадування з /list
+reminder-not-found = Нагадування не знайдено, можливо, його було видалено
+reminder-done = ✅ { $name } позначив(-ла) нагадування виконаним
+reminder-snoozed = ⏰ { $name } відклав(-ла) нагадування на { $minutes } хв
+not-recipient = Це можуть зробити лише автор і отримувачі нагадування
+failed-snooze = Не вдалося відкласти нагадування...
+history-header = Історія надісланих нагадувань:
+empty-history = Ще не було надіслано жодного нагадування
+success-undo = ↩️ Відновлено нагадування: { $reminder }
+failed-undo = Не вдалося скасувати...
+nothing-to-undo = Нічого скасовувати
+chosen-language = 🌐 Вибрано мову: { language-name }
+language-usage = Використання: /language en, /language ru або /language uk, /language auto, щоб слідувати мові Telegram
+hello =
+    Привіт! Я бот remindee. Я нагадаю вам про що завгодно і коли завгодно.
+
+    Приклади:
+    17:30 піти в ресторан => нагадає сьогодні о 17:30
+    01.01 00:00 З Новим роком => нагадає 1 січня о 00:00
+    кожного понеділка 9:00 нарада => нагадає щопонеділка о 9:00
+    через 2год зателефонувати => нагадає через 2 години
+    15:00 Europe/Berlin дзвінок з Гансом => нагадає о 15:00 за берлінським часом
+    9:00 tz:EST нарада => нагадає о 9:00 за нью-йоркським часом (скорочення позначаються tz:)
+    55 10 * * 1-5 дзвінок => нагадає о 10:55 кожного буднього дня (формат CRON)
+
+    Перш ніж почати, надішліть мені своє місцезнаходження або виберіть часовий пояс вручну командою /settimezone.
+
+## Оформлення нагадувань
+
+next-time = Наступного разу → { $time }
+scheduled-for = (заплановано на { $time })
+list-group-today = Сьогодні
+list-group-tomorrow = Завтра
+list-group-this-week = Цього тижня
+list-group-later = Пізніше
+list-group-recurring = Повторювані
+list-group-paused = Призупинені
+weekday-mon = Пн
+weekday-tue = Вт
+weekday-wed = Ср
+weekday-thu = Чт
+weekday-fri = Пт
+weekday-sat = Сб
+weekday-sun = Нд
+
+## Кнопки
+
+button-next-occurrences = 🔮 Наступні спрацювання
+button-undo = ↩️ Скасувати
+button-select-multiple = ☑️ Вибрати кілька
+button-select-all = ☑️ Вибрати все на сторінці
+button-delete-selected = 🗑 Видалити вибрані
+button-pause-selected = ⏸ Призупинити вибрані
+button-keep-wall-clock = 🕘 Місцевий час
+button-keep-instants = ⏱ Точні моменти
+button-copy-text = 📋 Скопіювати текстом
+button-snooze = ⏰ { $minutes } хв
+button-done = ✅ Готово
+button-change-text = ✏️ Змінити текст
+button-change-pattern = 🕘 Змінити час
+button-change-timezone = 🌍 Змінити часовий пояс
+button-cancel = ✖️ Скасувати
+button-timezone = 🌍 Часовий пояс: { $timezone }
+button-timezone-not-selected = 🌍 Часовий пояс: не вибрано
+button-notifications-silent = 🔕 Сповіщення: без звуку
+button-notifications-sound = 🔔 Сповіщення: зі звуком
+button-quiet-hours = 🌙 Тихі години: { $start }–{ $end }
+button-quiet-hours-off = 🌙 Тихі години: вимкнено
+button-date-format = 📅 Формат дати: { $example }
+button-clock-24 = 🕐 Годинник: 24-годинний
+button-clock-12 = 🕐 Годинник: 12-годинний
+button-default-snooze = ⏰ Відкласти нагадування: на { $minutes } хв
+button-overlap-once = 🔂 Повтор години при переведенні годинника: один раз
+button-overlap-twice = 🔁 Повтор години при переведенні годинника: двічі
+button-language = 🌐 Мова: { language-name }
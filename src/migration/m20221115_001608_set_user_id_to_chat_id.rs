@@ -1,4 +1,4 @@
-use sea_orm::{ConnectionTrait, Statement};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -7,6 +7,27 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // PostgreSQL doesn't accept backtick-quoted identifiers
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let conn = manager.get_connection();
+            let db_backend = manager.get_database_backend();
+            for stmt in [
+                Query::update()
+                    .table(Reminder::Table)
+                    .value(Reminder::UserId, Expr::col(Reminder::ChatId))
+                    .to_owned(),
+                Query::update()
+                    .table(CronReminder::Table)
+                    .value(
+                        CronReminder::UserId,
+                        Expr::col(CronReminder::ChatId),
+                    )
+                    .to_owned(),
+            ] {
+                conn.execute(db_backend.build(&stmt)).await?;
+            }
+            return Ok(());
+        }
         let sql = r#"
             UPDATE `reminder` SET `user_id` = `chat_id`;
             UPDATE `cron_reminder` SET `user_id` = `chat_id`
@@ -22,3 +43,17 @@ impl MigrationTrait for Migration {
         Ok(())
     }
 }
+
+#[derive(Iden)]
+pub enum Reminder {
+    Table,
+    UserId,
+    ChatId,
+}
+
+#[derive(Iden)]
+pub enum CronReminder {
+    Table,
+    UserId,
+    ChatId,
+}
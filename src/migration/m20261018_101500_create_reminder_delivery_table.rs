@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderDelivery::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReminderDelivery::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::ReminderId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::ReminderType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::Desc)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::Time)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::SentTime)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderDelivery::MessageId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_delivery_chat_id_sent_time")
+                    .table(ReminderDelivery::Table)
+                    .col(ReminderDelivery::ChatId)
+                    .col(ReminderDelivery::SentTime)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReminderDelivery::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReminderDelivery {
+    Table,
+    Id,
+    ReminderId,
+    ReminderType,
+    ChatId,
+    Desc,
+    Time,
+    SentTime,
+    MessageId,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderUndo::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReminderUndo::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderUndo::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReminderUndo::Action).text().not_null())
+                    .col(
+                        ColumnDef::new(ReminderUndo::ReminderType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderUndo::Reminder)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReminderUndo::NewReminderType).text())
+                    .col(
+                        ColumnDef::new(ReminderUndo::NewReminderId)
+                            .big_integer(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderUndo::Time)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_undo_chat_id_time")
+                    .table(ReminderUndo::Table)
+                    .col(ReminderUndo::ChatId)
+                    .col(ReminderUndo::Time)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReminderUndo::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReminderUndo {
+    Table,
+    Id,
+    ChatId,
+    Action,
+    ReminderType,
+    Reminder,
+    NewReminderType,
+    NewReminderId,
+    Time,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderSelection::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReminderSelection::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSelection::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSelection::MessageId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSelection::ReminderType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSelection::ReminderId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSelection::Time)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_selection_chat_id_message_id")
+                    .table(ReminderSelection::Table)
+                    .col(ReminderSelection::ChatId)
+                    .col(ReminderSelection::MessageId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop().table(ReminderSelection::Table).to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReminderSelection {
+    Table,
+    Id,
+    ChatId,
+    MessageId,
+    ReminderType,
+    ReminderId,
+    Time,
+}
@@ -0,0 +1,132 @@
+use crate::parsers::parse_tags;
+use sea_orm::{ConnectionTrait, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Extract tags of the already existing reminders of the table
+async fn fill_tags<T: Iden + Copy + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+    reminder_type: &str,
+) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let db_backend = manager.get_database_backend();
+    let stmt = Query::select()
+        .columns([Alias::new("id"), Alias::new("chat_id"), Alias::new("desc")])
+        .from(table)
+        .to_owned();
+    for row in conn.query_all(db_backend.build(&stmt)).await? {
+        let id: i64 = row.try_get("", "id")?;
+        let chat_id: i64 = row.try_get("", "chat_id")?;
+        let desc: String = row.try_get("", "desc")?;
+        for tag in parse_tags(&desc) {
+            let stmt = Query::insert()
+                .into_table(ReminderTag::Table)
+                .columns([
+                    ReminderTag::ChatId,
+                    ReminderTag::ReminderType,
+                    ReminderTag::ReminderId,
+                    ReminderTag::Name,
+                ])
+                .values_panic([
+                    chat_id.into(),
+                    reminder_type.into(),
+                    id.into(),
+                    tag.into(),
+                ])
+                .to_owned();
+            let stmt: Statement = db_backend.build(&stmt);
+            conn.execute(stmt).await?;
+        }
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderTag::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReminderTag::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderTag::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderTag::ReminderType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderTag::ReminderId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReminderTag::Name).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_tag_chat_id_name")
+                    .table(ReminderTag::Table)
+                    .col(ReminderTag::ChatId)
+                    .col(ReminderTag::Name)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_tag_reminder_type_reminder_id")
+                    .table(ReminderTag::Table)
+                    .col(ReminderTag::ReminderType)
+                    .col(ReminderTag::ReminderId)
+                    .to_owned(),
+            )
+            .await?;
+        fill_tags(manager, Reminder::Table, "rem").await?;
+        fill_tags(manager, CronReminder::Table, "cron_rem").await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReminderTag::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReminderTag {
+    Table,
+    Id,
+    ChatId,
+    ReminderType,
+    ReminderId,
+    Name,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Reminder {
+    Table,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum CronReminder {
+    Table,
+}
@@ -0,0 +1,177 @@
+use crate::parsers::normalize_search_text;
+use sea_orm::{ConnectionTrait, DbBackend};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Full-text index kept in sync with `reminder_search` by triggers,
+/// created only if SQLite is built with FTS5
+const SQLITE_FTS_STATEMENTS: [&str; 4] = [
+    concat!(
+        "CREATE VIRTUAL TABLE reminder_search_fts USING fts5(",
+        "content, content='reminder_search', content_rowid='id')"
+    ),
+    concat!(
+        "CREATE TRIGGER reminder_search_ai AFTER INSERT ON reminder_search ",
+        "BEGIN INSERT INTO reminder_search_fts(rowid, content) ",
+        "VALUES (new.id, new.content); END"
+    ),
+    concat!(
+        "CREATE TRIGGER reminder_search_ad AFTER DELETE ON reminder_search ",
+        "BEGIN INSERT INTO reminder_search_fts(reminder_search_fts, rowid, ",
+        "content) VALUES ('delete', old.id, old.content); END"
+    ),
+    concat!(
+        "CREATE TRIGGER reminder_search_au AFTER UPDATE ON reminder_search ",
+        "BEGIN INSERT INTO reminder_search_fts(reminder_search_fts, rowid, ",
+        "content) VALUES ('delete', old.id, old.content); ",
+        "INSERT INTO reminder_search_fts(rowid, content) ",
+        "VALUES (new.id, new.content); END"
+    ),
+];
+
+/// Index the already existing reminders of the table
+async fn fill_search<T: Iden + Copy + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+    reminder_type: &str,
+) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let db_backend = manager.get_database_backend();
+    let stmt = Query::select()
+        .columns([Alias::new("id"), Alias::new("chat_id"), Alias::new("desc")])
+        .from(table)
+        .to_owned();
+    for row in conn.query_all(db_backend.build(&stmt)).await? {
+        let id: i64 = row.try_get("", "id")?;
+        let chat_id: i64 = row.try_get("", "chat_id")?;
+        let desc: String = row.try_get("", "desc")?;
+        let stmt = Query::insert()
+            .into_table(ReminderSearch::Table)
+            .columns([
+                ReminderSearch::ChatId,
+                ReminderSearch::ReminderType,
+                ReminderSearch::ReminderId,
+                ReminderSearch::Content,
+            ])
+            .values_panic([
+                chat_id.into(),
+                reminder_type.into(),
+                id.into(),
+                normalize_search_text(&desc).into(),
+            ])
+            .to_owned();
+        conn.execute(db_backend.build(&stmt)).await?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderSearch::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReminderSearch::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSearch::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSearch::ReminderType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSearch::ReminderId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReminderSearch::Content)
+                            .text()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_search_chat_id")
+                    .table(ReminderSearch::Table)
+                    .col(ReminderSearch::ChatId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_reminder_search_reminder_type_reminder_id")
+                    .table(ReminderSearch::Table)
+                    .col(ReminderSearch::ReminderType)
+                    .col(ReminderSearch::ReminderId)
+                    .to_owned(),
+            )
+            .await?;
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            let conn = manager.get_connection();
+            // Without FTS5 the search falls back to substring matching
+            if conn
+                .execute_unprepared(SQLITE_FTS_STATEMENTS[0])
+                .await
+                .is_ok()
+            {
+                for stmt in &SQLITE_FTS_STATEMENTS[1..] {
+                    conn.execute_unprepared(stmt).await?;
+                }
+            }
+        }
+        fill_search(manager, Reminder::Table, "rem").await?;
+        fill_search(manager, CronReminder::Table, "cron_rem").await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            manager
+                .get_connection()
+                .execute_unprepared("DROP TABLE IF EXISTS reminder_search_fts")
+                .await?;
+        }
+        manager
+            .drop_table(Table::drop().table(ReminderSearch::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReminderSearch {
+    Table,
+    Id,
+    ChatId,
+    ReminderType,
+    ReminderId,
+    Content,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum Reminder {
+    Table,
+}
+
+#[derive(Iden, Clone, Copy)]
+pub enum CronReminder {
+    Table,
+}
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChatDigest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChatDigest::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ChatDigest::ChatId)
+                            .big_integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ChatDigest::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ChatDigest::Time).time().not_null())
+                    .col(
+                        ColumnDef::new(ChatDigest::IncludeEmpty)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChatDigest::NextTime)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_chat_digest_next_time")
+                    .table(ChatDigest::Table)
+                    .col(ChatDigest::NextTime)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChatDigest::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ChatDigest {
+    Table,
+    Id,
+    ChatId,
+    UserId,
+    Time,
+    IncludeEmpty,
+    NextTime,
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserQuietHours::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserQuietHours::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(UserQuietHours::UserId)
+                            .big_integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserQuietHours::StartTime)
+                            .time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserQuietHours::EndTime)
+                            .time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserQuietHours::Silent)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserQuietHours::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum UserQuietHours {
+    Table,
+    Id,
+    UserId,
+    StartTime,
+    EndTime,
+    Silent,
+}
@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserSettings::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(UserSettings::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserSettings::Key).text().not_null())
+                    .col(ColumnDef::new(UserSettings::Value).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_user_settings_user_id_key")
+                    .table(UserSettings::Table)
+                    .col(UserSettings::UserId)
+                    .col(UserSettings::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Move the timezones into the settings
+        let db_backend = manager.get_database_backend();
+        let stmt = Query::insert()
+            .into_table(UserSettings::Table)
+            .columns([
+                UserSettings::UserId,
+                UserSettings::Key,
+                UserSettings::Value,
+            ])
+            .select_from(
+                Query::select()
+                    .column(UserTimezone::UserId)
+                    .expr(Expr::val("timezone"))
+                    .column(UserTimezone::Timezone)
+                    .from(UserTimezone::Table)
+                    .to_owned(),
+            )
+            .map_err(|err| DbErr::Migration(err.to_string()))?
+            .to_owned();
+        manager
+            .get_connection()
+            .execute(db_backend.build(&stmt))
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserTimezone::Table).to_owned())
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserTimezone::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserTimezone::UserId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTimezone::Timezone)
+                            .text()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        let db_backend = manager.get_database_backend();
+        let stmt = Query::insert()
+            .into_table(UserTimezone::Table)
+            .columns([UserTimezone::UserId, UserTimezone::Timezone])
+            .select_from(
+                Query::select()
+                    .column(UserSettings::UserId)
+                    .column(UserSettings::Value)
+                    .from(UserSettings::Table)
+                    .and_where(Expr::col(UserSettings::Key).eq("timezone"))
+                    .to_owned(),
+            )
+            .map_err(|err| DbErr::Migration(err.to_string()))?
+            .to_owned();
+        manager
+            .get_connection()
+            .execute(db_backend.build(&stmt))
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum UserSettings {
+    Table,
+    Id,
+    UserId,
+    Key,
+    Value,
+}
+
+#[derive(Iden)]
+pub enum UserTimezone {
+    Table,
+    UserId,
+    Timezone,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite alters one column per statement
+        for column in [Reminder::MediaType, Reminder::FileId] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Reminder::Table)
+                        .add_column(ColumnDef::new(column).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for column in [CronReminder::MediaType, CronReminder::FileId] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(CronReminder::Table)
+                        .add_column(ColumnDef::new(column).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Reminder::MediaType, Reminder::FileId] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Reminder::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for column in [CronReminder::MediaType, CronReminder::FileId] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(CronReminder::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Reminder {
+    Table,
+    MediaType,
+    FileId,
+}
+
+#[derive(Iden)]
+pub enum CronReminder {
+    Table,
+    MediaType,
+    FileId,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reminder::Table)
+                    .add_column(ColumnDef::new(Reminder::Recipients).text())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CronReminder::Table)
+                    .add_column(ColumnDef::new(CronReminder::Recipients).text())
+                    .to_owned(),
+            )
+            .await?;
+        // SQLite alters one column per statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReminderDelivery::Table)
+                    .add_column(
+                        ColumnDef::new(ReminderDelivery::UserId).big_integer(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReminderDelivery::Table)
+                    .add_column(
+                        ColumnDef::new(ReminderDelivery::Recipients).text(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reminder::Table)
+                    .drop_column(Reminder::Recipients)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CronReminder::Table)
+                    .drop_column(CronReminder::Recipients)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [ReminderDelivery::UserId, ReminderDelivery::Recipients] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ReminderDelivery::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Reminder {
+    Table,
+    Recipients,
+}
+
+#[derive(Iden)]
+pub enum CronReminder {
+    Table,
+    Recipients,
+}
+
+#[derive(Iden)]
+pub enum ReminderDelivery {
+    Table,
+    UserId,
+    Recipients,
+}
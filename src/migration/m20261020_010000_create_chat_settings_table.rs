@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChatSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChatSettings::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(ChatSettings::ChatId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ChatSettings::Key).text().not_null())
+                    .col(ColumnDef::new(ChatSettings::Value).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("ix_chat_settings_chat_id_key")
+                    .table(ChatSettings::Table)
+                    .col(ChatSettings::ChatId)
+                    .col(ChatSettings::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChatSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ChatSettings {
+    Table,
+    Id,
+    ChatId,
+    Key,
+    Value,
+}
@@ -0,0 +1,43 @@
+use sea_orm::{ConnectionTrait, DbBackend};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Full-text index of `reminder_search` on PostgreSQL, computed from the
+/// content so that it stays in sync without triggers
+const POSTGRES_TSV_STATEMENTS: [&str; 2] = [
+    concat!(
+        "ALTER TABLE reminder_search ADD COLUMN IF NOT EXISTS content_tsv ",
+        "tsvector GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED"
+    ),
+    concat!(
+        "CREATE INDEX IF NOT EXISTS ix_reminder_search_content_tsv ",
+        "ON reminder_search USING GIN (content_tsv)"
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let conn = manager.get_connection();
+            for stmt in POSTGRES_TSV_STATEMENTS {
+                conn.execute_unprepared(stmt).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "ALTER TABLE reminder_search DROP COLUMN content_tsv",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
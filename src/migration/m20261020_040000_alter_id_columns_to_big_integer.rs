@@ -0,0 +1,43 @@
+use sea_orm::{ConnectionTrait, DbBackend};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables created with 32-bit integer ids on PostgreSQL which Telegram
+/// chat and user ids overflow, SQLite integers are 64-bit anyway
+const TABLES: [&str; 2] = ["reminder", "cron_reminder"];
+
+async fn alter_id_columns(
+    manager: &SchemaManager<'_>,
+    column_type: &str,
+) -> Result<(), DbErr> {
+    if manager.get_database_backend() != DbBackend::Postgres {
+        return Ok(());
+    }
+    let conn = manager.get_connection();
+    for table in TABLES {
+        conn.execute_unprepared(&format!(
+            "ALTER TABLE {table} ALTER COLUMN id TYPE {column_type}, \
+             ALTER COLUMN chat_id TYPE {column_type}, \
+             ALTER COLUMN user_id TYPE {column_type}"
+        ))
+        .await?;
+        conn.execute_unprepared(&format!(
+            "ALTER SEQUENCE {table}_id_seq AS {column_type}"
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        alter_id_columns(manager, "bigint").await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        alter_id_columns(manager, "integer").await
+    }
+}
@@ -10,6 +10,27 @@ mod m20221130_233038_remove_sent_column;
 mod m20230224_061834_create_reminder_paused_columns;
 mod m20230301_070153_create_reminder_pattern_column;
 mod m20230526_143912_add_active_reminders_indexes;
+mod m20261018_101500_create_reminder_delivery_table;
+mod m20261018_143000_create_reminder_undo_table;
+mod m20261018_170000_create_reminder_selection_table;
+mod m20261018_200000_create_reminder_tag_table;
+mod m20261018_220000_create_reminder_search_table;
+mod m20261019_090000_create_chat_digest_table;
+mod m20261019_110000_create_urgent_columns;
+mod m20261019_113000_create_user_quiet_hours_table;
+mod m20261019_140000_create_user_settings_table;
+mod m20261019_160000_create_cron_reminder_timezone_column;
+mod m20261019_180000_create_edit_mode_columns;
+mod m20261019_200000_create_reply_to_message_id_columns;
+mod m20261019_220000_create_attachment_columns;
+mod m20261019_230000_create_entities_columns;
+mod m20261020_000000_create_recipients_columns;
+mod m20261020_010000_create_chat_settings_table;
+mod m20261020_020000_create_deleted_time_columns;
+mod m20261020_020100_recreate_reminder_undo_table;
+mod m20261020_030000_create_search_content_tsv_column;
+mod m20261020_040000_alter_id_columns_to_big_integer;
+mod m20261020_050000_create_reply_to_chat_id_columns;
 
 pub struct Migrator;
 
@@ -31,6 +52,43 @@ impl MigratorTrait for Migrator {
                 m20230301_070153_create_reminder_pattern_column::Migration,
             ),
             Box::new(m20230526_143912_add_active_reminders_indexes::Migration),
+            Box::new(
+                m20261018_101500_create_reminder_delivery_table::Migration,
+            ),
+            Box::new(m20261018_143000_create_reminder_undo_table::Migration),
+            Box::new(
+                m20261018_170000_create_reminder_selection_table::Migration,
+            ),
+            Box::new(m20261018_200000_create_reminder_tag_table::Migration),
+            Box::new(m20261018_220000_create_reminder_search_table::Migration),
+            Box::new(m20261019_090000_create_chat_digest_table::Migration),
+            Box::new(m20261019_110000_create_urgent_columns::Migration),
+            Box::new(m20261019_113000_create_user_quiet_hours_table::Migration),
+            Box::new(m20261019_140000_create_user_settings_table::Migration),
+            Box::new(
+                m20261019_160000_create_cron_reminder_timezone_column::Migration,
+            ),
+            Box::new(m20261019_180000_create_edit_mode_columns::Migration),
+            Box::new(
+                m20261019_200000_create_reply_to_message_id_columns::Migration,
+            ),
+            Box::new(m20261019_220000_create_attachment_columns::Migration),
+            Box::new(m20261019_230000_create_entities_columns::Migration),
+            Box::new(m20261020_000000_create_recipients_columns::Migration),
+            Box::new(m20261020_010000_create_chat_settings_table::Migration),
+            Box::new(m20261020_020000_create_deleted_time_columns::Migration),
+            Box::new(
+                m20261020_020100_recreate_reminder_undo_table::Migration,
+            ),
+            Box::new(
+                m20261020_030000_create_search_content_tsv_column::Migration,
+            ),
+            Box::new(
+                m20261020_040000_alter_id_columns_to_big_integer::Migration,
+            ),
+            Box::new(
+                m20261020_050000_create_reply_to_chat_id_columns::Migration,
+            ),
         ]
     }
 }
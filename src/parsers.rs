@@ -1,5 +1,7 @@
 use crate::grammar;
-use crate::serializers::Pattern;
+use crate::i18n::Locale;
+use crate::serializers::{OverlapPolicy, Pattern};
+use crate::tz::{move_reminder, parse_timezone, parse_timezone_word};
 
 use crate::entity::{cron_reminder, reminder};
 use chrono::prelude::*;
@@ -7,7 +9,15 @@ use chrono::Utc;
 use chrono_tz::Tz;
 use cron_parser::parse as parse_cron;
 use sea_orm::ActiveValue::{NotSet, Set};
-use serde_json::to_string;
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, to_string};
+use std::str::FromStr;
+use teloxide::types::{MessageEntity, MessageEntityKind, User};
+use teloxide::utils::markdown::escape;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Longer hashtags are truncated
+const MAX_TAG_LEN: usize = 32;
 
 #[cfg(not(test))]
 pub fn now_time() -> NaiveDateTime {
@@ -19,11 +29,14 @@ pub async fn parse_reminder(
     chat_id: i64,
     user_id: u64,
     user_timezone: Tz,
+    overlap: OverlapPolicy,
+    locale: Locale,
 ) -> Option<reminder::ActiveModel> {
-    let rem = grammar::parse_reminder(s).ok()?;
+    let rem = grammar::parse_reminder(s, locale).ok()?;
     let description = rem.description.map(|x| x.0).unwrap_or("".to_owned());
     let mut pattern =
         Pattern::from_with_tz(rem.pattern?, user_timezone).ok()?;
+    pattern.set_overlap(overlap);
     let time = pattern.next(now_time())?;
     // Convert to UTC
     Some(reminder::ActiveModel {
@@ -35,6 +48,15 @@ pub async fn parse_reminder(
         edit: Set(false),
         paused: Set(false),
         pattern: Set(to_string(&pattern).ok()),
+        urgent: Set(rem.urgent),
+        edit_mode: Set(None),
+        reply_to_message_id: Set(None),
+        reply_to_chat_id: Set(None),
+        media_type: Set(None),
+        file_id: Set(None),
+        entities: Set(None),
+        recipients: Set(None),
+        deleted_time: Set(None),
     })
 }
 
@@ -44,30 +66,550 @@ pub async fn parse_cron_reminder(
     user_id: u64,
     user_timezone: Tz,
 ) -> Option<cron_reminder::ActiveModel> {
+    // Reminders marked with "!" ring even during quiet hours
+    let (urgent, text) = match text.trim_start().strip_prefix('!') {
+        Some(text) => (true, text.trim_start()),
+        None => (false, text),
+    };
+    let (cron_expr, timezone, desc) = split_cron_expr(text)?;
+    parse_cron(
+        &cron_expr,
+        &Utc::now().with_timezone(&timezone.unwrap_or(user_timezone)),
+    )
+    .map(|time| cron_reminder::ActiveModel {
+        id: NotSet,
+        chat_id: Set(chat_id),
+        user_id: Set(Some(user_id as i64)),
+        cron_expr: Set(cron_expr.clone()),
+        time: Set(time.with_timezone(&Utc).naive_utc()),
+        desc: Set(desc.to_owned()),
+        edit: Set(false),
+        paused: Set(false),
+        urgent: Set(urgent),
+        timezone: Set(timezone.map(|tz| tz.name().to_owned())),
+        edit_mode: Set(None),
+        reply_to_message_id: Set(None),
+        reply_to_chat_id: Set(None),
+        media_type: Set(None),
+        file_id: Set(None),
+        entities: Set(None),
+        recipients: Set(None),
+        deleted_time: Set(None),
+    })
+    .ok()
+}
+
+/// Split the text into a cron expression, the timezone following it
+/// if there is one and the description
+fn split_cron_expr(text: &str) -> Option<(String, Option<Tz>, &str)> {
     let cron_fields: Vec<&str> = text.split_whitespace().take(5).collect();
     if cron_fields.len() < 5 {
-        None
-    } else {
-        let cron_expr = cron_fields.join(" ");
-        parse_cron(&cron_expr, &Utc::now().with_timezone(&user_timezone))
-            .map(|time| cron_reminder::ActiveModel {
-                id: NotSet,
-                chat_id: Set(chat_id),
-                user_id: Set(Some(user_id as i64)),
-                cron_expr: Set(cron_expr.clone()),
-                time: Set(time.with_timezone(&Utc).naive_utc()),
-                desc: Set(text
-                    .strip_prefix(&(cron_expr.to_owned()))
-                    .unwrap_or("")
-                    .trim()
-                    .to_owned()),
-                edit: Set(false),
-                paused: Set(false),
+        return None;
+    }
+    let cron_expr = cron_fields.join(" ");
+    let desc = text.strip_prefix(&cron_expr).unwrap_or("").trim();
+    // A timezone can follow the cron expression
+    match desc
+        .split_whitespace()
+        .next()
+        .and_then(|word| Some((word, parse_timezone_word(word)?)))
+    {
+        Some((word, timezone)) => {
+            Some((cron_expr, Some(timezone), desc[word.len()..].trim_start()))
+        }
+        None => Some((cron_expr, None, desc)),
+    }
+}
+
+/// Increasing times of a cron expression's firings in the user's timezone
+pub struct CronOccurrences {
+    cron_expr: String,
+    user_timezone: Tz,
+    cur: NaiveDateTime,
+}
+
+/// Iterator over the times the cron expression fires after `cur`
+pub fn cron_occurrences(
+    cron_expr: &str,
+    user_timezone: Tz,
+    cur: NaiveDateTime,
+) -> CronOccurrences {
+    CronOccurrences {
+        cron_expr: cron_expr.to_owned(),
+        user_timezone,
+        cur,
+    }
+}
+
+impl Iterator for CronOccurrences {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<NaiveDateTime> {
+        let next_time = parse_cron(
+            &self.cron_expr,
+            &self.user_timezone.from_utc_datetime(&self.cur),
+        )
+        .ok()?
+        .with_timezone(&Utc)
+        .naive_utc();
+        if next_time > self.cur {
+            self.cur = next_time;
+            Some(next_time)
+        } else {
+            None
+        }
+    }
+}
+
+/// Extract hashtags (`#work`) from the reminder's description,
+/// normalized to lowercase and without duplicates
+pub fn parse_tags(desc: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+    for word in desc.split_whitespace() {
+        if let Some(tag) = word.strip_prefix('#') {
+            let tag = tag
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .take(MAX_TAG_LEN)
+                .flat_map(char::to_lowercase)
+                .collect::<String>();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
+/// Short key of the tag to refer to it in callback data, which is limited
+/// to 64 bytes (a tag of non-latin letters takes twice as many bytes)
+pub fn tag_key(tag: &str) -> String {
+    // 32-bit FNV-1a hash
+    let hash = tag.bytes().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    });
+    format!("{:08x}", hash)
+}
+
+/// Parse a tag given as a command argument with or without the leading `#`
+pub fn parse_tag_filter(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    match arg.split_whitespace().count() {
+        1 => parse_tags(&format!("#{}", arg.trim_start_matches('#')))
+            .into_iter()
+            .next(),
+        _ => None,
+    }
+}
+
+/// Lowercase the text and strip accents off its letters
+/// to match it regardless of case and diacritics
+pub fn normalize_search_text(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Split a search query into normalized words
+pub fn parse_search_terms(query: &str) -> Vec<String> {
+    normalize_search_text(query)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Parse the digest's local time and whether to send it on days
+/// with nothing scheduled (`08:00 always`)
+pub fn parse_digest_args(arg: &str) -> Option<(NaiveTime, bool)> {
+    let mut words = arg.split_whitespace();
+    let time = NaiveTime::parse_from_str(words.next()?, "%H:%M").ok()?;
+    let include_empty = match words.next() {
+        Some(word) if word.eq_ignore_ascii_case("always") => true,
+        Some(_) => return None,
+        None => false,
+    };
+    match words.next() {
+        Some(_) => None,
+        None => Some((time, include_empty)),
+    }
+}
+
+/// Parse the quiet hours' local window and whether reminders are sent
+/// silently instead of being deferred (`23:00-07:30 silent`)
+pub fn parse_quiet_hours_args(
+    arg: &str,
+) -> Option<(NaiveTime, NaiveTime, bool)> {
+    let mut words = arg.split_whitespace();
+    let (start, end) = words.next()?.split_once('-')?;
+    let start = NaiveTime::parse_from_str(start, "%H:%M").ok()?;
+    let end = NaiveTime::parse_from_str(end, "%H:%M").ok()?;
+    if start == end {
+        return None;
+    }
+    let silent = match words.next() {
+        Some(word) if word.eq_ignore_ascii_case("silent") => true,
+        Some(word) if word.eq_ignore_ascii_case("defer") => false,
+        Some(_) => return None,
+        None => false,
+    };
+    match words.next() {
+        Some(_) => None,
+        None => Some((start, end, silent)),
+    }
+}
+
+/// Parse a reference to a reminder as shown by `/next`:
+/// `12` for a reminder and `c12` for a cron reminder
+pub fn parse_reminder_ref(arg: &str) -> Option<(&'static str, i64)> {
+    let arg = arg.trim();
+    match arg.strip_prefix('c') {
+        Some(id) => id.parse().ok().map(|id| ("cron_rem", id)),
+        None => arg.parse().ok().map(|id| ("rem", id)),
+    }
+}
+
+/// Reference to a reminder accepted by [`parse_reminder_ref`]
+pub fn format_reminder_ref(reminder_type: &str, id: i64) -> String {
+    match reminder_type {
+        "cron_rem" => format!("c{}", id),
+        _ => id.to_string(),
+    }
+}
+
+/// Message a reminder is set in reply to, the reminder is delivered
+/// with a copy of it
+pub struct ReplyTo<'a> {
+    /// Chat the message is copied from, the reminder replies
+    /// to the message in its own chat if there is none
+    pub chat_id: Option<i64>,
+    pub message_id: i32,
+    /// Text or caption of the message
+    pub text: Option<&'a str>,
+}
+
+impl ReplyTo<'_> {
+    /// Description of the reminder, the first line of the message's text
+    /// if the reminder has none
+    pub fn describe(&self, desc: &str) -> String {
+        match self.text.and_then(|text| text.lines().next()) {
+            Some(line) if desc.is_empty() => line.trim().to_owned(),
+            _ => desc.to_owned(),
+        }
+    }
+}
+
+/// Message forwarded to the bot without a reminder in its caption,
+/// the reminder sent next is set about it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForwardedMessage {
+    pub message_id: i32,
+    /// First line of the message's text or caption
+    pub text: String,
+}
+
+impl ForwardedMessage {
+    pub fn reply_to(&self, chat_id: i64) -> ReplyTo<'_> {
+        ReplyTo {
+            chat_id: Some(chat_id),
+            message_id: self.message_id,
+            text: Some(&self.text),
+        }
+    }
+}
+
+impl FromStr for ForwardedMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (message_id, text) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid forwarded message: {}", s))?;
+        Ok(Self {
+            message_id: message_id.parse().map_err(|_| {
+                format!("invalid forwarded message id: {}", message_id)
+            })?,
+            text: text.to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for ForwardedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.message_id, self.text)
+    }
+}
+
+/// Group member a reminder is assigned to, recipients are mentioned
+/// when the reminder is delivered and can snooze it or mark it done
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recipient {
+    /// Member mentioned by the username without the "@"
+    Username(String),
+    /// Member without a username mentioned by the name
+    User { id: u64, name: String },
+}
+
+impl Recipient {
+    fn from_entity(kind: &MessageEntityKind, text: &str) -> Option<Self> {
+        match kind {
+            MessageEntityKind::Mention => {
+                Some(Self::Username(text.strip_prefix('@')?.to_owned()))
+            }
+            MessageEntityKind::TextMention { user } => Some(Self::User {
+                id: user.id.0,
+                name: text.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Mention of the recipient in MarkdownV2
+    pub fn mention(&self) -> String {
+        match self {
+            Self::Username(username) => escape(&format!("@{}", username)),
+            Self::User { id, name } => {
+                format!("[{}](tg://user?id={})", escape(name), id)
+            }
+        }
+    }
+
+    pub fn is(&self, user: &User) -> bool {
+        match self {
+            Self::Username(username) => user
+                .username
+                .as_deref()
+                .is_some_and(|x| x.eq_ignore_ascii_case(username)),
+            Self::User { id, .. } => user.id.0 == *id,
+        }
+    }
+
+    /// Recipients stored in a reminder's column
+    pub fn from_column(recipients: Option<&str>) -> Vec<Self> {
+        recipients
+            .and_then(|recipients| from_str(recipients).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn to_column(recipients: &[Self]) -> Option<String> {
+        match recipients {
+            [] => None,
+            recipients => to_string(recipients).ok(),
+        }
+    }
+}
+
+/// Members mentioned at the start of the text and the rest of the text
+pub fn split_recipients<'a>(
+    text: &'a str,
+    entities: &[MessageEntity],
+) -> (Vec<Recipient>, &'a str) {
+    let mut recipients = vec![];
+    let mut rest = text.trim_start();
+    loop {
+        let offset = text[..text.len() - rest.len()].encode_utf16().count();
+        let recipient = entities
+            .iter()
+            .filter(|entity| entity.offset == offset)
+            .find_map(|entity| {
+                let mention = utf16_prefix(rest, entity.length)?;
+                Some((Recipient::from_entity(&entity.kind, mention)?, mention))
+            });
+        match recipient {
+            Some((recipient, mention)) => {
+                recipients.push(recipient);
+                rest = rest[mention.len()..].trim_start();
+            }
+            None => return (recipients, rest),
+        }
+    }
+}
+
+/// Beginning of the text of the length in UTF-16 code units
+fn utf16_prefix(text: &str, length: usize) -> Option<&str> {
+    let mut len = 0;
+    for (i, c) in text.char_indices() {
+        if len == length {
+            return Some(&text[..i]);
+        }
+        len += c.len_utf16();
+    }
+    (len == length).then_some(text)
+}
+
+/// Entities of the text clipped to the part ending it with offsets
+/// in the part, none if the text doesn't end with it
+pub fn suffix_entities(
+    text: &str,
+    part: &str,
+    entities: &[MessageEntity],
+) -> Vec<MessageEntity> {
+    let (text, part) = (text.trim_end(), part.trim_end());
+    if part.is_empty() || !text.ends_with(part) {
+        return vec![];
+    }
+    let end = text.encode_utf16().count();
+    let start = end - part.encode_utf16().count();
+    entities
+        .iter()
+        .filter_map(|entity| {
+            let from = entity.offset.max(start);
+            let until = (entity.offset + entity.length).min(end);
+            (from < until).then(|| MessageEntity {
+                kind: entity.kind.clone(),
+                offset: from - start,
+                length: until - from,
             })
-            .ok()
+        })
+        .collect()
+}
+
+/// Serialized formatting entities of the description ending the text
+pub fn desc_entities(
+    text: &str,
+    desc: &str,
+    entities: &[MessageEntity],
+) -> Option<String> {
+    match suffix_entities(text, desc, entities) {
+        entities if entities.is_empty() => None,
+        entities => to_string(&entities).ok(),
     }
 }
 
+/// Part of a reminder replaced by an edit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditMode {
+    /// Description
+    Text,
+    /// Time pattern or cron expression
+    Pattern,
+    /// Timezone of the pattern or cron expression
+    Timezone,
+}
+
+impl EditMode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Pattern => "pattern",
+            Self::Timezone => "timezone",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        [Self::Text, Self::Pattern, Self::Timezone]
+            .into_iter()
+            .find(|edit_mode| edit_mode.code() == code)
+    }
+}
+
+/// Reminder with the part replaced by the text: a description,
+/// a pattern without a description or a timezone
+pub fn parse_reminder_edit(
+    rem: reminder::Model,
+    edit_mode: EditMode,
+    s: &str,
+    entities: &[MessageEntity],
+    user_timezone: Tz,
+    locale: Locale,
+) -> Option<reminder::ActiveModel> {
+    let (timezone, overlap) = match rem.pattern {
+        Some(ref pattern) => {
+            let pattern = from_str::<Pattern>(pattern).ok()?;
+            (pattern.timezone(), pattern.overlap())
+        }
+        None => (user_timezone, OverlapPolicy::default()),
+    };
+    let rem = match edit_mode {
+        EditMode::Text => {
+            let desc = parse_edited_text(s)?;
+            reminder::ActiveModel {
+                entities: Set(desc_entities(s, &desc, entities)),
+                desc: Set(desc),
+                ..rem.into()
+            }
+        }
+        EditMode::Pattern => {
+            // The pattern stays in its timezone unless another one is given
+            let mut pattern = Pattern::from_with_tz(
+                grammar::parse_pattern(s, locale).ok()?,
+                timezone,
+            )
+            .ok()?;
+            pattern.set_overlap(overlap);
+            reminder::ActiveModel {
+                time: Set(pattern.next(now_time())?),
+                pattern: Set(to_string(&pattern).ok()),
+                ..rem.into()
+            }
+        }
+        EditMode::Timezone => {
+            move_reminder(rem, timezone, parse_timezone(s.trim())?, now_time())?
+        }
+    };
+    Some(reminder::ActiveModel {
+        edit: Set(false),
+        edit_mode: Set(None),
+        ..rem
+    })
+}
+
+/// Cron reminder with the part replaced by the text: a description,
+/// a cron expression without a description or a timezone
+pub fn parse_cron_reminder_edit(
+    cron_rem: cron_reminder::Model,
+    edit_mode: EditMode,
+    s: &str,
+    entities: &[MessageEntity],
+    user_timezone: Tz,
+) -> Option<cron_reminder::ActiveModel> {
+    let timezone = cron_rem.timezone.as_deref().and_then(parse_timezone);
+    let (cron_expr, timezone) = match edit_mode {
+        EditMode::Text => {
+            let desc = parse_edited_text(s)?;
+            return Some(cron_reminder::ActiveModel {
+                entities: Set(desc_entities(s, &desc, entities)),
+                desc: Set(desc),
+                edit: Set(false),
+                edit_mode: Set(None),
+                ..cron_rem.into()
+            });
+        }
+        EditMode::Pattern => match split_cron_expr(s)? {
+            (cron_expr, new_timezone, "") => {
+                (cron_expr, new_timezone.or(timezone))
+            }
+            _ => return None,
+        },
+        EditMode::Timezone => {
+            (cron_rem.cron_expr.clone(), Some(parse_timezone(s.trim())?))
+        }
+    };
+    let time = cron_occurrences(
+        &cron_expr,
+        timezone.unwrap_or(user_timezone),
+        now_time(),
+    )
+    .next()?;
+    Some(cron_reminder::ActiveModel {
+        cron_expr: Set(cron_expr),
+        time: Set(time),
+        timezone: Set(timezone.map(|tz| tz.name().to_owned())),
+        edit: Set(false),
+        edit_mode: Set(None),
+        ..cron_rem.into()
+    })
+}
+
+/// New description of a reminder, it can't be empty
+fn parse_edited_text(s: &str) -> Option<String> {
+    Some(s.trim())
+        .filter(|s| !s.is_empty())
+        .map(ToOwned::to_owned)
+}
+
 #[cfg(test)]
 pub fn now_time() -> NaiveDateTime {
     unsafe {
@@ -78,6 +620,8 @@ pub fn now_time() -> NaiveDateTime {
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use crate::generic_reminder::GenericReminder;
+    use sea_orm::TryIntoModel;
     use test_case::test_case;
     extern crate strfmt;
     use std::collections::HashMap;
@@ -123,15 +667,21 @@ pub mod test {
         unsafe {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
-        let result =
-            parse_reminder(&strfmt(fmt_str, &vars).unwrap(), 0, 0u64, *TEST_TZ)
-                .await
-                .map(|reminder| {
-                    (
-                        TEST_TZ.from_utc_datetime(&reminder.time.unwrap()),
-                        reminder.desc.unwrap(),
-                    )
-                });
+        let result = parse_reminder(
+            &strfmt(fmt_str, &vars).unwrap(),
+            0,
+            0u64,
+            *TEST_TZ,
+            OverlapPolicy::Once,
+            Locale::En,
+        )
+        .await
+        .map(|reminder| {
+            (
+                TEST_TZ.from_utc_datetime(&reminder.time.unwrap()),
+                reminder.desc.unwrap(),
+            )
+        });
         match result {
             Some((time, desc)) => {
                 assert_eq!(desc, TEST_DESCRIPTION.to_owned());
@@ -147,4 +697,406 @@ pub mod test {
             None => None,
         }
     }
+
+    #[test_case("buy milk" => Vec::<String>::new() ; "no tags" )]
+    #[test_case("call mom #Family" => vec!["family".to_owned()] ; "lowercase" )]
+    #[test_case("#work report #work #urgent!" => vec!["work".to_owned(), "urgent".to_owned()] ; "duplicates and punctuation" )]
+    #[test_case("issue#12 and # alone" => Vec::<String>::new() ; "not a hashtag" )]
+    #[test_case("позвонить #Семья" => vec!["семья".to_owned()] ; "unicode" )]
+    fn test_parse_tags(desc: &str) -> Vec<String> {
+        parse_tags(desc)
+    }
+
+    #[test_case("!10:00 wake up" => Some((true, "wake up".to_owned())) ; "urgent" )]
+    #[test_case("! /fri,mon 11:00 wake up" => Some((true, "wake up".to_owned())) ; "urgent recurrence" )]
+    #[test_case("10:00 wake up!" => Some((false, "wake up!".to_owned())) ; "exclamation in description" )]
+    #[tokio::test]
+    async fn test_parse_urgent_reminder(s: &str) -> Option<(bool, String)> {
+        parse_reminder(s, 0, 0u64, *TEST_TZ, OverlapPolicy::Once, Locale::En)
+            .await
+            .map(|reminder| (reminder.urgent.unwrap(), reminder.desc.unwrap()))
+    }
+
+    #[test_case("!0 7 * * * wake up" => Some((true, "wake up".to_owned())) ; "urgent" )]
+    #[test_case("0 7 * * * wake up" => Some((false, "wake up".to_owned())) ; "not urgent" )]
+    #[tokio::test]
+    async fn test_parse_urgent_cron_reminder(
+        s: &str,
+    ) -> Option<(bool, String)> {
+        parse_cron_reminder(s, 0, 0u64, *TEST_TZ)
+            .await
+            .map(|reminder| (reminder.urgent.unwrap(), reminder.desc.unwrap()))
+    }
+
+    #[test_case("15:00 Europe/Berlin sync with Hans" => Some((Time(2007, 2, 2, 17, 0, 0), "sync with Hans".to_owned())) ; "iana name" )]
+    #[test_case("15:00 tz:CET sync" => Some((Time(2007, 2, 2, 17, 0, 0), "sync".to_owned())) ; "marked abbreviation" )]
+    #[test_case("15:00 CET sync" => Some((Time(2007, 2, 2, 15, 0, 0), "CET sync".to_owned())) ; "abbreviation without marker" )]
+    #[test_case("9:00 CT scan" => Some((Time(2007, 2, 3, 9, 0, 0), "CT scan".to_owned())) ; "ct scan" )]
+    #[test_case("10:00 PT appointment" => Some((Time(2007, 2, 3, 10, 0, 0), "PT appointment".to_owned())) ; "pt appointment" )]
+    #[test_case("every fri 9:00 tz:PST standup" => Some((Time(2007, 2, 2, 20, 0, 0), "standup".to_owned())) ; "recurrence" )]
+    #[test_case("13:00 UTC+3" => Some((Time(2007, 2, 2, 13, 0, 0), "".to_owned())) ; "offset without description" )]
+    #[test_case("15:00 Call  mom" => Some((Time(2007, 2, 2, 15, 0, 0), "Call  mom".to_owned())) ; "not a timezone" )]
+    #[test_case("15:00 Call, mom" => Some((Time(2007, 2, 2, 15, 0, 0), "Call, mom".to_owned())) ; "not a word" )]
+    #[tokio::test]
+    async fn test_parse_reminder_timezone(s: &str) -> Option<(Time, String)> {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        parse_reminder(s, 0, 0u64, *TEST_TZ, OverlapPolicy::Once, Locale::En)
+            .await
+            .map(|reminder| {
+                let time = TEST_TZ.from_utc_datetime(&reminder.time.unwrap());
+                (
+                    Time(
+                        time.year(),
+                        time.month(),
+                        time.day(),
+                        time.hour(),
+                        time.minute(),
+                        time.second(),
+                    ),
+                    reminder.desc.unwrap(),
+                )
+            })
+    }
+
+    #[test_case("0 15 * * * Europe/Berlin sync" => Some((Some("Europe/Berlin".to_owned()), "sync".to_owned())) ; "iana name" )]
+    #[test_case("0 15 * * * tz:EST" => Some((Some("America/New_York".to_owned()), "".to_owned())) ; "marked abbreviation" )]
+    #[test_case("0 9 * * * CT scan" => Some((None, "CT scan".to_owned())) ; "ct scan" )]
+    #[test_case("0 10 * * * PT appointment" => Some((None, "PT appointment".to_owned())) ; "pt appointment" )]
+    #[test_case("0 15 * * * Sync" => Some((None, "Sync".to_owned())) ; "no timezone" )]
+    #[tokio::test]
+    async fn test_parse_cron_reminder_timezone(
+        s: &str,
+    ) -> Option<(Option<String>, String)> {
+        parse_cron_reminder(s, 0, 0u64, *TEST_TZ)
+            .await
+            .map(|reminder| {
+                (reminder.timezone.unwrap(), reminder.desc.unwrap())
+            })
+    }
+
+    #[test_case("#work" => Some("work".to_owned()) ; "with hash" )]
+    #[test_case(" Work " => Some("work".to_owned()) ; "without hash" )]
+    #[test_case("" => None ; "empty" )]
+    #[test_case("#work #home" => None ; "several tags" )]
+    fn test_parse_tag_filter(arg: &str) -> Option<String> {
+        parse_tag_filter(arg)
+    }
+
+    #[test_case("work" => "5b98d260" ; "latin" )]
+    #[test_case(&"праздник".repeat(4) => "4fd98ca5" ; "long cyrillic" )]
+    fn test_tag_key(tag: &str) -> String {
+        tag_key(tag)
+    }
+
+    #[test_case("Dentist" => "dentist" ; "lowercase" )]
+    #[test_case("Café Crème" => "cafe creme" ; "accents" )]
+    #[test_case("Ёлка #Праздник" => "елка #праздник" ; "cyrillic" )]
+    fn test_normalize_search_text(text: &str) -> String {
+        normalize_search_text(text)
+    }
+
+    #[test_case("  the Dentist, #health " => vec!["the".to_owned(), "dentist".to_owned(), "health".to_owned()] ; "punctuation" )]
+    #[test_case("***" => Vec::<String>::new() ; "no words" )]
+    fn test_parse_search_terms(query: &str) -> Vec<String> {
+        parse_search_terms(query)
+    }
+
+    #[test_case("08:00" => Some((NaiveTime::from_hms_opt(8, 0, 0).unwrap(), false)) ; "time" )]
+    #[test_case("7:30 Always" => Some((NaiveTime::from_hms_opt(7, 30, 0).unwrap(), true)) ; "always" )]
+    #[test_case("25:00" => None ; "invalid time" )]
+    #[test_case("08:00 never" => None ; "unknown option" )]
+    #[test_case("08:00 always now" => None ; "extra words" )]
+    fn test_parse_digest_args(arg: &str) -> Option<(NaiveTime, bool)> {
+        parse_digest_args(arg)
+    }
+
+    #[test_case("! /mon 10:00 Europe/Berlin call" => "! —/Mon 10:00 Europe/Berlin call" ; "recurrence in timezone" )]
+    #[test_case("every 2w 10:00 water plants" => "02.02.2007—/2w 10:00 water plants" ; "interval" )]
+    #[test_case("15.03 9:00-18:00/2h stretch" => "15.03.2007 09:00—18:00/2h stretch" ; "time range" )]
+    #[test_case("1h30m tea" => "02.02.2007 14:00:30 tea" ; "countdown" )]
+    #[tokio::test]
+    async fn test_reminder_to_text(s: &str) -> String {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let text = parse_reminder(
+            s,
+            0,
+            0u64,
+            *TEST_TZ,
+            OverlapPolicy::Once,
+            Locale::En,
+        )
+        .await
+        .unwrap()
+        .to_text(*TEST_TZ);
+        let reparsed = parse_reminder(
+            &text,
+            0,
+            0u64,
+            *TEST_TZ,
+            OverlapPolicy::Once,
+            Locale::En,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reparsed.to_text(*TEST_TZ), text);
+        text
+    }
+
+    #[test_case("!0 9 * * 1 Europe/Berlin standup" => "! 0 9 * * 1 Europe/Berlin standup" ; "timezone" )]
+    #[test_case("0 7 * * * wake up" => "0 7 * * * wake up" ; "user timezone" )]
+    #[tokio::test]
+    async fn test_cron_reminder_to_text(s: &str) -> String {
+        parse_cron_reminder(s, 0, 0u64, *TEST_TZ)
+            .await
+            .unwrap()
+            .to_text(*TEST_TZ)
+    }
+
+    #[test_case(EditMode::Text, " call Hans " => Some("! —/Mon 10:00 Europe/Berlin call Hans".to_owned()) ; "text" )]
+    #[test_case(EditMode::Text, " " => None ; "empty text" )]
+    #[test_case(EditMode::Pattern, "every fri 18:00" => Some("! —/Fri 18:00 Europe/Berlin call".to_owned()) ; "pattern keeps timezone" )]
+    #[test_case(EditMode::Pattern, "every fri 18:00 UTC" => Some("! —/Fri 18:00 UTC call".to_owned()) ; "pattern with timezone" )]
+    #[test_case(EditMode::Pattern, "every fri 18:00 sync" => None ; "pattern with text" )]
+    #[test_case(EditMode::Timezone, "Asia/Tokyo" => Some("! —/Mon 10:00 Asia/Tokyo call".to_owned()) ; "timezone" )]
+    #[test_case(EditMode::Timezone, "Nowhere" => None ; "unknown timezone" )]
+    #[tokio::test]
+    async fn test_parse_reminder_edit(
+        edit_mode: EditMode,
+        s: &str,
+    ) -> Option<String> {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let rem = reminder::ActiveModel {
+            id: Set(1),
+            edit: Set(true),
+            edit_mode: Set(Some(edit_mode.code().to_owned())),
+            ..parse_reminder(
+                "! every mon 10:00 Europe/Berlin call",
+                0,
+                0u64,
+                *TEST_TZ,
+                OverlapPolicy::Once,
+                Locale::En,
+            )
+            .await
+            .unwrap()
+        }
+        .try_into_model()
+        .unwrap();
+        let rem =
+            parse_reminder_edit(rem, edit_mode, s, &[], *TEST_TZ, Locale::En)?;
+        assert_eq!(rem.edit, Set(false));
+        assert_eq!(rem.edit_mode, Set(None));
+        Some(rem.to_text(*TEST_TZ))
+    }
+
+    #[tokio::test]
+    async fn test_overlap_policy_kept_on_pattern_edit() {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let overlap = |rem: &reminder::ActiveModel| {
+            from_str::<Pattern>(&rem.pattern.clone().unwrap().unwrap())
+                .unwrap()
+                .overlap()
+        };
+        let rem = parse_reminder(
+            "every 1d 1:30 backup",
+            0,
+            0u64,
+            *TEST_TZ,
+            OverlapPolicy::Twice,
+            Locale::En,
+        )
+        .await
+        .unwrap();
+        assert_eq!(overlap(&rem), OverlapPolicy::Twice);
+        let rem = reminder::ActiveModel { id: Set(1), ..rem }
+            .try_into_model()
+            .unwrap();
+        let rem = parse_reminder_edit(
+            rem,
+            EditMode::Pattern,
+            "every 1d 2:30",
+            &[],
+            *TEST_TZ,
+            Locale::En,
+        )
+        .unwrap();
+        assert_eq!(overlap(&rem), OverlapPolicy::Twice);
+    }
+
+    #[test_case(EditMode::Text, "retro" => Some("0 9 * * 1 Europe/Berlin retro".to_owned()) ; "text" )]
+    #[test_case(EditMode::Pattern, "0 10 * * 2" => Some("0 10 * * 2 Europe/Berlin standup".to_owned()) ; "pattern keeps timezone" )]
+    #[test_case(EditMode::Pattern, "0 10 * * 2 retro" => None ; "pattern with text" )]
+    #[test_case(EditMode::Timezone, "UTC" => Some("0 9 * * 1 UTC standup".to_owned()) ; "timezone" )]
+    #[tokio::test]
+    async fn test_parse_cron_reminder_edit(
+        edit_mode: EditMode,
+        s: &str,
+    ) -> Option<String> {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let cron_rem = cron_reminder::ActiveModel {
+            id: Set(1),
+            edit: Set(true),
+            ..parse_cron_reminder(
+                "0 9 * * 1 Europe/Berlin standup",
+                0,
+                0u64,
+                *TEST_TZ,
+            )
+            .await
+            .unwrap()
+        }
+        .try_into_model()
+        .unwrap();
+        let cron_rem =
+            parse_cron_reminder_edit(cron_rem, edit_mode, s, &[], *TEST_TZ)?;
+        assert_eq!(cron_rem.edit, Set(false));
+        Some(cron_rem.to_text(*TEST_TZ))
+    }
+
+    #[test_case("", Some("Meeting notes\nsecond line") => "Meeting notes" ; "first line of text" )]
+    #[test_case("read", Some("Meeting notes") => "read" ; "description" )]
+    #[test_case("", None => "" ; "no text" )]
+    fn test_reply_to_describe(desc: &str, text: Option<&str>) -> String {
+        ReplyTo {
+            chat_id: None,
+            message_id: 1,
+            text,
+        }
+        .describe(desc)
+    }
+
+    #[test_case("12:Meeting notes" => Some((12, "Meeting notes")) ; "text" )]
+    #[test_case("12:10:00 call" => Some((12, "10:00 call")) ; "colon in text" )]
+    #[test_case("12:" => Some((12, "")) ; "no text" )]
+    #[test_case("twelve:notes" => None ; "invalid id" )]
+    #[test_case("12" => None ; "no separator" )]
+    fn test_forwarded_message_from_str(s: &str) -> Option<(i32, &str)> {
+        let forwarded: ForwardedMessage = s.parse().ok()?;
+        assert_eq!(forwarded.to_string(), s);
+        Some((forwarded.message_id, s.split_once(':')?.1))
+    }
+
+    #[test_case("10:00 buy milk", "buy milk", 10, 4 => Some((4, 4)) ; "inside" )]
+    #[test_case("10:00 buy milk ", "buy milk", 0, 14 => Some((0, 8)) ; "clipped" )]
+    #[test_case("10:00 buy milk", "buy milk", 0, 5 => None ; "pattern only" )]
+    #[test_case("10:00 🥛 milk", "🥛 milk", 9, 4 => Some((3, 4)) ; "utf16 offset" )]
+    #[test_case("10:00 buy milk", "Meeting notes", 10, 4 => None ; "description from elsewhere" )]
+    fn test_desc_entities(
+        text: &str,
+        desc: &str,
+        offset: usize,
+        length: usize,
+    ) -> Option<(usize, usize)> {
+        let entity = MessageEntity {
+            kind: teloxide::types::MessageEntityKind::Italic,
+            offset,
+            length,
+        };
+        let entities: Vec<MessageEntity> =
+            from_str(&desc_entities(text, desc, &[entity])?).unwrap();
+        Some((entities[0].offset, entities[0].length))
+    }
+
+    #[test]
+    fn test_split_recipients() {
+        let text = "@alice  Bob fri 16:00 submit timesheets to @carol";
+        let mention = |kind, offset, length| MessageEntity {
+            kind,
+            offset,
+            length,
+        };
+        let user = User {
+            id: teloxide::types::UserId(42),
+            is_bot: false,
+            first_name: "Bob".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            is_premium: false,
+            added_to_attachment_menu: false,
+        };
+        let entities = [
+            mention(MessageEntityKind::Mention, 0, 6),
+            mention(MessageEntityKind::TextMention { user }, 8, 3),
+            mention(MessageEntityKind::Mention, 43, 6),
+        ];
+        let (recipients, rest) = split_recipients(text, &entities);
+        assert_eq!(
+            recipients,
+            vec![
+                Recipient::Username("alice".to_owned()),
+                Recipient::User {
+                    id: 42,
+                    name: "Bob".to_owned()
+                }
+            ]
+        );
+        assert_eq!(rest, "fri 16:00 submit timesheets to @carol");
+        assert_eq!(split_recipients(rest, &[]), (vec![], rest));
+        assert_eq!(
+            Recipient::from_column(
+                Recipient::to_column(&recipients).as_deref()
+            ),
+            recipients
+        );
+        assert_eq!(
+            recipients
+                .iter()
+                .map(Recipient::mention)
+                .collect::<Vec<_>>(),
+            vec!["@alice", "[Bob](tg://user?id=42)"]
+        );
+    }
+
+    #[test_case("23:00-07:30" => Some((NaiveTime::from_hms_opt(23, 0, 0).unwrap(), NaiveTime::from_hms_opt(7, 30, 0).unwrap(), false)) ; "defer by default" )]
+    #[test_case("13:00-14:00 Silent" => Some((NaiveTime::from_hms_opt(13, 0, 0).unwrap(), NaiveTime::from_hms_opt(14, 0, 0).unwrap(), true)) ; "silent" )]
+    #[test_case("23:00-07:30 defer" => Some((NaiveTime::from_hms_opt(23, 0, 0).unwrap(), NaiveTime::from_hms_opt(7, 30, 0).unwrap(), false)) ; "defer" )]
+    #[test_case("23:00-23:00" => None ; "empty window" )]
+    #[test_case("23:00" => None ; "no end" )]
+    #[test_case("23:00-07:30 loud" => None ; "unknown option" )]
+    fn test_parse_quiet_hours_args(
+        arg: &str,
+    ) -> Option<(NaiveTime, NaiveTime, bool)> {
+        parse_quiet_hours_args(arg)
+    }
+
+    #[test_case("12" => Some(("rem", 12)) ; "reminder" )]
+    #[test_case(" c7 " => Some(("cron_rem", 7)) ; "cron reminder" )]
+    #[test_case("" => None ; "empty" )]
+    #[test_case("c" => None ; "no id" )]
+    #[test_case("x12" => None ; "unknown prefix" )]
+    fn test_parse_reminder_ref(arg: &str) -> Option<(&'static str, i64)> {
+        parse_reminder_ref(arg)
+    }
+
+    #[test]
+    fn test_cron_occurrences() {
+        let cur = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(6, 0, 0)
+            .unwrap();
+        let times: Vec<_> = cron_occurrences("0 */8 * * *", Tz::UTC, cur)
+            .take(3)
+            .map(|time| time.time())
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            ]
+        );
+        assert!(cron_occurrences("bad", Tz::UTC, cur).next().is_none());
+    }
 }
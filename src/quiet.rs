@@ -0,0 +1,90 @@
+use crate::date;
+use crate::entity::user_quiet_hours;
+use chrono::{NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+/// Whether the local time of day falls into the window `[start, end)`,
+/// which wraps around midnight if it ends before it starts
+pub fn is_quiet_time(
+    start: NaiveTime,
+    end: NaiveTime,
+    time: NaiveTime,
+) -> bool {
+    if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    }
+}
+
+/// UTC time the quiet hours end at if they are on at `now`
+pub fn quiet_hours_end(
+    quiet_hours: &user_quiet_hours::Model,
+    user_timezone: Tz,
+    now: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    let local_time = user_timezone.from_utc_datetime(&now).time();
+    is_quiet_time(quiet_hours.start_time, quiet_hours.end_time, local_time)
+        .then(|| {
+            date::next_time_of_day(quiet_hours.end_time, user_timezone, now)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::NaiveDate;
+    use test_case::test_case;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test_case(time(13, 0), time(14, 0), time(13, 30) => true ; "inside" )]
+    #[test_case(time(13, 0), time(14, 0), time(14, 0) => false ; "end is excluded" )]
+    #[test_case(time(23, 0), time(7, 30), time(3, 0) => true ; "after midnight" )]
+    #[test_case(time(23, 0), time(7, 30), time(23, 0) => true ; "start is included" )]
+    #[test_case(time(23, 0), time(7, 30), time(12, 0) => false ; "daytime" )]
+    fn test_is_quiet_time(
+        start: NaiveTime,
+        end: NaiveTime,
+        time: NaiveTime,
+    ) -> bool {
+        is_quiet_time(start, end, time)
+    }
+
+    #[test]
+    fn test_quiet_hours_end() {
+        let quiet_hours = user_quiet_hours::Model {
+            id: 1,
+            user_id: 1,
+            start_time: time(23, 0),
+            end_time: time(7, 30),
+            silent: false,
+        };
+        let utc = |day, hour, minute| {
+            NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        // 03:00 in Moscow
+        assert_eq!(
+            quiet_hours_end(
+                &quiet_hours,
+                chrono_tz::Europe::Moscow,
+                utc(10, 0, 0)
+            ),
+            Some(utc(10, 4, 30))
+        );
+        // 12:00 in Moscow
+        assert_eq!(
+            quiet_hours_end(
+                &quiet_hours,
+                chrono_tz::Europe::Moscow,
+                utc(10, 9, 0)
+            ),
+            None
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use std::cmp::max;
 use std::fmt::Formatter;
+use std::str::FromStr;
 
 use bitmask_enum::bitmask;
-use chrono::offset::TimeZone;
+use chrono::offset::{LocalResult, TimeZone};
 use chrono::prelude::*;
 use chrono::Duration;
 use chronoutil::{shift_months, shift_years};
@@ -11,6 +12,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::date;
 use crate::grammar;
+use crate::i18n::{weekday_name, Locale, LocalizedDisplay};
 use crate::parsers::now_time;
 
 #[derive(Debug)]
@@ -112,6 +114,39 @@ pub struct Recurrence {
     pub time_patterns: Vec<TimePattern>,
     #[serde(rename = "tz")]
     pub timezone: Tz,
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+}
+
+/// How a local time repeated by a backward clock change fires
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum OverlapPolicy {
+    /// At the first of the two moments only
+    #[default]
+    Once,
+    /// At both moments
+    Twice,
+}
+
+impl FromStr for OverlapPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "once" => Ok(Self::Once),
+            "twice" => Ok(Self::Twice),
+            _ => Err(format!("unknown overlap policy: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OverlapPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Once => write!(f, "once"),
+            Self::Twice => write!(f, "twice"),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -130,12 +165,15 @@ pub enum Pattern {
     Countdown(Countdown),
 }
 
+/// Formatting of dates patterns which can be parsed back, `today` being
+/// the date the omitted beginnings of ranges are filled with
 trait DateDisplay {
-    fn relfmt<D: Datelike>(
+    fn fmt_date(
         &self,
         f: &mut Formatter<'_>,
-        now: &D,
-    ) -> Result<bool, std::fmt::Error>;
+        today: NaiveDate,
+        locale: Locale,
+    ) -> std::fmt::Result;
 }
 
 pub fn fill_date_holes(
@@ -182,12 +220,46 @@ impl<'de> Deserialize<'de> for Tz {
     }
 }
 
+/// Moment of the local time in the timezone: the earlier one if the time
+/// is repeated and the one shifted forward by the gap if it's skipped
+pub fn local_to_utc(
+    timezone: chrono_tz::Tz,
+    time: &NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    Tz(timezone).local_to_utc(time)
+}
+
 impl Tz {
+    /// Moment of the local time: the earlier one if the time is repeated
+    /// and the one shifted forward by the gap if the time is skipped
     fn local_to_utc(&self, time: &NaiveDateTime) -> Option<NaiveDateTime> {
-        self.0
-            .from_local_datetime(time)
-            .earliest()
-            .map(|dt| dt.naive_utc())
+        self.instants(time, OverlapPolicy::Once).into_iter().next()
+    }
+
+    /// Increasing moments of the local time according to the overlap policy.
+    /// A time inside a gap is taken with the offset in effect before the gap,
+    /// i.e. 02:30 in a 02:00-03:00 gap becomes 03:30
+    fn instants(
+        &self,
+        time: &NaiveDateTime,
+        overlap: OverlapPolicy,
+    ) -> Vec<NaiveDateTime> {
+        match self.0.from_local_datetime(time) {
+            LocalResult::Single(dt) => vec![dt.naive_utc()],
+            LocalResult::Ambiguous(earliest, latest) => match overlap {
+                OverlapPolicy::Once => vec![earliest.naive_utc()],
+                OverlapPolicy::Twice => {
+                    vec![earliest.naive_utc(), latest.naive_utc()]
+                }
+            },
+            LocalResult::None => self
+                .0
+                .from_local_datetime(&(*time - Duration::days(1)))
+                .earliest()
+                .map(|before| *time - before.offset().fix())
+                .into_iter()
+                .collect(),
+        }
     }
 }
 
@@ -275,10 +347,8 @@ impl DateRange {
                 }
             }
             DateDivisor::Interval(int) => {
-                let mut nearest_date = self.from;
-                while nearest_date < date {
-                    nearest_date = date::add_date_interval(nearest_date, &int);
-                }
+                let nearest_date =
+                    date::nearest_interval_date(self.from, &int, date)?;
                 if self
                     .until
                     .map(|until| nearest_date <= until)
@@ -359,6 +429,7 @@ impl Recurrence {
         recurrence: grammar::Recurrence,
         tz: chrono_tz::Tz,
     ) -> Result<Self, ()> {
+        let tz = recurrence.timezone.unwrap_or(tz);
         let lower_bound = tz.from_utc_datetime(&now_time()).naive_local();
         let first_time = match recurrence.time_patterns.first() {
             Some(time_pattern) => match time_pattern {
@@ -447,11 +518,58 @@ impl Recurrence {
             dates_patterns,
             time_patterns,
             timezone: Tz(tz),
+            overlap: OverlapPolicy::default(),
         })
     }
 
+    /// Move the recurrence to the timezone keeping its local times
+    pub fn set_timezone(&mut self, tz: chrono_tz::Tz) {
+        self.timezone = Tz(tz);
+    }
+
+    /// First moment of the recurrence after `cur`, times skipped or repeated
+    /// by clock changes are shifted forward or fired per the overlap policy
     pub fn next(&self, cur: NaiveDateTime) -> Option<NaiveDateTime> {
-        let cur = self.timezone.0.from_utc_datetime(&cur).naive_local();
+        let tz = self.timezone.0;
+        let offset = |time| {
+            tz.offset_from_utc_datetime(&time).fix().local_minus_utc() as i64
+        };
+        // Local times repeated by a nearby backward change may still have
+        // their later moments ahead, so start before the repeated interval
+        let offsets = [
+            offset(cur - Duration::days(1)),
+            offset(cur),
+            offset(cur + Duration::days(1)),
+        ];
+        let lag = offsets.iter().max().unwrap() - offsets.iter().min().unwrap();
+        let cur_local = tz.from_utc_datetime(&cur).naive_local();
+        let mut local =
+            self.next_local(cur_local - Duration::seconds(lag + 1))?;
+        let mut next_time: Option<NaiveDateTime> = None;
+        loop {
+            let instants = self.timezone.instants(&local, self.overlap);
+            // Moments of later local times can't precede the earliest one
+            if let (Some(next_time), Some(&first)) =
+                (next_time, instants.first())
+            {
+                if first >= next_time {
+                    return Some(next_time);
+                }
+            }
+            next_time = instants
+                .into_iter()
+                .filter(|&instant| instant > cur)
+                .chain(next_time)
+                .min();
+            match self.next_local(local).filter(|&next| next > local) {
+                Some(next) => local = next,
+                None => return next_time,
+            }
+        }
+    }
+
+    /// First local time of the recurrence after the local time `cur`
+    fn next_local(&self, cur: NaiveDateTime) -> Option<NaiveDateTime> {
         let cur_date = cur.date();
         let cur_time = cur.time();
         let first_date = self
@@ -475,9 +593,7 @@ impl Recurrence {
             })
             .min()?;
         if first_date > cur_date {
-            return self
-                .timezone
-                .local_to_utc(&first_date.and_time(first_time));
+            return Some(first_date.and_time(first_time));
         }
         let next_time = self
             .time_patterns
@@ -521,7 +637,7 @@ impl Recurrence {
             })
             .min();
         if let Some(next_time) = next_time {
-            return self.timezone.local_to_utc(&cur_date.and_time(next_time));
+            return Some(cur_date.and_time(next_time));
         }
         let next_date = self
             .dates_patterns
@@ -536,29 +652,12 @@ impl Recurrence {
             .flat_map(|int| match int {
                 &DatePattern::Point(date) => Some(date),
                 DatePattern::Range(ref range) => {
-                    let from = range.from;
-                    if from > cur_date {
-                        Some(from)
-                    } else {
-                        let next_date = range
-                            .get_nearest_date(cur_date + Duration::days(1))?;
-                        if range
-                            .until
-                            .map(|date_until| next_date <= date_until)
-                            .unwrap_or(true)
-                        {
-                            Some(next_date)
-                        } else {
-                            None
-                        }
-                    }
+                    range.get_nearest_date(cur_date + Duration::days(1))
                 }
             })
             .min();
 
-        next_date
-            .map(|next_date| next_date.and_time(first_time))
-            .and_then(|next_dt| self.timezone.local_to_utc(&next_dt))
+        next_date.map(|next_date| next_date.and_time(first_time))
     }
 }
 
@@ -615,12 +714,97 @@ impl Pattern {
             Self::Countdown(countdown) => countdown.next(),
         }
     }
+
+    /// Whether the pattern fires more than once
+    pub fn is_recurring(&self) -> bool {
+        match self {
+            Self::Recurrence(recurrence) => {
+                recurrence.dates_patterns.len() != 1
+                    || recurrence.time_patterns.len() != 1
+                    || matches!(
+                        recurrence.dates_patterns[0],
+                        DatePattern::Range(_)
+                    )
+                    || matches!(
+                        recurrence.time_patterns[0],
+                        TimePattern::Range(_)
+                    )
+            }
+            Self::Countdown(countdown) => !countdown.durations.is_empty(),
+        }
+    }
+
+    /// Timezone the pattern's dates and times are in
+    pub fn timezone(&self) -> chrono_tz::Tz {
+        match self {
+            Self::Recurrence(recurrence) => recurrence.timezone.0,
+            Self::Countdown(countdown) => countdown.timezone.0,
+        }
+    }
+
+    /// Move the pattern to the timezone keeping its local times
+    pub fn set_timezone(&mut self, tz: chrono_tz::Tz) {
+        match self {
+            Self::Recurrence(recurrence) => recurrence.set_timezone(tz),
+            Self::Countdown(countdown) => countdown.timezone = Tz(tz),
+        }
+    }
+
+    /// How the pattern's local times repeated by a backward clock change
+    /// fire, countdowns don't have local times
+    pub fn overlap(&self) -> OverlapPolicy {
+        match self {
+            Self::Recurrence(recurrence) => recurrence.overlap,
+            Self::Countdown(_) => OverlapPolicy::default(),
+        }
+    }
+
+    pub fn set_overlap(&mut self, overlap: OverlapPolicy) {
+        if let Self::Recurrence(recurrence) = self {
+            recurrence.overlap = overlap;
+        }
+    }
+
+    /// Iterator over the times the pattern fires after `cur`
+    pub fn occurrences(self, cur: NaiveDateTime) -> Occurrences {
+        Occurrences { pattern: self, cur }
+    }
+}
+
+/// Increasing times of a pattern's firings, see [`Pattern::occurrences`]
+pub struct Occurrences {
+    pattern: Pattern,
+    cur: NaiveDateTime,
+}
+
+impl Iterator for Occurrences {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<NaiveDateTime> {
+        match self.pattern.next(self.cur) {
+            Some(next_time) if next_time > self.cur => {
+                self.cur = next_time;
+                Some(next_time)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Pattern {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_localized(f, Locale::En)
+    }
+}
+
+impl LocalizedDisplay for Pattern {
+    fn fmt_localized(
+        &self,
+        f: &mut Formatter<'_>,
+        locale: Locale,
+    ) -> std::fmt::Result {
         match self {
-            Self::Recurrence(recurrence) => write!(f, "{}", recurrence),
+            Self::Recurrence(recurrence) => recurrence.fmt_localized(f, locale),
             Self::Countdown(countdown) => write!(f, "{}", countdown),
         }
     }
@@ -628,24 +812,24 @@ impl std::fmt::Display for Pattern {
 
 impl std::fmt::Display for Recurrence {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let now = self.timezone.0.from_utc_datetime(&now_time());
-        if self.time_patterns.len() == 1
-            && self.dates_patterns.len() == 1
-            && matches!(self.time_patterns[0], TimePattern::Point(_))
-            && matches!(self.dates_patterns[0], DatePattern::Point(_))
-        {
-            return Ok(());
-        }
-        let mut nonempty = false;
+        self.fmt_localized(f, Locale::En)
+    }
+}
+
+impl LocalizedDisplay for Recurrence {
+    fn fmt_localized(
+        &self,
+        f: &mut Formatter<'_>,
+        locale: Locale,
+    ) -> std::fmt::Result {
+        let today = self.timezone.0.from_utc_datetime(&now_time()).date_naive();
         for (i, dates_pattern) in self.dates_patterns.iter().enumerate() {
             if i != 0 {
                 write!(f, ",")?;
             }
-            nonempty |= dates_pattern.relfmt(f, &now)?;
-        }
-        if nonempty {
-            write!(f, " ")?;
+            dates_pattern.fmt_date(f, today, locale)?;
         }
+        write!(f, " ")?;
         for (i, time_pattern) in self.time_patterns.iter().enumerate() {
             if i != 0 {
                 write!(f, ",")?;
@@ -668,26 +852,34 @@ impl std::fmt::Display for Countdown {
     }
 }
 
+/// Time with seconds only if there are any
+fn fmt_time(f: &mut Formatter<'_>, time: NaiveTime) -> std::fmt::Result {
+    write!(f, "{:02}:{:02}", time.hour(), time.minute())?;
+    if time.second() != 0 {
+        write!(f, ":{:02}", time.second())?;
+    }
+    Ok(())
+}
+
 impl std::fmt::Display for TimePattern {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Point(time) => {
-                write!(f, "{:02}:{:02}", time.hour(), time.minute())
-            }
+            &Self::Point(time) => fmt_time(f, time),
             Self::Range(range) => range.fmt(f),
         }
     }
 }
 
 impl DateDisplay for DatePattern {
-    fn relfmt<D: Datelike>(
+    fn fmt_date(
         &self,
         f: &mut Formatter<'_>,
-        now: &D,
-    ) -> Result<bool, std::fmt::Error> {
+        today: NaiveDate,
+        locale: Locale,
+    ) -> std::fmt::Result {
         match self {
-            Self::Point(date) => date.relfmt(f, now),
-            Self::Range(range) => range.relfmt(f, now),
+            Self::Point(date) => date.fmt_date(f, today, locale),
+            Self::Range(range) => range.fmt_date(f, today, locale),
         }
     }
 }
@@ -695,11 +887,11 @@ impl DateDisplay for DatePattern {
 impl std::fmt::Display for TimeRange {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(from) = self.from {
-            write!(f, "{:02}:{:02}", from.hour(), from.minute())?;
+            fmt_time(f, from)?;
         }
         write!(f, "—")?;
         if let Some(until) = self.until {
-            write!(f, "{:02}:{:02}", until.hour(), until.minute())?;
+            fmt_time(f, until)?;
         }
         write!(f, "/")?;
         self.interval.fmt(f)?;
@@ -708,26 +900,13 @@ impl std::fmt::Display for TimeRange {
 }
 
 impl DateDisplay for DateRange {
-    fn relfmt<D: Datelike>(
+    fn fmt_date(
         &self,
         f: &mut Formatter<'_>,
-        now: &D,
-    ) -> Result<bool, std::fmt::Error> {
-        if self.from.year() > now.year()
-            || self.from.year() == now.year()
-                && (self.from.month() > now.month()
-                    || self.from.month() == now.month()
-                        && self.from.day() >= now.day())
-        {
-            self.from.relfmt(f, now)?;
-        }
-        write!(f, "—")?;
-        if let Some(until) = self.until {
-            if self.from != until {
-                until.relfmt(f, now)?;
-            }
-        }
-        if !matches!(
+        today: NaiveDate,
+        locale: Locale,
+    ) -> std::fmt::Result {
+        let daily = matches!(
             self.date_divisor,
             DateDivisor::Interval(DateInterval {
                 years: 0,
@@ -735,58 +914,73 @@ impl DateDisplay for DateRange {
                 weeks: 0,
                 days: 1,
             })
-        ) {
-            write!(f, "/{}", self.date_divisor)?;
+        );
+        // The dates of weekdays and daily ranges don't depend on
+        // the beginning if it has come
+        let aligned =
+            daily || matches!(self.date_divisor, DateDivisor::Weekdays(_));
+        if self.from > today || !aligned {
+            self.from.fmt_date(f, today, locale)?;
+        }
+        write!(f, "—")?;
+        if let Some(until) = self.until {
+            until.fmt_date(f, today, locale)?;
+        }
+        if !daily {
+            write!(f, "/")?;
+            self.date_divisor.fmt_localized(f, locale)?;
         }
-        Ok(true)
+        Ok(())
     }
 }
 
 impl DateDisplay for NaiveDate {
-    fn relfmt<D: Datelike>(
+    fn fmt_date(
         &self,
         f: &mut Formatter<'_>,
-        now: &D,
-    ) -> Result<bool, std::fmt::Error> {
-        let same_year = self.year() == now.year();
-        let same_month = same_year && self.month() == now.month();
-        let same_day = same_month && self.day() == now.day();
-        if same_day {
-            Ok(false)
-        } else {
-            if same_month {
-                write!(f, "{}", self.format("%d"))?;
-            } else if same_year {
-                write!(f, "{}", self.format("%d.%m"))?;
-            } else {
-                write!(f, "{}", self.format("%d.%m.%y"))?;
-            }
-            Ok(true)
-        }
+        _today: NaiveDate,
+        _locale: Locale,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.format("%d.%m.%Y"))
     }
 }
 
 impl std::fmt::Display for DateDivisor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_localized(f, Locale::En)
+    }
+}
+
+impl LocalizedDisplay for DateDivisor {
+    fn fmt_localized(
+        &self,
+        f: &mut Formatter<'_>,
+        locale: Locale,
+    ) -> std::fmt::Result {
         match *self {
-            DateDivisor::Weekdays(weekdays) => weekdays.fmt(f),
-            DateDivisor::Interval(interval) => interval.fmt(f),
+            DateDivisor::Weekdays(weekdays) => {
+                weekdays.fmt_localized(f, locale)
+            }
+            DateDivisor::Interval(interval) => write!(f, "{}", interval),
         }
     }
 }
 
 impl std::fmt::Display for Weekdays {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
-            .iter()
-            .enumerate()
-            .filter_map(|(i, w)| {
-                if self.bits() & (1 << i) != 0 {
-                    Some(w)
-                } else {
-                    None
-                }
-            });
+        self.fmt_localized(f, Locale::En)
+    }
+}
+
+impl LocalizedDisplay for Weekdays {
+    fn fmt_localized(
+        &self,
+        f: &mut Formatter<'_>,
+        locale: Locale,
+    ) -> std::fmt::Result {
+        let weekdays = (0..7)
+            .filter(|i| self.bits() & (1 << i) != 0)
+            .map(|i| weekday_name(locale, Weekday::try_from(i).unwrap()));
         for (i, weekday) in weekdays.enumerate() {
             if i != 0 {
                 write!(f, ",")?;
@@ -815,13 +1009,13 @@ impl std::fmt::Display for TimeInterval {
 impl std::fmt::Display for DateInterval {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.years != 0 {
-            write!(f, "{}s", self.years)?;
+            write!(f, "{}y", self.years)?;
         }
         if self.months != 0 {
             write!(f, "{}m", self.months)?;
         }
         if self.weeks != 0 {
-            write!(f, "{}s", self.weeks)?;
+            write!(f, "{}w", self.weeks)?;
         }
         if self.days != 0 {
             write!(f, "{}d", self.days)?;
@@ -833,13 +1027,13 @@ impl std::fmt::Display for DateInterval {
 impl std::fmt::Display for Interval {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.years != 0 {
-            write!(f, "{}s", self.years)?;
+            write!(f, "{}y", self.years)?;
         }
         if self.months != 0 {
             write!(f, "{}mo", self.months)?;
         }
         if self.weeks != 0 {
-            write!(f, "{}s", self.weeks)?;
+            write!(f, "{}w", self.weeks)?;
         }
         if self.days != 0 {
             write!(f, "{}d", self.days)?;
@@ -862,15 +1056,15 @@ mod test {
     use super::*;
     use crate::{
         grammar::parse_reminder,
+        i18n::Localized,
         parsers::test::{TEST_TIME, TEST_TIMESTAMP, TEST_TZ},
     };
+    use proptest::prelude::*;
+    use test_case::test_case;
 
-    fn get_all_times(
-        mut pattern: Pattern,
-    ) -> impl Iterator<Item = NaiveDateTime> {
-        let cur = now_time();
-        std::iter::successors(Some(cur), move |&cur| pattern.next(cur))
-            .skip(1)
+    fn get_all_times(pattern: Pattern) -> impl Iterator<Item = NaiveDateTime> {
+        pattern
+            .occurrences(now_time())
             .map(|x| TEST_TZ.from_utc_datetime(&x).naive_local())
     }
 
@@ -894,7 +1088,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "1w1h2m3s countdown";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("countdown".to_owned())
@@ -913,7 +1107,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "1w1h2m3s,2w1h20m7s countdown";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("countdown".to_owned())
@@ -932,7 +1126,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "- 11-18/1h periodic";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("periodic".to_owned())
@@ -967,7 +1161,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "3-6/2d 13:37 date range";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("date range".to_owned())
@@ -986,7 +1180,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "07.06.2025 13:37";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(parsed_rem.description.map(|x| x.0), None);
         let parsed = parsed_rem.pattern.unwrap();
         let pattern = Pattern::from_with_tz(parsed, *TEST_TZ).unwrap();
@@ -1002,7 +1196,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "2025/06/07 13:37 date format2";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("date format2".to_owned())
@@ -1021,7 +1215,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "12/31/1MONTH 13:37 end of month";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("end of month".to_owned())
@@ -1057,7 +1251,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "/fri,mon 11:00 weekdays";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("weekdays".to_owned())
@@ -1081,7 +1275,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "/fri-mon,wed 15:00:20 weekdays ranges";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("weekdays ranges".to_owned())
@@ -1105,13 +1299,69 @@ mod test {
         );
     }
 
+    #[test_case(Locale::En => "—/Mon,Wed,Fri,Sat,Sun 15:00" ; "english" )]
+    #[test_case(Locale::Ru => "—/Пн,Ср,Пт,Сб,Вс 15:00" ; "russian" )]
+    #[test_case(Locale::Uk => "—/Пн,Ср,Пт,Сб,Нд 15:00" ; "ukrainian" )]
+    fn test_localized_weekdays(locale: Locale) -> String {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let parsed = parse_reminder("/fri-mon,wed 15:00 x", Locale::En)
+            .unwrap()
+            .pattern
+            .unwrap();
+        let pattern = Pattern::from_with_tz(parsed, *TEST_TZ).unwrap();
+        Localized(&pattern, locale).to_string()
+    }
+
+    #[test_case("10:00", Locale::En => "03.02.2007 10:00" ; "time")]
+    #[test_case("15:16:17", Locale::En => "02.02.2007 15:16:17" ; "seconds")]
+    #[test_case("07.06.2025 13:37", Locale::En => "07.06.2025 13:37" ; "date")]
+    #[test_case("2025/06/07 13:37", Locale::En => "07.06.2025 13:37" ; "slashed date")]
+    #[test_case("1.3,5.3 10:00,11:30", Locale::En => "01.03.2007,05.03.2007 10:00,11:30" ; "lists")]
+    #[test_case("- 11-18/1h", Locale::En => "— 11:00—18:00/1h" ; "every day")]
+    #[test_case("3-6/2d 13:37", Locale::En => "03.02.2007—06.02.2007/2d 13:37" ; "date range")]
+    #[test_case("12/31/1MONTH 13:37", Locale::En => "31.12.2007—/1m 13:37" ; "open date range")]
+    #[test_case("12/16-3/16/1m 18:15", Locale::En => "16.12.2007—16.03.2008/1m 18:15" ; "date range over year")]
+    #[test_case("every 1y 10:00", Locale::En => "02.02.2007—/1y 10:00" ; "years")]
+    #[test_case("every 2w 10:00", Locale::En => "02.02.2007—/2w 10:00" ; "weeks")]
+    #[test_case("every 1y2m3w4d 10:00", Locale::En => "02.02.2007—/1y2m3w4d 10:00" ; "all date units")]
+    #[test_case("/fri,mon 11:00", Locale::En => "—/Mon,Fri 11:00" ; "weekdays")]
+    #[test_case("/fri-mon,wed 15:00:20", Locale::En => "—/Mon,Wed,Fri,Sat,Sun 15:00:20" ; "weekdays ranges")]
+    #[test_case("10-20/mon,fri-sun 11-12/1h", Locale::En => "10.02.2007—20.02.2007/Mon,Fri,Sat,Sun 11:00—12:00/1h" ; "weekdays date range")]
+    #[test_case("10:00-18:00/30m", Locale::En => "02.02.2007 10:00—18:00/30m" ; "time range")]
+    #[test_case("/1h30m15s", Locale::En => "02.02.2007 —/1h30m15s" ; "open time range")]
+    #[test_case("-13/1h", Locale::En => "02.02.2007 —13:00/1h" ; "time range until")]
+    #[test_case("1w1h2m3s", Locale::En => "1w1h2m3s" ; "countdown")]
+    #[test_case("1y2mo,3d", Locale::En => "1y2mo,3d" ; "countdowns")]
+    #[test_case("/пт,пн 11:00", Locale::Ru => "—/Пн,Пт 11:00" ; "russian weekdays")]
+    #[test_case("/пт,нд 11:00", Locale::Uk => "—/Пт,Нд 11:00" ; "ukrainian weekdays")]
+    fn test_display_round_trip(s: &str, locale: Locale) -> String {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let parse = |s: &str| {
+            let parsed = parse_reminder(s, locale).unwrap().pattern.unwrap();
+            Pattern::from_with_tz(parsed, *TEST_TZ).unwrap()
+        };
+        let pattern = parse(s);
+        let displayed = Localized(&pattern, locale).to_string();
+        let reparsed = parse(&displayed);
+        assert_eq!(Localized(&reparsed, locale).to_string(), displayed);
+        assert_eq!(
+            get_all_times(reparsed).take(10).collect::<Vec<_>>(),
+            get_all_times(pattern).take(10).collect::<Vec<_>>()
+        );
+        displayed
+    }
+
     #[test]
     fn test_description_trim() {
         unsafe {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "15:16     test    description   ";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("test    description".to_owned())
@@ -1130,7 +1380,7 @@ mod test {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "10-20/mon,fri-sun 11-12/1h date range weekends";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("date range weekends".to_owned())
@@ -1158,13 +1408,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_date_range_from_outside_weekdays() {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let s = "1-3,14-20/sat,sun 11:00 two ranges";
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
+        let parsed = parsed_rem.pattern.unwrap();
+        let pattern = Pattern::from_with_tz(parsed, *TEST_TZ).unwrap();
+        assert_eq!(
+            get_all_times(pattern).collect::<Vec<_>>(),
+            vec![
+                tz(2007, 3, 1, 11, 0, 0),
+                tz(2007, 3, 2, 11, 0, 0),
+                tz(2007, 3, 3, 11, 0, 0),
+                tz(2007, 3, 17, 11, 0, 0),
+                tz(2007, 3, 18, 11, 0, 0),
+            ]
+        );
+    }
+
     #[test]
     fn test_date_range_over_year() {
         unsafe {
             TEST_TIMESTAMP = TEST_TIME.timestamp();
         }
         let s = "12/16-3/16/1m 18:15 date range over year";
-        let parsed_rem = parse_reminder(s).unwrap();
+        let parsed_rem = parse_reminder(s, Locale::En).unwrap();
         assert_eq!(
             parsed_rem.description.map(|x| x.0),
             Some("date range over year".to_owned())
@@ -1181,4 +1452,479 @@ mod test {
             ]
         );
     }
+
+    fn utc(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+    ) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    fn daily(
+        timezone: chrono_tz::Tz,
+        from: NaiveDate,
+        time_pattern: TimePattern,
+        overlap: OverlapPolicy,
+    ) -> Recurrence {
+        Recurrence {
+            dates_patterns: vec![DatePattern::Range(DateRange {
+                from,
+                until: None,
+                date_divisor: DateDivisor::Interval(DateInterval {
+                    years: 0,
+                    months: 0,
+                    weeks: 0,
+                    days: 1,
+                }),
+            })],
+            time_patterns: vec![time_pattern],
+            timezone: Tz(timezone),
+            overlap,
+        }
+    }
+
+    fn at(hour: u32, min: u32) -> TimePattern {
+        TimePattern::Point(NaiveTime::from_hms_opt(hour, min, 0).unwrap())
+    }
+
+    fn dst_times(
+        recurrence: Recurrence,
+        cur: NaiveDateTime,
+        count: usize,
+    ) -> Vec<NaiveDateTime> {
+        Pattern::Recurrence(recurrence)
+            .occurrences(cur)
+            .take(count)
+            .collect()
+    }
+
+    #[test_case(
+        chrono_tz::America::New_York, (2024, 3, 9), utc(2024, 3, 9, 12, 0),
+        at(2, 30)
+        => vec![utc(2024, 3, 10, 7, 30), utc(2024, 3, 11, 6, 30)]
+        ; "new york spring forward"
+    )]
+    #[test_case(
+        chrono_tz::Europe::Berlin, (2024, 3, 30), utc(2024, 3, 30, 12, 0),
+        at(2, 30)
+        => vec![utc(2024, 3, 31, 1, 30), utc(2024, 4, 1, 0, 30)]
+        ; "berlin spring forward"
+    )]
+    #[test_case(
+        chrono_tz::Australia::Sydney, (2024, 10, 5), utc(2024, 10, 5, 0, 0),
+        at(2, 30)
+        => vec![utc(2024, 10, 5, 16, 30), utc(2024, 10, 6, 15, 30)]
+        ; "sydney spring forward"
+    )]
+    #[test_case(
+        chrono_tz::Australia::Lord_Howe, (2024, 10, 5), utc(2024, 10, 5, 0, 0),
+        at(2, 15)
+        => vec![utc(2024, 10, 5, 15, 45), utc(2024, 10, 6, 15, 15)]
+        ; "lord howe half hour gap"
+    )]
+    #[test_case(
+        chrono_tz::Europe::Berlin, (2024, 10, 26), utc(2024, 10, 26, 12, 0),
+        at(2, 30)
+        => vec![utc(2024, 10, 27, 0, 30), utc(2024, 10, 28, 1, 30)]
+        ; "berlin fall back"
+    )]
+    fn test_dst_transitions(
+        timezone: chrono_tz::Tz,
+        (year, month, day): (i32, u32, u32),
+        cur: NaiveDateTime,
+        time_pattern: TimePattern,
+    ) -> Vec<NaiveDateTime> {
+        let from = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let recurrence =
+            daily(timezone, from, time_pattern, OverlapPolicy::Once);
+        dst_times(recurrence, cur, 2)
+    }
+
+    #[test]
+    fn test_dst_overlap_twice() {
+        let from = NaiveDate::from_ymd_opt(2024, 10, 27).unwrap();
+        let recurrence = daily(
+            chrono_tz::Europe::Berlin,
+            from,
+            at(2, 30),
+            OverlapPolicy::Twice,
+        );
+        assert_eq!(
+            dst_times(recurrence, utc(2024, 10, 26, 12, 0), 3),
+            vec![
+                utc(2024, 10, 27, 0, 30),
+                utc(2024, 10, 27, 1, 30),
+                utc(2024, 10, 28, 1, 30),
+            ]
+        );
+    }
+
+    #[test_case(
+        OverlapPolicy::Once
+        => vec![
+            utc(2024, 11, 3, 5, 0),
+            utc(2024, 11, 3, 5, 30),
+            utc(2024, 11, 3, 7, 0),
+            utc(2024, 11, 4, 6, 0),
+        ]
+        ; "once"
+    )]
+    #[test_case(
+        OverlapPolicy::Twice
+        => vec![
+            utc(2024, 11, 3, 5, 0),
+            utc(2024, 11, 3, 5, 30),
+            utc(2024, 11, 3, 6, 0),
+            utc(2024, 11, 3, 6, 30),
+            utc(2024, 11, 3, 7, 0),
+            utc(2024, 11, 4, 6, 0),
+        ]
+        ; "twice"
+    )]
+    fn test_dst_overlap_time_range(
+        overlap: OverlapPolicy,
+    ) -> Vec<NaiveDateTime> {
+        let from = NaiveDate::from_ymd_opt(2024, 11, 3).unwrap();
+        let time_range = TimePattern::Range(TimeRange {
+            from: NaiveTime::from_hms_opt(1, 0, 0),
+            until: NaiveTime::from_hms_opt(2, 0, 0),
+            interval: TimeInterval {
+                hours: 0,
+                minutes: 30,
+                seconds: 0,
+            },
+        });
+        let recurrence =
+            daily(chrono_tz::America::New_York, from, time_range, overlap);
+        let count = if overlap == OverlapPolicy::Once { 4 } else { 6 };
+        dst_times(recurrence, utc(2024, 11, 3, 4, 0), count)
+    }
+
+    #[test]
+    fn test_dst_gap_time_range() {
+        let from = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let time_range = TimePattern::Range(TimeRange {
+            from: NaiveTime::from_hms_opt(1, 0, 0),
+            until: NaiveTime::from_hms_opt(3, 30, 0),
+            interval: TimeInterval {
+                hours: 0,
+                minutes: 30,
+                seconds: 0,
+            },
+        });
+        let recurrence = daily(
+            chrono_tz::America::New_York,
+            from,
+            time_range,
+            OverlapPolicy::Once,
+        );
+        // 02:00 and 02:30 are shifted onto 03:00 and 03:30
+        assert_eq!(
+            dst_times(recurrence, utc(2024, 3, 10, 5, 0), 5),
+            vec![
+                utc(2024, 3, 10, 6, 0),
+                utc(2024, 3, 10, 6, 30),
+                utc(2024, 3, 10, 7, 0),
+                utc(2024, 3, 10, 7, 30),
+                utc(2024, 3, 11, 5, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_countdown_into_dst_gap() {
+        let mut countdown = Countdown {
+            // 2024-03-09 02:30 in New York
+            time_from: utc(2024, 3, 9, 7, 30),
+            durations: vec![Interval {
+                years: 0,
+                months: 0,
+                weeks: 0,
+                days: 1,
+                hours: 0,
+                minutes: 0,
+                seconds: 0,
+            }],
+            timezone: Tz(chrono_tz::America::New_York),
+        };
+        assert_eq!(countdown.next(), Some(utc(2024, 3, 10, 7, 30)));
+    }
+
+    fn any_timezone() -> impl Strategy<Value = chrono_tz::Tz> {
+        prop::sample::select(vec![
+            chrono_tz::UTC,
+            chrono_tz::Europe::Moscow,
+            chrono_tz::Europe::Berlin,
+            chrono_tz::America::New_York,
+            chrono_tz::Australia::Sydney,
+            chrono_tz::Australia::Lord_Howe,
+            chrono_tz::Asia::Kolkata,
+        ])
+    }
+
+    fn any_holey_date(
+        years: impl Strategy<Value = Option<i32>>,
+        months: impl Strategy<Value = Option<u32>>,
+    ) -> impl Strategy<Value = grammar::HoleyDate> {
+        (years, months, prop::option::of(1..=31u32)).prop_map(
+            |(year, month, day)| grammar::HoleyDate { year, month, day },
+        )
+    }
+
+    fn any_grammar_date_interval(
+    ) -> impl Strategy<Value = grammar::DateInterval> {
+        (0..3i32, 0..13u32, 0..3u32, 0..10u32)
+            .prop_filter("empty interval", |&(y, mo, w, d)| {
+                y != 0 || mo != 0 || w != 0 || d != 0
+            })
+            .prop_map(|(years, months, weeks, days)| grammar::DateInterval {
+                years,
+                months,
+                weeks,
+                days,
+            })
+    }
+
+    fn any_grammar_date_pattern() -> impl Strategy<Value = grammar::DatePattern>
+    {
+        let date = || {
+            any_holey_date(
+                prop::option::of(2007..2010),
+                prop::option::of(1..=12u32),
+            )
+        };
+        let divisor = prop_oneof![
+            (1..128u8).prop_map(|bits| grammar::DateDivisor::Weekdays(
+                grammar::Weekdays::from(bits)
+            )),
+            any_grammar_date_interval()
+                .prop_map(grammar::DateDivisor::Interval),
+        ];
+        prop_oneof![
+            date().prop_map(grammar::DatePattern::Point),
+            (date(), prop::option::of(date()), divisor).prop_map(
+                |(from, until, date_divisor)| {
+                    grammar::DatePattern::Range(grammar::DateRange {
+                        from,
+                        until,
+                        date_divisor,
+                    })
+                }
+            ),
+        ]
+    }
+
+    fn any_grammar_time() -> impl Strategy<Value = grammar::Time> {
+        (0..24u32, 0..60u32, 0..60u32).prop_map(|(hour, minute, second)| {
+            grammar::Time {
+                hour,
+                minute,
+                second,
+            }
+        })
+    }
+
+    fn any_grammar_time_pattern() -> impl Strategy<Value = grammar::TimePattern>
+    {
+        let interval = (0..3u32, 0..60u32, 0..60u32)
+            .prop_filter("empty interval", |&(h, m, s)| {
+                h != 0 || m != 0 || s != 0
+            })
+            .prop_map(|(hours, minutes, seconds)| grammar::TimeInterval {
+                hours,
+                minutes,
+                seconds,
+            });
+        let time = || prop::option::of(any_grammar_time());
+        prop_oneof![
+            any_grammar_time().prop_map(grammar::TimePattern::Point),
+            (time(), time(), interval)
+                .prop_filter("reversed range", |(from, until, _)| {
+                    match (from, until) {
+                        (Some(from), Some(until)) => {
+                            (from.hour, from.minute, from.second)
+                                <= (until.hour, until.minute, until.second)
+                        }
+                        _ => true,
+                    }
+                })
+                .prop_map(|(from, until, interval)| {
+                    grammar::TimePattern::Range(grammar::TimeRange {
+                        from,
+                        until,
+                        interval,
+                    })
+                }),
+        ]
+    }
+
+    fn any_grammar_recurrence() -> impl Strategy<Value = grammar::Recurrence> {
+        (
+            prop::collection::vec(any_grammar_date_pattern(), 1..=3),
+            prop::collection::vec(any_grammar_time_pattern(), 1..3),
+        )
+            .prop_map(|(dates_patterns, time_patterns)| {
+                grammar::Recurrence {
+                    dates_patterns: NonEmpty::from_vec(dates_patterns).unwrap(),
+                    time_patterns,
+                    timezone: None,
+                }
+            })
+    }
+
+    fn recurrence_times(
+        recurrence: &Recurrence,
+        count: usize,
+    ) -> Vec<NaiveDateTime> {
+        let mut cur = now_time();
+        let mut times = vec![];
+        while times.len() < count {
+            match recurrence.next(cur) {
+                Some(next_time) => {
+                    times.push(next_time);
+                    cur = next_time;
+                }
+                None => break,
+            }
+        }
+        times
+    }
+
+    fn date_matches(pattern: &DatePattern, date: NaiveDate) -> bool {
+        match pattern {
+            &DatePattern::Point(point) => point == date,
+            DatePattern::Range(range) => {
+                range.from <= date
+                    && range.until.map(|until| date <= until).unwrap_or(true)
+                    && match range.date_divisor {
+                        DateDivisor::Weekdays(weekdays) => {
+                            weekdays.bits()
+                                & (1 << date.weekday().num_days_from_monday())
+                                != 0
+                        }
+                        DateDivisor::Interval(int) => {
+                            date::nearest_interval_date(range.from, &int, date)
+                                == Some(date)
+                        }
+                    }
+            }
+        }
+    }
+
+    fn time_matches(pattern: &TimePattern, time: NaiveTime) -> bool {
+        match pattern {
+            &TimePattern::Point(point) => point == time,
+            TimePattern::Range(range) => {
+                let from = range.from.unwrap_or(NaiveTime::MIN);
+                let interval = Duration::from(range.interval).num_seconds();
+                from <= time
+                    && range.until.map(|until| time <= until).unwrap_or(true)
+                    && (time - from).num_seconds() % interval == 0
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_recurrence_next_invariants(
+            recurrence in any_grammar_recurrence(),
+            timezone in any_timezone(),
+        ) {
+            unsafe {
+                TEST_TIMESTAMP = TEST_TIME.timestamp();
+            }
+            let recurrence = Recurrence::from_with_tz(recurrence, timezone);
+            prop_assume!(recurrence.is_ok());
+            let recurrence = recurrence.unwrap();
+            let times = recurrence_times(&recurrence, 20);
+            let mut cur = now_time();
+            for time in times {
+                prop_assert!(time > cur, "{} isn't after {}", time, cur);
+                cur = time;
+                let local = timezone.from_utc_datetime(&time).naive_local();
+                prop_assert!(
+                    recurrence
+                        .dates_patterns
+                        .iter()
+                        .any(|pattern| date_matches(pattern, local.date())),
+                    "date of {} matches no pattern",
+                    local
+                );
+                // Times near clock changes can be shifted
+                let offset = |time| timezone.offset_from_utc_datetime(&time).fix();
+                if offset(time - Duration::days(1)) == offset(time)
+                    && offset(time + Duration::days(1)) == offset(time)
+                {
+                    prop_assert!(
+                        recurrence
+                            .time_patterns
+                            .iter()
+                            .any(|pattern| time_matches(pattern, local.time())),
+                        "time of {} matches no pattern",
+                        local
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_recurrence_serde_round_trip(
+            recurrence in any_grammar_recurrence(),
+            timezone in any_timezone(),
+        ) {
+            unsafe {
+                TEST_TIMESTAMP = TEST_TIME.timestamp();
+            }
+            let recurrence = Recurrence::from_with_tz(recurrence, timezone);
+            prop_assume!(recurrence.is_ok());
+            let recurrence = recurrence.unwrap();
+            let serialized = serde_json::to_string(&recurrence).unwrap();
+            let deserialized: Recurrence =
+                serde_json::from_str(&serialized).unwrap();
+            prop_assert_eq!(
+                &serde_json::to_string(&deserialized).unwrap(),
+                &serialized
+            );
+            prop_assert_eq!(
+                recurrence_times(&deserialized, 20),
+                recurrence_times(&recurrence, 20)
+            );
+        }
+
+        #[test]
+        fn test_recurrence_display_round_trip(
+            recurrence in any_grammar_recurrence(),
+            timezone in any_timezone(),
+        ) {
+            unsafe {
+                TEST_TIMESTAMP = TEST_TIME.timestamp();
+            }
+            let recurrence = Recurrence::from_with_tz(recurrence, timezone);
+            prop_assume!(recurrence.is_ok());
+            let recurrence = recurrence.unwrap();
+            let displayed = recurrence.to_string();
+            let reparsed = match parse_reminder(&displayed, Locale::En)
+                .ok()
+                .and_then(|reminder| reminder.pattern)
+            {
+                Some(grammar::ReminderPattern::Recurrence(reparsed)) => {
+                    Recurrence::from_with_tz(reparsed, timezone)
+                }
+                _ => Err(()),
+            };
+            prop_assert!(reparsed.is_ok(), "{} isn't reparsed", displayed);
+            prop_assert_eq!(
+                recurrence_times(&reparsed.unwrap(), 20),
+                recurrence_times(&recurrence, 20),
+                "{}",
+                displayed
+            );
+        }
+    }
 }
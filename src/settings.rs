@@ -0,0 +1,85 @@
+use crate::format::DateOrder;
+use crate::i18n::Locale;
+use crate::parsers::ForwardedMessage;
+use crate::serializers::OverlapPolicy;
+use std::str::FromStr;
+
+/// User's or chat's setting stored as a string under its key
+pub trait Setting {
+    const KEY: &'static str;
+    type Value: FromStr + ToString;
+}
+
+/// Name of the user's timezone or the group's one
+/// set by its administrators
+pub struct Timezone;
+
+impl Setting for Timezone {
+    const KEY: &'static str = "timezone";
+    type Value = String;
+}
+
+/// Whether reminders are sent without a notification sound
+pub struct SilentNotifications;
+
+impl Setting for SilentNotifications {
+    const KEY: &'static str = "silent_notifications";
+    type Value = bool;
+}
+
+/// Language of the bot's messages chosen with /language
+pub struct Language;
+
+impl Setting for Language {
+    const KEY: &'static str = "language";
+    type Value = Locale;
+}
+
+/// Language of the user's Telegram, the last one seen
+pub struct TelegramLanguage;
+
+impl Setting for TelegramLanguage {
+    const KEY: &'static str = "telegram_language";
+    type Value = Locale;
+}
+
+/// Order of the day and month in shown dates
+pub struct DateFormat;
+
+impl Setting for DateFormat {
+    const KEY: &'static str = "date_format";
+    type Value = DateOrder;
+}
+
+/// Whether times are shown with the 12-hour clock
+pub struct TwelveHourClock;
+
+impl Setting for TwelveHourClock {
+    const KEY: &'static str = "twelve_hour_clock";
+    type Value = bool;
+}
+
+/// Minutes a delivered reminder is snoozed for by default
+pub struct DefaultSnooze;
+
+impl Setting for DefaultSnooze {
+    const KEY: &'static str = "default_snooze";
+    type Value = i64;
+}
+
+/// How recurring reminders at a local time repeated by a backward clock
+/// change fire, applied to the reminders set afterwards
+pub struct ClockChangeOverlap;
+
+impl Setting for ClockChangeOverlap {
+    const KEY: &'static str = "clock_change_overlap";
+    type Value = OverlapPolicy;
+}
+
+/// Message the user forwarded to the bot waiting for a reminder about it
+pub struct PendingForward;
+
+impl Setting for PendingForward {
+    const KEY: &'static str = "pending_forward";
+    type Value = ForwardedMessage;
+}
@@ -1,9 +1,24 @@
-use teloxide::payloads::SendMessageSetters;
+use crate::bot::Command;
+use crate::i18n::{self, tr_with, Locale};
+use crate::parsers::EditMode;
+use crate::tz::Reanchor;
+use teloxide::payloads::setters::*;
 use teloxide::prelude::*;
+use teloxide::requests::HasPayload;
 use teloxide::types::ParseMode::MarkdownV2;
-use teloxide::types::{ChatId, InlineKeyboardMarkup, MessageId};
+use teloxide::types::{
+    BotCommand, ChatId, InlineKeyboardButton, InlineKeyboardButtonKind,
+    InlineKeyboardMarkup, InputFile, MessageId,
+};
+use teloxide::utils::command::BotCommands;
 use teloxide::utils::markdown::escape;
-use teloxide::RequestError;
+use teloxide::{ApiError, RequestError};
+
+/// Telegram's limit of a message's length
+pub const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Telegram's limit of a media caption's length
+pub const MAX_CAPTION_LEN: usize = 1024;
 
 pub enum TgResponse {
     SuccessInsert(String),
@@ -12,15 +27,31 @@ pub enum TgResponse {
     IncorrectRequest,
     QueryingError,
     RemindersListHeader,
+    TaggedRemindersListHeader(String),
+    TagsListHeader,
+    NoTags,
+    SearchResultsHeader,
+    NothingFound,
+    EmptySearchQuery,
     SelectTimezone,
     ChosenTimezone(String),
     NoChosenTimezone,
     FailedSetTimezone(String),
+    SelectChatTimezone,
+    ChatTimezone(String),
+    NotChatAdmin,
+    NoChatTimezone,
+    ReanchorQuestion(String, String),
+    Reanchored(Reanchor, String),
+    FailedReanchor,
     ChooseDeleteReminder,
     SuccessDelete(String),
     FailedDelete,
     ChooseEditReminder,
     EnterNewReminder,
+    EnterReminderPart(EditMode),
+    CopyReminderText,
+    ReplyToForwarded,
     SuccessEdit(String, String),
     FailedEdit,
     CancelEdit,
@@ -29,82 +60,541 @@ pub enum TgResponse {
     SuccessPause(String),
     SuccessResume(String),
     FailedPause,
+    SuccessBulkDelete(usize),
+    SuccessBulkPause(usize),
+    NothingSelected,
+    DigestSchedule(String, bool),
+    DigestDisabled,
+    DigestUsage,
+    FailedSetDigest,
+    DigestHeader,
+    EmptyDigest,
+    SettingsHeader,
+    FailedSetSetting,
+    QuietHours(String, String, bool),
+    QuietHoursDisabled,
+    QuietHoursUsage,
+    FailedSetQuietHours,
+    ChooseNextReminder,
+    NextOccurrencesHeader(String, String),
+    NextUsage,
+    ReminderNotFound,
+    ReminderDone(String),
+    ReminderSnoozed(String, i64),
+    NotRecipient,
+    FailedSnooze,
+    HistoryHeader,
+    EmptyHistory,
+    SuccessUndo(String),
+    FailedUndo,
+    NothingToUndo,
+    ChosenLanguage,
+    LanguageUsage,
+    Help,
     Hello,
 }
 
 impl TgResponse {
-    pub fn to_unescaped_string(&self) -> String {
+    pub fn to_unescaped_string(&self, locale: Locale) -> String {
+        let tr = |key| i18n::tr(locale, key);
+        let tr_reminder = |key, reminder_str: &String| {
+            tr_with(locale, key, [("reminder", reminder_str.into())])
+        };
         match self {
-            Self::SuccessInsert(reminder_str) => format!("Added a reminder:\n{}", reminder_str),
-            Self::SuccessPeriodicInsert(reminder_str) => format!("Added a periodic reminder:\n{}", reminder_str),
-            Self::FailedInsert => "Failed to create a reminder...".to_owned(),
-            Self::IncorrectRequest => "Incorrect request!".to_owned(),
-            Self::QueryingError => "Error occured while querying reminders...".to_owned(),
-            Self::RemindersListHeader => "List of reminders:".to_owned(),
-            Self::SelectTimezone => "Select your timezone:".to_owned(),
-            Self::ChosenTimezone(tz_name) => format!(
-                concat!(
-                    "Selected timezone {}. Now you can set some reminders.\n\n",
-                    "You can get the commands I understand with /help."
-                ),
-                tz_name
+            Self::SuccessInsert(reminder_str) => {
+                tr_reminder("success-insert", reminder_str)
+            }
+            Self::SuccessPeriodicInsert(reminder_str) => {
+                tr_reminder("success-periodic-insert", reminder_str)
+            }
+            Self::FailedInsert => tr("failed-insert"),
+            Self::IncorrectRequest => tr("incorrect-request"),
+            Self::QueryingError => tr("querying-error"),
+            Self::RemindersListHeader => tr("reminders-list-header"),
+            Self::TaggedRemindersListHeader(tag) => tr_with(
+                locale,
+                "tagged-reminders-list-header",
+                [("tag", tag.into())],
             ),
-            Self::NoChosenTimezone => "You've not selected timezone yet, please do it with /settimezone command".to_owned(),
-            Self::FailedSetTimezone(tz_name) => format!("Failed to set timezone {}", tz_name),
-            Self::ChooseDeleteReminder => "Choose a reminder to delete:".to_owned(),
-            Self::SuccessDelete(reminder_str) => format!("🗑 Deleted a reminder: {}", reminder_str),
-            Self::FailedDelete => "Failed to delete...".to_owned(),
-            Self::ChooseEditReminder => "Choose a reminder to edit:".to_owned(),
-            Self::EnterNewReminder => "Enter reminder to replace with:".to_owned(),
-            Self::SuccessEdit(old_reminder_str, reminder_str) => format!("📝 Replaced a reminder: {}\nwith ➡️ {}", old_reminder_str, reminder_str),
-            Self::FailedEdit => "Failed to edit... You can try again or cancel editing with /cancel".to_owned(),
-            Self::CancelEdit => "Canceled editing".to_owned(),
-            Self::FailedCancelEdit => "Failed to canceled editing...".to_owned(),
-            Self::ChoosePauseReminder => "Choose a reminder to pause/resume:".to_owned(),
-            Self::SuccessPause(reminder_str) => format!("⏸ Paused a reminder: {}", reminder_str),
-            Self::SuccessResume(reminder_str) => format!("▶️ Resumed a reminder: {}", reminder_str),
-            Self::FailedPause => "Failed to pause...".to_owned(),
-            Self::Hello => concat!(
-                "Hello! I'm remindee bot. My purpose is to remind you of whatever you ask and ",
-                "whenever you ask.\n\n",
-                "Examples:\n17:30 go to restaurant => notify today at 5:30 PM\n",
-                "01.01 00:00 Happy New Year => notify at 1st of January at 12 AM\n",
-                "55 10 * * 1-5 meeting call => notify at 10:55 AM every weekday ",
-                "(CRON expression format)\n\n",
-                "Before we start, please either send me your location or manually select the timezone using the /settimezone command first."
-            )
-            .to_owned(),
+            Self::TagsListHeader => tr("tags-list-header"),
+            Self::SearchResultsHeader => tr("search-results-header"),
+            Self::NothingFound => tr("nothing-found"),
+            Self::EmptySearchQuery => tr("empty-search-query"),
+            Self::NoTags => tr("no-tags"),
+            Self::SelectTimezone => tr("select-timezone"),
+            Self::ChosenTimezone(tz_name) => tr_with(
+                locale,
+                "chosen-timezone",
+                [("timezone", tz_name.into())],
+            ),
+            Self::NoChosenTimezone => tr("no-chosen-timezone"),
+            Self::FailedSetTimezone(tz_name) => tr_with(
+                locale,
+                "failed-set-timezone",
+                [("timezone", tz_name.into())],
+            ),
+            Self::SelectChatTimezone => tr("select-chat-timezone"),
+            Self::ChatTimezone(tz_name) => {
+                tr_with(locale, "chat-timezone", [("timezone", tz_name.into())])
+            }
+            Self::NotChatAdmin => tr("not-chat-admin"),
+            Self::NoChatTimezone => tr("no-chat-timezone"),
+            Self::ReanchorQuestion(old_tz_name, tz_name) => tr_with(
+                locale,
+                "reanchor-question",
+                [
+                    ("old_timezone", old_tz_name.into()),
+                    ("timezone", tz_name.into()),
+                ],
+            ),
+            Self::Reanchored(reanchor, tz_name) => tr_with(
+                locale,
+                match reanchor {
+                    Reanchor::WallClock => "reanchored-wall-clock",
+                    Reanchor::Instants => "reanchored-instants",
+                },
+                [("timezone", tz_name.into())],
+            ),
+            Self::FailedReanchor => tr("failed-reanchor"),
+            Self::ChooseDeleteReminder => tr("choose-delete-reminder"),
+            Self::SuccessDelete(reminder_str) => {
+                tr_reminder("success-delete", reminder_str)
+            }
+            Self::FailedDelete => tr("failed-delete"),
+            Self::ChooseEditReminder => tr("choose-edit-reminder"),
+            Self::EnterNewReminder => tr("enter-new-reminder"),
+            Self::EnterReminderPart(edit_mode) => tr(match edit_mode {
+                EditMode::Text => "enter-new-text",
+                EditMode::Pattern => "enter-new-pattern",
+                EditMode::Timezone => "enter-new-timezone",
+            }),
+            Self::CopyReminderText => tr("copy-reminder-text"),
+            Self::ReplyToForwarded => tr("reply-to-forwarded"),
+            Self::SuccessEdit(old_reminder_str, reminder_str) => tr_with(
+                locale,
+                "success-edit",
+                [
+                    ("old_reminder", old_reminder_str.into()),
+                    ("reminder", reminder_str.into()),
+                ],
+            ),
+            Self::FailedEdit => tr("failed-edit"),
+            Self::CancelEdit => tr("cancel-edit"),
+            Self::FailedCancelEdit => tr("failed-cancel-edit"),
+            Self::ChoosePauseReminder => tr("choose-pause-reminder"),
+            Self::SuccessPause(reminder_str) => {
+                tr_reminder("success-pause", reminder_str)
+            }
+            Self::SuccessResume(reminder_str) => {
+                tr_reminder("success-resume", reminder_str)
+            }
+            Self::FailedPause => tr("failed-pause"),
+            Self::SuccessBulkDelete(count) => tr_with(
+                locale,
+                "success-bulk-delete",
+                [("count", (*count).into())],
+            ),
+            Self::SuccessBulkPause(count) => tr_with(
+                locale,
+                "success-bulk-pause",
+                [("count", (*count).into())],
+            ),
+            Self::NothingSelected => tr("nothing-selected"),
+            Self::DigestSchedule(time, include_empty) => tr_with(
+                locale,
+                "digest-schedule",
+                [
+                    ("time", time.into()),
+                    ("always", include_empty.to_string().into()),
+                ],
+            ),
+            Self::DigestDisabled => tr("digest-disabled"),
+            Self::DigestUsage => tr("digest-usage"),
+            Self::FailedSetDigest => tr("failed-set-digest"),
+            Self::DigestHeader => tr("digest-header"),
+            Self::EmptyDigest => tr("empty-digest"),
+            Self::SettingsHeader => tr("settings-header"),
+            Self::FailedSetSetting => tr("failed-set-setting"),
+            Self::QuietHours(start, end, silent) => tr_with(
+                locale,
+                "quiet-hours",
+                [
+                    ("start", start.into()),
+                    ("end", end.into()),
+                    ("silent", silent.to_string().into()),
+                ],
+            ),
+            Self::QuietHoursDisabled => tr("quiet-hours-disabled"),
+            Self::QuietHoursUsage => tr("quiet-hours-usage"),
+            Self::FailedSetQuietHours => tr("failed-set-quiet-hours"),
+            Self::ChooseNextReminder => tr("choose-next-reminder"),
+            Self::NextOccurrencesHeader(reminder_ref, reminder_str) => tr_with(
+                locale,
+                "next-occurrences-header",
+                [
+                    ("ref", reminder_ref.into()),
+                    ("reminder", reminder_str.into()),
+                ],
+            ),
+            Self::NextUsage => tr("next-usage"),
+            Self::ReminderNotFound => tr("reminder-not-found"),
+            Self::ReminderDone(name) => {
+                tr_with(locale, "reminder-done", [("name", name.into())])
+            }
+            Self::ReminderSnoozed(name, minutes) => tr_with(
+                locale,
+                "reminder-snoozed",
+                [("name", name.into()), ("minutes", (*minutes).into())],
+            ),
+            Self::NotRecipient => tr("not-recipient"),
+            Self::FailedSnooze => tr("failed-snooze"),
+            Self::HistoryHeader => tr("history-header"),
+            Self::EmptyHistory => tr("empty-history"),
+            Self::SuccessUndo(reminder_str) => {
+                tr_reminder("success-undo", reminder_str)
+            }
+            Self::FailedUndo => tr("failed-undo"),
+            Self::NothingToUndo => tr("nothing-to-undo"),
+            Self::ChosenLanguage => tr("chosen-language"),
+            Self::LanguageUsage => tr("language-usage"),
+            Self::Help => help_text(locale),
+            Self::Hello => tr("hello"),
         }
     }
 }
 
-impl ToString for TgResponse {
-    fn to_string(&self) -> String {
-        escape(&self.to_unescaped_string())
+/// List of the bot's commands with their descriptions in the locale
+fn help_text(locale: Locale) -> String {
+    std::iter::once(i18n::tr(locale, "commands-header") + "\n")
+        .chain(command_descriptions(locale).into_iter().map(|command| {
+            format!("/{} — {}", command.command, command.description)
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Bot's commands with their descriptions in the locale
+pub fn command_descriptions(locale: Locale) -> Vec<BotCommand> {
+    Command::bot_commands()
+        .into_iter()
+        .map(|command| {
+            let name = command.command.trim_start_matches('/');
+            BotCommand::new(name, i18n::tr(locale, &format!("cmd-{}", name)))
+        })
+        .collect()
+}
+
+/// Minutes a delivered reminder can be snoozed for,
+/// the default snooze is chosen from them
+pub const SNOOZE_MINUTES: [i64; 5] = [5, 10, 15, 30, 60];
+
+/// Snooze of a delivered reminder if the user hasn't chosen another one
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 10;
+
+/// Snooze offered along with the default one
+const LONG_SNOOZE_MINUTES: i64 = 60;
+
+/// Keyboard of a delivered reminder to snooze it
+/// for the default time or an hour, or mark it done
+pub fn delivery_markup(
+    locale: Locale,
+    snooze_minutes: i64,
+) -> InlineKeyboardMarkup {
+    let mut snoozes = vec![snooze_minutes];
+    if snooze_minutes != LONG_SNOOZE_MINUTES {
+        snoozes.push(LONG_SNOOZE_MINUTES);
+    }
+    let mut buttons: Vec<_> = snoozes
+        .into_iter()
+        .map(|minutes| {
+            InlineKeyboardButton::new(
+                tr_with(locale, "button-snooze", [("minutes", minutes.into())]),
+                InlineKeyboardButtonKind::CallbackData(format!(
+                    "delivery::snooze::{}",
+                    minutes
+                )),
+            )
+        })
+        .collect();
+    buttons.push(InlineKeyboardButton::new(
+        i18n::tr(locale, "button-done"),
+        InlineKeyboardButtonKind::CallbackData("delivery::done".to_owned()),
+    ));
+    InlineKeyboardMarkup::default().append_row(buttons)
+}
+
+/// Text of a reply in the user's language, escaped for MarkdownV2
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for TgResponse {
+    fn localize(&self, locale: Locale) -> String {
+        escape(&self.to_unescaped_string(locale))
     }
 }
 
+/// Already formatted text
+impl<T: AsRef<str>> Localize for T {
+    fn localize(&self, _locale: Locale) -> String {
+        self.as_ref().to_owned()
+    }
+}
+
+/// Send the message replying to `reply_to` if it's given and still exists
+/// with the keyboard if it's given
 pub async fn _send_message(
     text: &str,
     bot: &Bot,
     user_id: ChatId,
     silent: bool,
-) -> Result<(), RequestError> {
-    bot.send_message(user_id, text)
+    reply_to: Option<MessageId>,
+    markup: Option<InlineKeyboardMarkup>,
+) -> Result<Message, RequestError> {
+    let mut request = bot
+        .send_message(user_id, text)
         .parse_mode(MarkdownV2)
         .disable_web_page_preview(true)
-        .disable_notification(silent)
-        .send()
-        .await
-        .map(|_| ())
+        .disable_notification(silent);
+    request.payload_mut().reply_markup = markup.map(Into::into);
+    match reply_to {
+        Some(msg_id) => {
+            request
+                .reply_to_message_id(msg_id)
+                .allow_sending_without_reply(true)
+                .send()
+                .await
+        }
+        None => request.send().await,
+    }
+}
+
+/// Description of the error copying a deleted message
+const MESSAGE_TO_COPY_NOT_FOUND: &str =
+    "Bad Request: message to copy not found";
+
+/// Copy the message from the chat it's in, `None` if it was deleted
+pub async fn copy_message(
+    bot: &Bot,
+    user_id: ChatId,
+    from_chat_id: ChatId,
+    msg_id: MessageId,
+    silent: bool,
+) -> Result<Option<MessageId>, RequestError> {
+    copied_message_id(
+        bot.copy_message(user_id, from_chat_id, msg_id)
+            .disable_notification(silent)
+            .send()
+            .await,
+    )
+}
+
+/// Id of the copy or `None` if the message to copy was deleted
+fn copied_message_id(
+    result: Result<MessageId, RequestError>,
+) -> Result<Option<MessageId>, RequestError> {
+    match result {
+        Ok(msg_id) => Ok(Some(msg_id)),
+        Err(RequestError::Api(ApiError::MessageToForwardNotFound)) => Ok(None),
+        Err(RequestError::Api(ApiError::Unknown(ref description)))
+            if description == MESSAGE_TO_COPY_NOT_FOUND =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Kind of a file attached to a reminder
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaType {
+    Photo,
+    Document,
+    Voice,
+    Sticker,
+    Video,
+    Audio,
+    Animation,
+}
+
+impl MediaType {
+    const ALL: [Self; 7] = [
+        Self::Photo,
+        Self::Document,
+        Self::Voice,
+        Self::Sticker,
+        Self::Video,
+        Self::Audio,
+        Self::Animation,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Photo => "photo",
+            Self::Document => "document",
+            Self::Voice => "voice",
+            Self::Sticker => "sticker",
+            Self::Video => "video",
+            Self::Audio => "audio",
+            Self::Animation => "animation",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|media_type| media_type.code() == code)
+    }
+}
+
+/// File attached to a reminder, it's sent again with the reminder
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attachment {
+    pub media_type: MediaType,
+    pub file_id: String,
+}
+
+impl Attachment {
+    /// File attached to the message, the largest size of a photo
+    pub fn from_message(msg: &Message) -> Option<Self> {
+        let (media_type, file) = if let Some(photo) = msg.photo() {
+            (MediaType::Photo, &photo.last()?.file)
+        } else if let Some(animation) = msg.animation() {
+            (MediaType::Animation, &animation.file)
+        } else if let Some(document) = msg.document() {
+            (MediaType::Document, &document.file)
+        } else if let Some(voice) = msg.voice() {
+            (MediaType::Voice, &voice.file)
+        } else if let Some(sticker) = msg.sticker() {
+            (MediaType::Sticker, &sticker.file)
+        } else if let Some(video) = msg.video() {
+            (MediaType::Video, &video.file)
+        } else if let Some(audio) = msg.audio() {
+            (MediaType::Audio, &audio.file)
+        } else {
+            return None;
+        };
+        Some(Self {
+            media_type,
+            file_id: file.id.clone(),
+        })
+    }
+
+    /// Attachment stored in a reminder's columns
+    pub fn from_columns(
+        media_type: Option<&str>,
+        file_id: Option<&str>,
+    ) -> Option<Self> {
+        Some(Self {
+            media_type: MediaType::from_code(media_type?)?,
+            file_id: file_id?.to_owned(),
+        })
+    }
+}
+
+/// Send the media request with the caption if there is one,
+/// replying to `reply_to` if it's given and still exists
+/// with the keyboard if it's given
+macro_rules! send_captioned {
+    ($request:expr, $caption:expr, $silent:expr, $reply_to:expr, $markup:expr) => {{
+        let mut request = $request
+            .disable_notification($silent)
+            .allow_sending_without_reply(true);
+        if let Some(caption) = $caption {
+            request = request.caption(caption).parse_mode(MarkdownV2);
+        }
+        request.payload_mut().reply_to_message_id = $reply_to;
+        request.payload_mut().reply_markup = $markup;
+        request.send().await
+    }};
+}
+
+/// Send the attachment with the text as its caption, the text is sent
+/// in a separate message if it doesn't fit into a caption
+/// or the attachment can't have one, the keyboard is attached
+/// to the message with the text
+pub async fn send_attachment(
+    text: &str,
+    attachment: &Attachment,
+    bot: &Bot,
+    user_id: ChatId,
+    silent: bool,
+    reply_to: Option<MessageId>,
+    markup: Option<InlineKeyboardMarkup>,
+) -> Result<Message, RequestError> {
+    let caption = match attachment.media_type {
+        MediaType::Sticker => None,
+        _ => Some(text).filter(|text| text.chars().count() <= MAX_CAPTION_LEN),
+    };
+    let file = InputFile::file_id(&attachment.file_id);
+    let sent_msg = match attachment.media_type {
+        MediaType::Photo => send_captioned!(
+            bot.send_photo(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Document => send_captioned!(
+            bot.send_document(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Voice => send_captioned!(
+            bot.send_voice(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Video => send_captioned!(
+            bot.send_video(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Audio => send_captioned!(
+            bot.send_audio(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Animation => send_captioned!(
+            bot.send_animation(user_id, file),
+            caption,
+            silent,
+            reply_to,
+            caption.and(markup.clone().map(Into::into))
+        ),
+        MediaType::Sticker => {
+            let mut request = bot
+                .send_sticker(user_id, file)
+                .disable_notification(silent)
+                .allow_sending_without_reply(true);
+            request.payload_mut().reply_to_message_id =
+                reply_to.map(|msg_id| msg_id.0);
+            request.send().await
+        }
+    }?;
+    match caption {
+        Some(_) => Ok(sent_msg),
+        None => {
+            _send_message(text, bot, user_id, silent, Some(sent_msg.id), markup)
+                .await
+        }
+    }
 }
 
 pub async fn send_message(
     text: &str,
     bot: &Bot,
     user_id: ChatId,
-) -> Result<(), RequestError> {
-    _send_message(text, bot, user_id, false).await
+) -> Result<Message, RequestError> {
+    _send_message(text, bot, user_id, false, None, None).await
 }
 
 pub async fn send_silent_message(
@@ -112,7 +602,9 @@ pub async fn send_silent_message(
     bot: &Bot,
     user_id: ChatId,
 ) -> Result<(), RequestError> {
-    _send_message(text, bot, user_id, true).await
+    _send_message(text, bot, user_id, true, None, None)
+        .await
+        .map(|_| ())
 }
 
 pub async fn send_markup(
@@ -143,3 +635,87 @@ pub async fn edit_markup(
         .await
         .map(|_| ())
 }
+
+pub async fn edit_text_and_markup(
+    text: &str,
+    markup: InlineKeyboardMarkup,
+    bot: &Bot,
+    msg_id: MessageId,
+    user_id: ChatId,
+) -> Result<(), RequestError> {
+    bot.edit_message_text(user_id, msg_id, text)
+        .parse_mode(MarkdownV2)
+        .disable_web_page_preview(true)
+        .reply_markup(markup)
+        .send()
+        .await
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_command_descriptions() {
+        for locale in Locale::ALL {
+            for command in command_descriptions(locale) {
+                assert!(
+                    !command.description.starts_with("cmd-"),
+                    "{} lacks description of /{}",
+                    locale,
+                    command.command
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_help() {
+        let help = TgResponse::Help.to_unescaped_string(Locale::Ru);
+        assert!(help.starts_with("Команды:\n\n/list — список напоминаний"));
+    }
+
+    #[test]
+    fn test_copied_message_id() {
+        assert_eq!(
+            copied_message_id(Ok(MessageId(2))).unwrap(),
+            Some(MessageId(2))
+        );
+        // The reminder is sent alone if the original was deleted
+        assert_eq!(
+            copied_message_id(Err(RequestError::Api(ApiError::Unknown(
+                MESSAGE_TO_COPY_NOT_FOUND.to_owned()
+            ))))
+            .unwrap(),
+            None
+        );
+        assert_eq!(
+            copied_message_id(Err(RequestError::Api(
+                ApiError::MessageToForwardNotFound
+            )))
+            .unwrap(),
+            None
+        );
+        assert!(copied_message_id(Err(RequestError::Api(
+            ApiError::BotBlocked
+        )))
+        .is_err());
+    }
+
+    #[test]
+    fn test_attachment_from_columns() {
+        for media_type in MediaType::ALL {
+            assert_eq!(
+                Attachment::from_columns(Some(media_type.code()), Some("id")),
+                Some(Attachment {
+                    media_type,
+                    file_id: "id".to_owned()
+                })
+            );
+        }
+        assert_eq!(Attachment::from_columns(Some("poll"), Some("id")), None);
+        assert_eq!(Attachment::from_columns(Some("photo"), None), None);
+        assert_eq!(Attachment::from_columns(None, None), None);
+    }
+}
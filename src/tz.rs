@@ -1,8 +1,15 @@
 use crate::db;
+use crate::entity::{cron_reminder, reminder};
 use crate::err;
+use crate::serializers::{local_to_utc, Pattern};
+use crate::settings;
 
-use chrono_tz::Tz;
-use teloxide::types::UserId;
+use chrono::{NaiveDateTime, TimeZone};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use cron_parser::parse as parse_cron;
+use sea_orm::ActiveValue::Set;
+use serde_json::{from_str, to_string};
+use teloxide::types::{ChatId, UserId};
 use tzf_rs::DefaultFinder;
 
 const TZ_NAMES: &[&str] = &[
@@ -356,10 +363,106 @@ const TZ_NAMES: &[&str] = &[
     "Pacific/Wallis",
 ];
 
+/// Common abbreviations of timezones and the zones they stand for,
+/// summer time abbreviations mean the same zones. Abbreviations shared
+/// by several zones (IST, GST, AST, ADT) or common words (PT, CT, ET, MT)
+/// are left out
+const TZ_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("UTC", "UTC"),
+    ("GMT", "UTC"),
+    ("WET", "WET"),
+    ("WEST", "WET"),
+    ("BST", "Europe/London"),
+    ("CET", "CET"),
+    ("CEST", "CET"),
+    ("EET", "EET"),
+    ("EEST", "EET"),
+    ("MSK", "Europe/Moscow"),
+    ("PKT", "Asia/Karachi"),
+    ("ICT", "Asia/Bangkok"),
+    ("SGT", "Asia/Singapore"),
+    ("HKT", "Asia/Hong_Kong"),
+    ("JST", "Asia/Tokyo"),
+    ("KST", "Asia/Seoul"),
+    ("AWST", "Australia/Perth"),
+    ("ACST", "Australia/Adelaide"),
+    ("ACDT", "Australia/Adelaide"),
+    ("AEST", "Australia/Sydney"),
+    ("AEDT", "Australia/Sydney"),
+    ("NZST", "Pacific/Auckland"),
+    ("NZDT", "Pacific/Auckland"),
+    ("HST", "Pacific/Honolulu"),
+    ("AKST", "America/Anchorage"),
+    ("AKDT", "America/Anchorage"),
+    ("PST", "America/Los_Angeles"),
+    ("PDT", "America/Los_Angeles"),
+    ("MST", "America/Denver"),
+    ("MDT", "America/Denver"),
+    ("CST", "America/Chicago"),
+    ("CDT", "America/Chicago"),
+    ("EST", "America/New_York"),
+    ("EDT", "America/New_York"),
+    ("BRT", "America/Sao_Paulo"),
+];
+
 lazy_static! {
     static ref FINDER: DefaultFinder = DefaultFinder::new();
 }
 
+/// Timezone given in a reminder: an IANA name (`Europe/Berlin`),
+/// an uppercase abbreviation (`CET`) or a whole hours offset (`UTC+3`)
+pub fn parse_timezone(s: &str) -> Option<Tz> {
+    if s.contains('/') {
+        return TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(s))
+            .copied();
+    }
+    if let Some((_, name)) = TZ_ABBREVIATIONS
+        .iter()
+        .find(|(abbreviation, _)| *abbreviation == s)
+    {
+        return name.parse().ok();
+    }
+    let offset = s.strip_prefix("UTC").or_else(|| s.strip_prefix("GMT"))?;
+    let hours: i32 = offset.strip_prefix('+').unwrap_or(offset).parse().ok()?;
+    if !offset.starts_with(['+', '-']) || hours == 0 {
+        return None;
+    }
+    // signs of Etc/GMT zones are inverted
+    format!("Etc/GMT{:+}", -hours).parse().ok()
+}
+
+/// Marker of a timezone abbreviation in a reminder's text
+const TIMEZONE_MARKER: &str = "tz:";
+
+/// Timezone following the time or the cron expression in a reminder's
+/// text: an IANA name with a region (`Europe/Berlin`), UTC or a whole
+/// hours offset (`UTC+3`), or an abbreviation with the explicit marker
+/// (`tz:CET`), so that a word of the description isn't taken for one
+pub fn parse_timezone_word(s: &str) -> Option<Tz> {
+    match s.strip_prefix(TIMEZONE_MARKER) {
+        Some(s) => parse_timezone(s),
+        None if s.contains('/')
+            || s.starts_with("UTC")
+            || s.starts_with("GMT") =>
+        {
+            parse_timezone(s)
+        }
+        None => None,
+    }
+}
+
+/// Timezone written in a reminder's text as [`parse_timezone_word`]
+/// accepts it
+pub fn format_timezone_word(timezone: Tz) -> String {
+    let name = timezone.name();
+    match parse_timezone_word(name) {
+        Some(_) => name.to_owned(),
+        None => format!("{}{}", TIMEZONE_MARKER, name),
+    }
+}
+
 pub fn get_tz_names_for_page_idx(num: usize) -> Option<Vec<&'static str>> {
     TZ_NAMES.chunks(30).nth(num).map(|v| v.to_vec())
 }
@@ -374,6 +477,248 @@ pub async fn get_user_timezone(
         .transpose()
 }
 
+/// Timezone of the reminders in the chat, the one set for a group
+/// or the user's own one if there is none
+pub async fn get_timezone(
+    db: &db::Database,
+    chat_id: ChatId,
+    user_id: UserId,
+) -> Result<Option<Tz>, err::Error> {
+    if !chat_id.is_user() {
+        if let Some(tz_name) =
+            db.get_chat_setting::<settings::Timezone>(chat_id.0).await?
+        {
+            return tz_name.parse::<Tz>().map(Some).map_err(err::Error::Parse);
+        }
+    }
+    get_user_timezone(db, user_id).await
+}
+
+/// What to keep of the user's reminders when their timezone changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reanchor {
+    /// Reminders fire at the same local times in the new timezone
+    WallClock,
+    /// Reminders fire at the same moments as in the old timezone
+    Instants,
+}
+
+impl Reanchor {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::WallClock => "wall",
+            Self::Instants => "instants",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        [Self::WallClock, Self::Instants]
+            .into_iter()
+            .find(|reanchor| reanchor.code() == code)
+    }
+}
+
+/// Reminder following the old timezone moved to the new one
+/// or `None` if it doesn't change: reminders keeping the instants
+/// already have the old timezone in their patterns, and countdowns
+/// and reminders with their own timezone are left as they are
+pub fn reanchor_reminder(
+    rem: reminder::Model,
+    old_timezone: Tz,
+    new_timezone: Tz,
+    reanchor: Reanchor,
+    now: NaiveDateTime,
+) -> Option<reminder::ActiveModel> {
+    if reanchor == Reanchor::Instants {
+        return None;
+    }
+    if let Some(ref s) = rem.pattern {
+        let pattern = from_str::<Pattern>(s).ok()?;
+        if pattern.timezone() != old_timezone
+            || matches!(pattern, Pattern::Countdown(_))
+        {
+            return None;
+        }
+    }
+    move_reminder(rem, old_timezone, new_timezone, now)
+}
+
+/// Reminder firing at the same local times in the new timezone
+pub fn move_reminder(
+    rem: reminder::Model,
+    old_timezone: Tz,
+    new_timezone: Tz,
+    now: NaiveDateTime,
+) -> Option<reminder::ActiveModel> {
+    let mut pattern = match rem.pattern {
+        Some(ref s) => {
+            let mut pattern = from_str::<Pattern>(s).ok()?;
+            pattern.set_timezone(new_timezone);
+            Some(pattern)
+        }
+        None => None,
+    };
+    let local_time = old_timezone.from_utc_datetime(&rem.time).naive_local();
+    // The time skipped in the new timezone is shifted by the gap
+    let mut time = local_to_utc(new_timezone, &local_time)?;
+    if time < now {
+        // Recurring reminders skip the time passed in the new timezone
+        if let Some(next_time) = pattern.as_mut().and_then(|x| x.next(now)) {
+            time = next_time;
+        }
+    }
+    Some(reminder::ActiveModel {
+        time: Set(time),
+        pattern: Set(pattern.and_then(|x| to_string(&x).ok())),
+        ..rem.into()
+    })
+}
+
+/// Cron reminder following the user's timezone re-evaluated in the new
+/// timezone or pinned to the old one, `None` if it has its own timezone
+pub fn reanchor_cron_reminder(
+    cron_rem: cron_reminder::Model,
+    old_timezone: Tz,
+    new_timezone: Tz,
+    reanchor: Reanchor,
+    now: NaiveDateTime,
+) -> Option<cron_reminder::ActiveModel> {
+    if cron_rem.timezone.is_some() {
+        return None;
+    }
+    match reanchor {
+        Reanchor::WallClock => {
+            let time = parse_cron(
+                &cron_rem.cron_expr,
+                &new_timezone.from_utc_datetime(&now),
+            )
+            .ok()?
+            .naive_utc();
+            Some(cron_reminder::ActiveModel {
+                time: Set(time),
+                ..cron_rem.into()
+            })
+        }
+        Reanchor::Instants => Some(cron_reminder::ActiveModel {
+            timezone: Set(Some(old_timezone.name().to_owned())),
+            ..cron_rem.into()
+        }),
+    }
+}
+
 pub fn get_timezone_name_of_location(lng: f64, lat: f64) -> &'static str {
     FINDER.get_tz_name(lng, lat)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::i18n::Locale;
+    use crate::parsers::test::{TEST_TIME, TEST_TIMESTAMP, TEST_TZ};
+    use crate::parsers::{now_time, parse_cron_reminder, parse_reminder};
+    use crate::serializers::OverlapPolicy;
+    use chrono::NaiveDate;
+    use sea_orm::TryIntoModel;
+    use test_case::test_case;
+
+    fn utc(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2007, 2, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    // The reminders are set at 12:30 in Moscow (09:30 UTC)
+    #[test_case("13:00 gym", Reanchor::WallClock, Tz::Europe__Berlin => Some((utc(2, 12), Some(Tz::Europe__Berlin))) ; "wall clock" )]
+    #[test_case("every 1d 13:00 gym", Reanchor::WallClock, Tz::Asia__Tokyo => Some((utc(3, 4), Some(Tz::Asia__Tokyo))) ; "wall clock passed" )]
+    #[test_case("13:00 gym", Reanchor::Instants, Tz::Europe__Berlin => None ; "instants" )]
+    #[test_case("in 1h gym", Reanchor::WallClock, Tz::Europe__Berlin => None ; "countdown" )]
+    #[test_case("13:00 Europe/London gym", Reanchor::WallClock, Tz::Europe__Berlin => None ; "own timezone" )]
+    #[test_case("11.03 02:30 gym", Reanchor::WallClock, Tz::America__New_York => Some((NaiveDate::from_ymd_opt(2007, 3, 11).unwrap().and_hms_opt(7, 30, 0).unwrap(), Some(Tz::America__New_York))) ; "wall clock in gap" )]
+    #[tokio::test]
+    async fn test_reanchor_reminder(
+        s: &str,
+        reanchor: Reanchor,
+        new_timezone: Tz,
+    ) -> Option<(NaiveDateTime, Option<Tz>)> {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let mut rem =
+            parse_reminder(s, 0, 0, *TEST_TZ, OverlapPolicy::Once, Locale::En)
+                .await
+                .unwrap();
+        rem.id = Set(1);
+        let rem = reanchor_reminder(
+            rem.try_into_model().unwrap(),
+            *TEST_TZ,
+            new_timezone,
+            reanchor,
+            now_time(),
+        )?;
+        let pattern = rem
+            .pattern
+            .unwrap()
+            .and_then(|s| from_str::<Pattern>(&s).ok());
+        Some((rem.time.unwrap(), pattern.map(|x| x.timezone())))
+    }
+
+    #[test_case("0 13 * * * gym", Reanchor::WallClock => Some((utc(2, 12), None)) ; "wall clock" )]
+    #[test_case("0 13 * * * gym", Reanchor::Instants => Some((utc(2, 10), Some("Europe/Moscow".to_owned()))) ; "instants" )]
+    #[test_case("0 13 * * * tz:CET gym", Reanchor::Instants => None ; "own timezone" )]
+    #[tokio::test]
+    async fn test_reanchor_cron_reminder(
+        s: &str,
+        reanchor: Reanchor,
+    ) -> Option<(NaiveDateTime, Option<String>)> {
+        unsafe {
+            TEST_TIMESTAMP = TEST_TIME.timestamp();
+        }
+        let mut cron_rem =
+            parse_cron_reminder(s, 0, 0, *TEST_TZ).await.unwrap();
+        cron_rem.id = Set(1);
+        // Set as if it was computed at the test time
+        cron_rem.time = Set(utc(2, 10));
+        let cron_rem = reanchor_cron_reminder(
+            cron_rem.try_into_model().unwrap(),
+            *TEST_TZ,
+            Tz::Europe__Berlin,
+            reanchor,
+            now_time(),
+        )?;
+        Some((cron_rem.time.unwrap(), cron_rem.timezone.unwrap()))
+    }
+
+    #[test_case("Europe/Berlin" => Some(Tz::Europe__Berlin) ; "iana name" )]
+    #[test_case("europe/berlin" => Some(Tz::Europe__Berlin) ; "iana name in lowercase" )]
+    #[test_case("CET" => Some(Tz::CET) ; "abbreviation" )]
+    #[test_case("PDT" => Some(Tz::America__Los_Angeles) ; "summer time abbreviation" )]
+    #[test_case("UTC+3" => Some(Tz::Etc__GMTMinus3) ; "offset" )]
+    #[test_case("GMT-5" => Some(Tz::Etc__GMTPlus5) ; "negative offset" )]
+    #[test_case("UTC+0" => None ; "zero offset" )]
+    #[test_case("cet" => None ; "lowercase abbreviation" )]
+    #[test_case("Japan" => None ; "legacy name" )]
+    #[test_case("Mars/Olympus" => None ; "unknown" )]
+    fn test_parse_timezone(s: &str) -> Option<Tz> {
+        parse_timezone(s)
+    }
+
+    #[test_case("Europe/Berlin" => Some(Tz::Europe__Berlin) ; "iana name" )]
+    #[test_case("UTC" => Some(Tz::UTC) ; "utc" )]
+    #[test_case("UTC-5" => Some(Tz::Etc__GMTPlus5) ; "offset" )]
+    #[test_case("tz:CET" => Some(Tz::CET) ; "marked abbreviation" )]
+    #[test_case("CET" => None ; "abbreviation without marker" )]
+    #[test_case("EST" => None ; "us abbreviation without marker" )]
+    #[test_case("tz:CT" => None ; "ambiguous abbreviation" )]
+    #[test_case("tz:IST" => None ; "abbreviation of several zones" )]
+    fn test_parse_timezone_word(s: &str) -> Option<Tz> {
+        parse_timezone_word(s)
+    }
+
+    #[test_case(Tz::Europe__Berlin => "Europe/Berlin" ; "iana name" )]
+    #[test_case(Tz::UTC => "UTC" ; "utc" )]
+    #[test_case(Tz::CET => "tz:CET" ; "name without region" )]
+    fn test_format_timezone_word(timezone: Tz) -> String {
+        format_timezone_word(timezone)
+    }
+}